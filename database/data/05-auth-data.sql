@@ -9,7 +9,8 @@ INSERT INTO auth.users (username, password, enabled) VALUES
 INSERT INTO auth.roles (name) VALUES
 ('ROLE_EMPLOYEE'),
 ('ROLE_MANAGER'),
-('ROLE_ADMIN');
+('ROLE_ADMIN')
+ON CONFLICT (name) DO NOTHING;
 
 -- USER ↔ ROLE MAPPING
 INSERT INTO auth.user_roles (user_id, role_id)
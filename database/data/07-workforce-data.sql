@@ -0,0 +1,43 @@
+-- DEPARTMENTS
+INSERT INTO public.departments (code, name) VALUES
+('HR',  'Human Resources'),
+('IT',  'Information Technology'),
+('FIN', 'Finance'),
+('SAL', 'Sales'),
+('MKT', 'Marketing'),
+('OPS', 'Operations'),
+('SUP', 'Customer Support'),
+('RND', 'Research & Development')
+ON CONFLICT (code) DO NOTHING;
+
+-- EMPLOYEE ↔ DEPARTMENT, HIRE AND EXIT DATES
+-- Dates are relative to the init date so the dashboard always has a
+-- populated twelve-month trend, hires this month and leavers this month.
+UPDATE public.employee e
+SET department_id = d.id,
+    hire_date     = v.hire_date,
+    exit_date     = v.exit_date
+FROM (VALUES
+    ('lucas@techthordev.com.br',     'IT',  (CURRENT_DATE - INTERVAL '30 months')::date, NULL::date),
+    ('sofia@techthordev.com.br',     'HR',  (CURRENT_DATE - INTERVAL '28 months')::date, NULL::date),
+    ('mateo@techthordev.com.br',     'FIN', (CURRENT_DATE - INTERVAL '26 months')::date, NULL::date),
+    ('valentina@techthordev.com.br', 'SAL', (CURRENT_DATE - INTERVAL '20 months')::date, NULL::date),
+    ('diego@techthordev.com.br',     'IT',  (CURRENT_DATE - INTERVAL '18 months')::date, NULL::date),
+    ('camila@techthordev.com.br',    'MKT', (CURRENT_DATE - INTERVAL '16 months')::date, (CURRENT_DATE - INTERVAL '4 months')::date),
+    ('andres@techthordev.com.br',    'OPS', (CURRENT_DATE - INTERVAL '14 months')::date, NULL::date),
+    ('paula@techthordev.com.br',     'SUP', (CURRENT_DATE - INTERVAL '13 months')::date, NULL::date),
+    ('javier@techthordev.com.br',    'RND', (CURRENT_DATE - INTERVAL '12 months')::date, NULL::date),
+    ('natalia@techthordev.com.br',   'IT',  (CURRENT_DATE - INTERVAL '11 months')::date, NULL::date),
+    ('bruno@techthordev.com.br',     'SAL', (CURRENT_DATE - INTERVAL '10 months')::date, (CURRENT_DATE - INTERVAL '2 months')::date),
+    ('mariana@techthordev.com.br',   'HR',  (CURRENT_DATE - INTERVAL '9 months')::date,  NULL::date),
+    ('rafael@techthordev.com.br',    'FIN', (CURRENT_DATE - INTERVAL '8 months')::date,  NULL::date),
+    ('fernanda@techthordev.com.br',  'MKT', (CURRENT_DATE - INTERVAL '7 months')::date,  NULL::date),
+    ('thiago@techthordev.com.br',    'OPS', (CURRENT_DATE - INTERVAL '6 months')::date,  NULL::date),
+    ('beatriz@techthordev.com.br',   'SUP', (CURRENT_DATE - INTERVAL '5 months')::date,  date_trunc('month', CURRENT_DATE)::date),
+    ('gabriel@techthordev.com.br',   'RND', (CURRENT_DATE - INTERVAL '4 months')::date,  NULL::date),
+    ('larissa@techthordev.com.br',   'IT',  (CURRENT_DATE - INTERVAL '3 months')::date,  NULL::date),
+    ('eduardo@techthordev.com.br',   'SAL', (CURRENT_DATE - INTERVAL '1 months')::date,  NULL::date),
+    ('renata@techthordev.com.br',    'IT',  date_trunc('month', CURRENT_DATE)::date,     NULL::date)
+) AS v (email, dept_code, hire_date, exit_date)
+JOIN public.departments d ON d.code = v.dept_code
+WHERE e.email = v.email;
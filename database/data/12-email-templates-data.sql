@@ -0,0 +1,27 @@
+-- ==================================================
+-- DEFAULT EMAIL TEMPLATES
+-- ==================================================
+
+INSERT INTO public.email_templates (key, description, subject, body, variables) VALUES
+(
+    'employee.welcome',
+    'Sent to a new employee after they were added',
+    'Welcome to the team, {{first_name}}!',
+    E'Hello {{first_name}} {{last_name}},\n\nwelcome aboard! Your employee record has been created with the address {{email}}.\n\nIf any of your details are wrong, please let HR know.\n\nBest regards,\nHR',
+    ARRAY['first_name', 'last_name', 'email']
+),
+(
+    'leave.approved',
+    'Sent to an employee when their leave request was approved',
+    'Your leave from {{start_date}} to {{end_date}} was approved',
+    E'Hello {{first_name}},\n\nyour leave from {{start_date}} to {{end_date}} has been approved by {{approved_by}}.\n\nEnjoy your time off!\n\nHR',
+    ARRAY['first_name', 'start_date', 'end_date', 'approved_by']
+),
+(
+    'password.reset',
+    'Sent when a user asks to reset their password',
+    'Reset your EMS password',
+    E'Hello {{username}},\n\nsomeone (hopefully you) asked to reset your password. Open the link below to choose a new one:\n\n{{reset_url}}\n\nThe link is valid for {{expires_in_minutes}} minutes and can be used once. If you did not ask for this, you can ignore this mail.\n\nEMS',
+    ARRAY['username', 'reset_url', 'expires_in_minutes']
+)
+ON CONFLICT (key) DO NOTHING;
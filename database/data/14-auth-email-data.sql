@@ -0,0 +1,8 @@
+-- ==========================================
+-- EMAIL ADDRESSES FOR SAMPLE USERS
+-- ==========================================
+
+UPDATE auth.users SET email = 'john@ems.local'  WHERE username = 'john';
+UPDATE auth.users SET email = 'mary@ems.local'  WHERE username = 'mary';
+UPDATE auth.users SET email = 'susan@ems.local' WHERE username = 'susan';
+UPDATE auth.users SET email = 'admin@ems.local' WHERE username = 'admin';
@@ -0,0 +1,12 @@
+-- ==================================================
+-- REQUIRED ROLES
+-- ==================================================
+-- The application checks for these names. 05-auth-data.sql creates them along
+-- with the sample users; this script makes sure a database without sample
+-- data has them too.
+
+INSERT INTO auth.roles (name) VALUES
+('ROLE_EMPLOYEE'),
+('ROLE_MANAGER'),
+('ROLE_ADMIN')
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,36 @@
+-- ==================================================
+-- WORKFORCE SCHEMA (public)
+-- ==================================================
+
+-- ------------------
+-- DEPARTMENTS
+-- ------------------
+CREATE TABLE IF NOT EXISTS public.departments (
+    id   BIGSERIAL PRIMARY KEY,
+    code VARCHAR(10) NOT NULL UNIQUE,
+    name VARCHAR(60) NOT NULL UNIQUE
+);
+
+ALTER TABLE public.departments OWNER TO rustconnector;
+
+-- ------------------
+-- EMPLOYEE LIFECYCLE
+-- ------------------
+-- hire_date defaults to the insert date so rows created through
+-- add_employee count as new hires without any client changes.
+-- exit_date stays NULL while the employee is active.
+ALTER TABLE public.employee
+    ADD COLUMN IF NOT EXISTS department_id BIGINT
+        REFERENCES public.departments (id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS hire_date DATE NOT NULL DEFAULT CURRENT_DATE,
+    ADD COLUMN IF NOT EXISTS exit_date DATE;
+
+ALTER TABLE public.employee
+    DROP CONSTRAINT IF EXISTS chk_employee_exit_after_hire;
+ALTER TABLE public.employee
+    ADD CONSTRAINT chk_employee_exit_after_hire
+        CHECK (exit_date IS NULL OR exit_date >= hire_date);
+
+CREATE INDEX IF NOT EXISTS idx_employee_department ON public.employee (department_id);
+CREATE INDEX IF NOT EXISTS idx_employee_hire_date  ON public.employee (hire_date);
+CREATE INDEX IF NOT EXISTS idx_employee_exit_date  ON public.employee (exit_date);
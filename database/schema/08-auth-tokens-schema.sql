@@ -0,0 +1,44 @@
+-- ==========================================
+-- AUTH SESSIONS & API TOKENS
+-- ==========================================
+
+-- ------------------
+-- BROWSER SESSIONS
+-- ------------------
+-- The cookie carries the raw session token, only its SHA-256 hash is stored.
+CREATE TABLE IF NOT EXISTS auth.sessions (
+    id         BIGSERIAL PRIMARY KEY,
+    user_id    BIGINT      NOT NULL REFERENCES auth.users (id) ON DELETE CASCADE,
+    token_hash CHAR(64)    NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_user ON auth.sessions (user_id);
+
+-- ------------------
+-- API TOKENS
+-- ------------------
+-- Bearer tokens for scripts and services. A token acts as its user, limited
+-- to the listed scopes. Like sessions, only the SHA-256 hash is stored;
+-- token_prefix is kept in clear text so admins can recognise a token.
+CREATE TABLE IF NOT EXISTS auth.api_tokens (
+    id           BIGSERIAL PRIMARY KEY,
+    user_id      BIGINT       NOT NULL REFERENCES auth.users (id) ON DELETE CASCADE,
+    name         VARCHAR(100) NOT NULL,
+    kind         VARCHAR(10)  NOT NULL CHECK (kind IN ('personal', 'service')),
+    token_prefix VARCHAR(12)  NOT NULL,
+    token_hash   CHAR(64)     NOT NULL UNIQUE,
+    scopes       TEXT[]       NOT NULL DEFAULT '{}',
+    created_by   BIGINT       REFERENCES auth.users (id) ON DELETE SET NULL,
+    created_at   TIMESTAMPTZ  NOT NULL DEFAULT now(),
+    expires_at   TIMESTAMPTZ  NOT NULL,
+    last_used_at TIMESTAMPTZ,
+    revoked_at   TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_tokens_user ON auth.api_tokens (user_id);
+
+-- Ownership
+ALTER TABLE auth.sessions OWNER TO rustconnector;
+ALTER TABLE auth.api_tokens OWNER TO rustconnector;
@@ -0,0 +1,54 @@
+-- ==================================================
+-- OUTGOING WEBHOOKS (public)
+-- ==================================================
+
+-- ------------------
+-- SUBSCRIPTIONS
+-- ------------------
+-- `secret` signs every payload (HMAC-SHA256), receivers verify it.
+CREATE TABLE IF NOT EXISTS public.webhook_subscriptions (
+    id         BIGSERIAL PRIMARY KEY,
+    name       VARCHAR(100) NOT NULL,
+    url        TEXT         NOT NULL,
+    secret     VARCHAR(64)  NOT NULL,
+    events     TEXT[]       NOT NULL DEFAULT '{}',
+    enabled    BOOLEAN      NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ  NOT NULL DEFAULT now()
+);
+
+-- ------------------
+-- DELIVERIES
+-- ------------------
+-- One row per (event, subscription). Each is sent by a `webhook.deliver` job,
+-- the job queue (10-jobs-schema) owns locking and retry scheduling; the row
+-- only records where the delivery stands.
+CREATE TABLE IF NOT EXISTS public.webhook_deliveries (
+    id              BIGSERIAL PRIMARY KEY,
+    subscription_id BIGINT      NOT NULL REFERENCES public.webhook_subscriptions (id) ON DELETE CASCADE,
+    event           VARCHAR(50) NOT NULL,
+    payload         JSONB       NOT NULL,
+    status          VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'succeeded', 'failed')),
+    attempts        INT         NOT NULL DEFAULT 0,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- ------------------
+-- DELIVERY LOG
+-- ------------------
+CREATE TABLE IF NOT EXISTS public.webhook_delivery_attempts (
+    id              BIGSERIAL PRIMARY KEY,
+    delivery_id     BIGINT      NOT NULL REFERENCES public.webhook_deliveries (id) ON DELETE CASCADE,
+    attempt         INT         NOT NULL,
+    attempted_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    response_status INT,
+    error           TEXT,
+    duration_ms     INT         NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_attempts_delivery ON public.webhook_delivery_attempts (delivery_id);
+
+-- Ownership
+ALTER TABLE public.webhook_subscriptions OWNER TO rustconnector;
+ALTER TABLE public.webhook_deliveries OWNER TO rustconnector;
+ALTER TABLE public.webhook_delivery_attempts OWNER TO rustconnector;
@@ -0,0 +1,28 @@
+-- ==================================================
+-- BACKGROUND JOBS / TRANSACTIONAL OUTBOX (public)
+-- ==================================================
+
+-- Jobs are inserted in the same transaction as the change that caused them
+-- (outbox pattern) and picked up by the worker pool with
+-- SELECT ... FOR UPDATE SKIP LOCKED. Jobs that exhaust max_attempts are
+-- dead-lettered (status 'dead') and kept for inspection.
+CREATE TABLE IF NOT EXISTS public.jobs (
+    id           BIGSERIAL PRIMARY KEY,
+    kind         VARCHAR(50)  NOT NULL,
+    payload      JSONB        NOT NULL DEFAULT '{}',
+    status       VARCHAR(20)  NOT NULL DEFAULT 'queued'
+        CHECK (status IN ('queued', 'running', 'succeeded', 'dead')),
+    attempts     INT          NOT NULL DEFAULT 0,
+    max_attempts INT          NOT NULL DEFAULT 5,
+    run_at       TIMESTAMPTZ  NOT NULL DEFAULT now(),
+    locked_at    TIMESTAMPTZ,
+    locked_by    VARCHAR(100),
+    last_error   TEXT,
+    created_at   TIMESTAMPTZ  NOT NULL DEFAULT now(),
+    updated_at   TIMESTAMPTZ  NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_jobs_due ON public.jobs (run_at, id) WHERE status = 'queued';
+CREATE INDEX IF NOT EXISTS idx_jobs_status ON public.jobs (status);
+
+ALTER TABLE public.jobs OWNER TO rustconnector;
@@ -0,0 +1,17 @@
+-- ==================================================
+-- EMAIL NOTIFICATION TEMPLATES (public)
+-- ==================================================
+
+-- One row per notification. Subject and body are editable by admins and may
+-- use {{variable}} placeholders from `variables`, which the sending code
+-- fills in. Mails themselves are sent through 'email.send' jobs.
+CREATE TABLE IF NOT EXISTS public.email_templates (
+    key         VARCHAR(50)  PRIMARY KEY,
+    description VARCHAR(200) NOT NULL,
+    subject     VARCHAR(200) NOT NULL,
+    body        TEXT         NOT NULL,
+    variables   TEXT[]       NOT NULL DEFAULT '{}',
+    updated_at  TIMESTAMPTZ  NOT NULL DEFAULT now()
+);
+
+ALTER TABLE public.email_templates OWNER TO rustconnector;
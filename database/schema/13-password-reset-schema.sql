@@ -0,0 +1,26 @@
+-- ==========================================
+-- PASSWORD RESET
+-- ==========================================
+
+-- Reset links are mailed, so users need an address
+ALTER TABLE auth.users ADD COLUMN IF NOT EXISTS email VARCHAR(100) UNIQUE;
+ALTER TABLE auth.users ADD COLUMN IF NOT EXISTS password_changed_at TIMESTAMPTZ;
+
+-- ------------------
+-- RESET TOKENS
+-- ------------------
+-- Single use and short lived. As with sessions only the SHA-256 hash of the
+-- token in the mailed link is stored.
+CREATE TABLE IF NOT EXISTS auth.password_reset_tokens (
+    id         BIGSERIAL PRIMARY KEY,
+    user_id    BIGINT      NOT NULL REFERENCES auth.users (id) ON DELETE CASCADE,
+    token_hash CHAR(64)    NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at    TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_password_reset_tokens_user ON auth.password_reset_tokens (user_id);
+
+-- Ownership
+ALTER TABLE auth.password_reset_tokens OWNER TO rustconnector;
@@ -0,0 +1,27 @@
+-- ==========================================
+-- LOGIN BRUTE-FORCE PROTECTION
+-- ==========================================
+
+-- Every login attempt, used to throttle per username and per client IP.
+-- Usernames are stored lower-cased and need not exist in auth.users.
+CREATE TABLE IF NOT EXISTS auth.login_attempts (
+    id           BIGSERIAL PRIMARY KEY,
+    username     VARCHAR(50) NOT NULL,
+    ip           VARCHAR(45) NOT NULL,
+    succeeded    BOOLEAN     NOT NULL,
+    attempted_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_login_attempts_username ON auth.login_attempts (username, attempted_at);
+CREATE INDEX IF NOT EXISTS idx_login_attempts_ip ON auth.login_attempts (ip, attempted_at);
+
+-- Consecutive failures and a temporary lock. Independent of `enabled`:
+-- a disabled account stays disabled when its lock expires or is lifted.
+-- The counter starts over after a quiet period since the last failure.
+ALTER TABLE auth.users ADD COLUMN IF NOT EXISTS failed_logins INT NOT NULL DEFAULT 0;
+ALTER TABLE auth.users ADD COLUMN IF NOT EXISTS last_failed_login_at TIMESTAMPTZ;
+ALTER TABLE auth.users ADD COLUMN IF NOT EXISTS locked_until TIMESTAMPTZ;
+ALTER TABLE auth.users ADD COLUMN IF NOT EXISTS last_login_at TIMESTAMPTZ;
+
+-- Ownership
+ALTER TABLE auth.login_attempts OWNER TO rustconnector;
@@ -0,0 +1,55 @@
+-- ==========================================
+-- TWO-FACTOR AUTHENTICATION (TOTP)
+-- ==========================================
+
+-- ------------------
+-- TOTP SECRETS
+-- ------------------
+-- One authenticator per user. The row exists from the start of enrollment,
+-- 2FA is only active once confirmed_at is set. last_used_step blocks replaying
+-- a code within its 30 second window.
+CREATE TABLE IF NOT EXISTS auth.user_totp (
+    user_id        BIGINT      PRIMARY KEY REFERENCES auth.users (id) ON DELETE CASCADE,
+    secret         VARCHAR(64) NOT NULL,
+    confirmed_at   TIMESTAMPTZ,
+    last_used_step BIGINT      NOT NULL DEFAULT 0,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- ------------------
+-- RECOVERY CODES
+-- ------------------
+-- Single use, stored as SHA-256 hashes like all other tokens.
+CREATE TABLE IF NOT EXISTS auth.recovery_codes (
+    id        BIGSERIAL PRIMARY KEY,
+    user_id   BIGINT      NOT NULL REFERENCES auth.users (id) ON DELETE CASCADE,
+    code_hash CHAR(64)    NOT NULL,
+    used_at   TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_recovery_codes_user ON auth.recovery_codes (user_id);
+
+-- ------------------
+-- LOGIN CHALLENGES
+-- ------------------
+-- Issued after a correct password when a second step is needed: either
+-- entering a code ('verify') or setting up 2FA first ('enroll').
+CREATE TABLE IF NOT EXISTS auth.login_challenges (
+    id         BIGSERIAL PRIMARY KEY,
+    user_id    BIGINT      NOT NULL REFERENCES auth.users (id) ON DELETE CASCADE,
+    token_hash CHAR(64)    NOT NULL UNIQUE,
+    purpose    VARCHAR(10) NOT NULL CHECK (purpose IN ('verify', 'enroll')),
+    attempts   INT         NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+-- ------------------
+-- ROLE POLICY
+-- ------------------
+-- Users with at least one such role must set up 2FA before they can sign in.
+ALTER TABLE auth.roles ADD COLUMN IF NOT EXISTS require_two_factor BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Ownership
+ALTER TABLE auth.user_totp OWNER TO rustconnector;
+ALTER TABLE auth.recovery_codes OWNER TO rustconnector;
+ALTER TABLE auth.login_challenges OWNER TO rustconnector;
@@ -0,0 +1,37 @@
+-- ==========================================
+-- OPENID CONNECT SINGLE SIGN-ON
+-- ==========================================
+
+-- ------------------
+-- LINKED IDENTITIES
+-- ------------------
+-- An IdP account (issuer + subject) signs in as a local user. Users created
+-- just-in-time by SSO get an unusable password hash.
+CREATE TABLE IF NOT EXISTS auth.user_identities (
+    id            BIGSERIAL PRIMARY KEY,
+    user_id       BIGINT       NOT NULL REFERENCES auth.users (id) ON DELETE CASCADE,
+    issuer        VARCHAR(255) NOT NULL,
+    subject       VARCHAR(255) NOT NULL,
+    email         VARCHAR(100),
+    created_at    TIMESTAMPTZ  NOT NULL DEFAULT now(),
+    last_login_at TIMESTAMPTZ,
+    UNIQUE (issuer, subject)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_identities_user ON auth.user_identities (user_id);
+
+-- ------------------
+-- PENDING LOGINS
+-- ------------------
+-- State, nonce and PKCE verifier between the redirect to the IdP and the
+-- callback. Rows are deleted when used and expire after a few minutes.
+CREATE TABLE IF NOT EXISTS auth.oidc_login_states (
+    state_hash    CHAR(64)     PRIMARY KEY,
+    nonce         VARCHAR(64)  NOT NULL,
+    code_verifier VARCHAR(128) NOT NULL,
+    expires_at    TIMESTAMPTZ  NOT NULL
+);
+
+-- Ownership
+ALTER TABLE auth.user_identities OWNER TO rustconnector;
+ALTER TABLE auth.oidc_login_states OWNER TO rustconnector;
@@ -0,0 +1,36 @@
+-- ==========================================
+-- LDAP DIRECTORY SYNC
+-- ==========================================
+
+-- ------------------
+-- EMPLOYEE ↔ DIRECTORY
+-- ------------------
+-- Employees are matched to directory entries by email. ldap_dn is set once an
+-- employee was seen in the directory; ldap_missing_since flags people who
+-- disappeared from it since. Nothing is deleted by the sync.
+ALTER TABLE public.employee
+    ADD COLUMN IF NOT EXISTS ldap_dn            VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS ldap_synced_at     TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS ldap_missing_since TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_employee_ldap_missing
+    ON public.employee (ldap_missing_since) WHERE ldap_missing_since IS NOT NULL;
+
+-- ------------------
+-- SYNC HISTORY
+-- ------------------
+CREATE TABLE IF NOT EXISTS public.ldap_sync_runs (
+    id           BIGSERIAL PRIMARY KEY,
+    triggered_by VARCHAR(50) NOT NULL,
+    status       VARCHAR(16) NOT NULL DEFAULT 'running'
+        CHECK (status IN ('running', 'succeeded', 'failed')),
+    created      INT         NOT NULL DEFAULT 0,
+    updated      INT         NOT NULL DEFAULT 0,
+    missing      INT         NOT NULL DEFAULT 0,
+    returned     INT         NOT NULL DEFAULT 0,
+    error        TEXT,
+    started_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    finished_at  TIMESTAMPTZ
+);
+
+ALTER TABLE public.ldap_sync_runs OWNER TO rustconnector;
@@ -0,0 +1,18 @@
+-- ==================================================
+-- EMPLOYEE UNDO (public)
+-- ==================================================
+
+-- ------------------
+-- DELETED EMPLOYEES
+-- ------------------
+-- Every delete keeps the whole row (all columns as JSON) here for a few
+-- minutes, so undoing it restores the same id, hire date, department, exit
+-- date and directory link. Each delete drops the entries that are too old.
+CREATE TABLE IF NOT EXISTS public.deleted_employees (
+    id         BIGINT      PRIMARY KEY,
+    data       JSONB       NOT NULL,
+    deleted_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Ownership
+ALTER TABLE public.deleted_employees OWNER TO rustconnector;
@@ -0,0 +1,22 @@
+-- ==================================================
+-- LOCAL EMPLOYEE DATABASE (SQLite, desktop offline mode)
+-- ==================================================
+-- Same columns and limits as public.employee in 02-default-schema.sql, so the
+-- rows can be exported and imported into Postgres unchanged.
+-- Applied by the desktop app on every start.
+
+CREATE TABLE IF NOT EXISTS employee (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    first_name TEXT NOT NULL CHECK (length(first_name) <= 45),
+    last_name  TEXT NOT NULL CHECK (length(last_name) <= 45),
+    email      TEXT NOT NULL UNIQUE CHECK (length(email) <= 100)
+);
+
+-- Deleted rows kept for a few minutes, so a delete can be undone with the same id
+CREATE TABLE IF NOT EXISTS deleted_employee (
+    id         INTEGER PRIMARY KEY,
+    first_name TEXT NOT NULL,
+    last_name  TEXT NOT NULL,
+    email      TEXT NOT NULL,
+    deleted_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
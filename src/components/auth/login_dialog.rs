@@ -0,0 +1,251 @@
+use dioxus::prelude::*;
+
+use super::totp_enrollment::TotpEnrollmentPanel;
+use super::use_auth;
+use crate::models::auth::LoginRequest;
+use crate::models::two_factor::LoginResult;
+use crate::server::password::RESET_TOKEN_TTL_MINUTES;
+
+/// Where the dialog is after the password (or the IdP) was accepted.
+#[derive(Clone, PartialEq)]
+pub enum SecondStep {
+    Code { challenge: String },
+    Enroll { challenge: String },
+}
+
+/// `resume` opens the dialog at the second step, for SSO logins that still need local 2FA.
+#[component]
+pub fn LoginDialog(on_close: EventHandler<()>, #[props(default)] resume: Option<SecondStep>) -> Element {
+    let mut auth = use_auth();
+    let mut username = use_signal(String::new);
+    let mut password = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut pending = use_signal(|| false);
+
+    // "Forgot password?" switches the dialog to requesting a reset link
+    let mut forgot = use_signal(|| false);
+    let mut reset_requested = use_signal(|| false);
+
+    // Second factor after a correct password
+    let mut second_step = use_signal(move || resume);
+    let mut code = use_signal(String::new);
+
+    // Offered next to the password form when an IdP is configured
+    let sso = use_resource(|| async move { crate::server::oidc::sso_provider().await.ok().flatten() });
+
+    rsx! {
+        div {
+            class: "fixed inset-0 bg-black/60 flex items-center justify-center z-50",
+            onclick: move |_| on_close.call(()),
+
+            div {
+                class: "bg-white text-slate-900 p-8 rounded-lg shadow-2xl max-w-sm w-full",
+                // Prevents closing the dialog when clicking inside the white box
+                onclick: |e| e.stop_propagation(),
+
+                if forgot() {
+                    h2 { class: "text-2xl font-bold mb-4", "Reset password" }
+
+                    if reset_requested() {
+                        p { class: "text-sm text-slate-600",
+                            "If an account with an email address matches, a reset link is on its way. "
+                            "It is valid for {RESET_TOKEN_TTL_MINUTES} minutes."
+                        }
+                    } else {
+                        form {
+                            class: "flex flex-col gap-4",
+                            onsubmit: move |e| async move {
+                                e.prevent_default();
+                                pending.set(true);
+                                error.set(None);
+                                match crate::server::password::request_password_reset(username()).await {
+                                    Ok(()) => reset_requested.set(true),
+                                    Err(e) => error.set(Some(e.to_string())),
+                                }
+                                pending.set(false);
+                            },
+
+                            div { class: "flex flex-col gap-1",
+                                label { class: "text-sm font-medium text-slate-700", "Username or email" }
+                                input {
+                                    class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                    autocomplete: "username",
+                                    value: "{username}",
+                                    oninput: move |e| username.set(e.value())
+                                }
+                            }
+
+                            if let Some(message) = error() {
+                                span { class: "text-sm text-red-600", "{message}" }
+                            }
+
+                            button {
+                                class: "w-full bg-blue-600 text-white py-2 rounded hover:bg-blue-700 transition font-medium disabled:opacity-50 disabled:cursor-not-allowed",
+                                r#type: "submit",
+                                disabled: pending() || username().trim().is_empty(),
+                                if pending() { "Sending..." } else { "Send reset link" }
+                            }
+                        }
+                    }
+
+                    button {
+                        class: "mt-4 text-sm text-blue-600 hover:underline",
+                        onclick: move |_| {
+                            forgot.set(false);
+                            reset_requested.set(false);
+                            error.set(None);
+                        },
+                        "Back to login"
+                    }
+                } else if let Some(SecondStep::Code { challenge }) = second_step() {
+                    h2 { class: "text-2xl font-bold mb-4", "Two-factor authentication" }
+
+                    form {
+                        class: "flex flex-col gap-4",
+                        onsubmit: move |e| {
+                            let challenge = challenge.clone();
+                            async move {
+                                e.prevent_default();
+                                pending.set(true);
+                                error.set(None);
+                                match crate::server::two_factor::verify_second_factor(challenge, code()).await {
+                                    Ok(user) => {
+                                        auth.user.set(Some(user));
+                                        on_close.call(());
+                                    }
+                                    Err(ServerFnError::ServerError { message, .. }) => error.set(Some(message)),
+                                    Err(_) => error.set(Some("Login failed, please try again".to_string())),
+                                }
+                                code.set(String::new());
+                                pending.set(false);
+                            }
+                        },
+
+                        div { class: "flex flex-col gap-1",
+                            label { class: "text-sm font-medium text-slate-700", "Code from your authenticator app" }
+                            input {
+                                class: "border rounded-lg px-4 py-2 text-center font-mono tracking-widest focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                autocomplete: "one-time-code",
+                                autofocus: true,
+                                placeholder: "123456",
+                                value: "{code}",
+                                oninput: move |e| code.set(e.value())
+                            }
+                            span { class: "text-xs text-slate-500", "Lost your device? Enter one of your recovery codes instead." }
+                        }
+
+                        if let Some(message) = error() {
+                            span { class: "text-sm text-red-600", "{message}" }
+                        }
+
+                        button {
+                            class: "w-full bg-blue-600 text-white py-2 rounded hover:bg-blue-700 transition font-medium disabled:opacity-50 disabled:cursor-not-allowed",
+                            r#type: "submit",
+                            disabled: pending() || code().trim().is_empty(),
+                            if pending() { "Verifying..." } else { "Verify" }
+                        }
+                    }
+                } else if let Some(SecondStep::Enroll { challenge }) = second_step() {
+                    h2 { class: "text-2xl font-bold mb-2", "Set up two-factor authentication" }
+                    p { class: "text-sm text-slate-600 mb-4", "Your role requires a second factor before you can sign in." }
+
+                    TotpEnrollmentPanel {
+                        challenge: Some(challenge),
+                        on_done: move |user| {
+                            if let Some(user) = user {
+                                auth.user.set(Some(user));
+                            }
+                            on_close.call(());
+                        }
+                    }
+                } else {
+                    h2 { class: "text-2xl font-bold mb-4", "Login" }
+
+                    form {
+                        class: "flex flex-col gap-4",
+                        onsubmit: move |e| async move {
+                            e.prevent_default();
+                            pending.set(true);
+                            error.set(None);
+                            let req = LoginRequest { username: username(), password: password() };
+                            match crate::server::auth::login(req).await {
+                                Ok(LoginResult::SignedIn { user }) => {
+                                    auth.user.set(Some(user));
+                                    password.set(String::new());
+                                    on_close.call(());
+                                }
+                                Ok(LoginResult::SecondFactorRequired { challenge }) => {
+                                    password.set(String::new());
+                                    second_step.set(Some(SecondStep::Code { challenge }));
+                                }
+                                Ok(LoginResult::EnrollmentRequired { challenge }) => {
+                                    password.set(String::new());
+                                    second_step.set(Some(SecondStep::Enroll { challenge }));
+                                }
+                                // Wrong credentials, throttling (429) or a locked account (423)
+                                Err(ServerFnError::ServerError { message, .. }) => error.set(Some(message)),
+                                Err(_) => error.set(Some("Login failed, please try again".to_string())),
+                            }
+                            pending.set(false);
+                        },
+
+                        div { class: "flex flex-col gap-1",
+                            label { class: "text-sm font-medium text-slate-700", "Username" }
+                            input {
+                                class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                autocomplete: "username",
+                                value: "{username}",
+                                oninput: move |e| username.set(e.value())
+                            }
+                        }
+
+                        div { class: "flex flex-col gap-1",
+                            label { class: "text-sm font-medium text-slate-700", "Password" }
+                            input {
+                                class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                r#type: "password",
+                                autocomplete: "current-password",
+                                value: "{password}",
+                                oninput: move |e| password.set(e.value())
+                            }
+                        }
+
+                        if let Some(message) = error() {
+                            span { class: "text-sm text-red-600", "{message}" }
+                        }
+
+                        button {
+                            class: "w-full bg-blue-600 text-white py-2 rounded hover:bg-blue-700 transition font-medium disabled:opacity-50 disabled:cursor-not-allowed",
+                            r#type: "submit",
+                            disabled: pending() || username().trim().is_empty() || password().is_empty(),
+                            if pending() { "Signing in..." } else { "Sign in" }
+                        }
+                    }
+
+                    if let Some(Some(provider)) = sso() {
+                        div { class: "flex items-center gap-3 my-4 text-xs text-slate-400",
+                            div { class: "flex-1 border-t" }
+                            "or"
+                            div { class: "flex-1 border-t" }
+                        }
+                        // A full page load: the IdP redirects back with the session cookie set
+                        a {
+                            class: "block w-full text-center border border-slate-300 py-2 rounded hover:bg-slate-50 transition font-medium",
+                            href: "{provider.login_url}",
+                            "Sign in with {provider.name}"
+                        }
+                    }
+
+                    button {
+                        class: "mt-4 text-sm text-blue-600 hover:underline",
+                        onclick: move |_| {
+                            forgot.set(true);
+                            error.set(None);
+                        },
+                        "Forgot password?"
+                    }
+                }
+            }
+        }
+    }
+}
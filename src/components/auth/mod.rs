@@ -0,0 +1,43 @@
+pub mod login_dialog;
+pub mod new_password_fields;
+pub mod totp_enrollment;
+
+use dioxus::prelude::*;
+
+use crate::models::auth::CurrentUser;
+
+/// Signed-in user shared through the context, `None` while anonymous.
+#[derive(Clone, Copy, PartialEq)]
+pub struct AuthState {
+    pub user: Signal<Option<CurrentUser>>,
+}
+
+impl AuthState {
+    pub fn is_admin(&self) -> bool {
+        self.user
+            .read()
+            .as_ref()
+            .is_some_and(|u| u.has_role(crate::models::auth::ROLE_ADMIN))
+    }
+}
+
+/// Provides the [`AuthState`] context and loads the session user once on start;
+/// offline it stays anonymous.
+pub fn use_auth_provider() -> AuthState {
+    let state = use_context_provider(|| AuthState { user: Signal::new(None) });
+
+    // The offline desktop build has no server and no login
+    #[cfg(not(feature = "offline"))]
+    use_future(move || async move {
+        let mut user = state.user;
+        if let Ok(current) = crate::server::auth::current_user().await {
+            user.set(current);
+        }
+    });
+
+    state
+}
+
+pub fn use_auth() -> AuthState {
+    use_context::<AuthState>()
+}
@@ -0,0 +1,51 @@
+use dioxus::prelude::*;
+
+use crate::models::auth::password_policy_errors;
+
+/// True once `password` passes the policy and matches `confirm`.
+pub fn new_password_ready(password: &str, confirm: &str, username: &str) -> bool {
+    password == confirm && password_policy_errors(password, username).is_empty()
+}
+
+/// New password + confirmation with the policy checked while typing.
+#[component]
+pub fn NewPasswordFields(
+    password: Signal<String>,
+    confirm: Signal<String>,
+    #[props(default)] username: String,
+) -> Element {
+    let errors = password_policy_errors(&password(), &username);
+    let mismatch = !confirm().is_empty() && password() != confirm();
+
+    rsx! {
+        div { class: "flex flex-col gap-1",
+            label { class: "text-sm font-medium text-slate-700", "New password" }
+            input {
+                class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                r#type: "password",
+                autocomplete: "new-password",
+                value: "{password}",
+                oninput: move |e| password.set(e.value())
+            }
+            if !password().is_empty() {
+                for error in errors {
+                    span { class: "text-xs text-red-600", "• {error}" }
+                }
+            }
+        }
+
+        div { class: "flex flex-col gap-1",
+            label { class: "text-sm font-medium text-slate-700", "Repeat new password" }
+            input {
+                class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                r#type: "password",
+                autocomplete: "new-password",
+                value: "{confirm}",
+                oninput: move |e| confirm.set(e.value())
+            }
+            if mismatch {
+                span { class: "text-xs text-red-600", "Passwords do not match" }
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use dioxus::prelude::*;
+
+use crate::models::auth::CurrentUser;
+
+/// Recovery codes shown once, with a button to confirm they were saved.
+#[component]
+pub fn RecoveryCodes(codes: Vec<String>, on_saved: EventHandler<()>) -> Element {
+    rsx! {
+        div { class: "flex flex-col gap-3",
+            p { class: "text-sm text-slate-600",
+                "Store these recovery codes somewhere safe. Each one signs you in once if you lose your authenticator. "
+                "They will not be shown again."
+            }
+            div { class: "grid grid-cols-2 gap-2 bg-slate-50 border rounded-lg p-3",
+                for code in codes.iter() {
+                    code { class: "font-mono text-sm text-center", "{code}" }
+                }
+            }
+            button {
+                class: "w-full bg-blue-600 text-white py-2 rounded hover:bg-blue-700 transition font-medium",
+                onclick: move |_| on_saved.call(()),
+                "I have saved these codes"
+            }
+        }
+    }
+}
+
+/// Sets up an authenticator app: QR code, first code, then recovery codes.
+///
+/// `challenge` is the login challenge when a role requires 2FA before signing in;
+/// `on_done` then receives the signed-in user.
+#[component]
+pub fn TotpEnrollmentPanel(challenge: Option<String>, on_done: EventHandler<Option<CurrentUser>>) -> Element {
+    let mut code = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut pending = use_signal(|| false);
+    let mut confirmation = use_signal(|| None::<crate::models::two_factor::TotpConfirmation>);
+
+    let enrollment = use_resource({
+        let challenge = challenge.clone();
+        move || crate::server::two_factor::begin_totp_enrollment(challenge.clone())
+    });
+
+    if let Some(confirmed) = confirmation() {
+        return rsx! {
+            RecoveryCodes {
+                codes: confirmed.recovery_codes.clone(),
+                on_saved: move |_| on_done.call(confirmed.user.clone())
+            }
+        };
+    }
+
+    rsx! {
+        match &*enrollment.read() {
+            Some(Ok(setup)) => rsx! {
+                div { class: "flex flex-col gap-4",
+                    p { class: "text-sm text-slate-600",
+                        "Scan the code with an authenticator app (e.g. Aegis, Google Authenticator, 1Password), "
+                        "then enter the 6-digit code it shows."
+                    }
+                    div { class: "mx-auto w-[200px]", dangerous_inner_html: "{setup.qr_svg}" }
+                    details { class: "text-xs text-slate-500",
+                        summary { class: "cursor-pointer", "Can't scan? Enter the key manually" }
+                        code { class: "block mt-2 font-mono break-all bg-slate-50 border rounded px-2 py-1", "{setup.secret}" }
+                    }
+                    form {
+                        class: "flex flex-col gap-3",
+                        onsubmit: {
+                            let challenge = challenge.clone();
+                            move |e: FormEvent| {
+                                let challenge = challenge.clone();
+                                async move {
+                                    e.prevent_default();
+                                    pending.set(true);
+                                    error.set(None);
+                                    match crate::server::two_factor::confirm_totp_enrollment(challenge, code()).await {
+                                        Ok(confirmed) => confirmation.set(Some(confirmed)),
+                                        Err(ServerFnError::ServerError { message, .. }) => error.set(Some(message)),
+                                        Err(e) => error.set(Some(e.to_string())),
+                                    }
+                                    pending.set(false);
+                                }
+                            }
+                        },
+                        input {
+                            class: "border rounded-lg px-4 py-2 text-center font-mono tracking-widest focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                            inputmode: "numeric",
+                            autocomplete: "one-time-code",
+                            placeholder: "123456",
+                            value: "{code}",
+                            oninput: move |e| code.set(e.value())
+                        }
+                        if let Some(message) = error() {
+                            span { class: "text-sm text-red-600", "{message}" }
+                        }
+                        button {
+                            class: "w-full bg-blue-600 text-white py-2 rounded hover:bg-blue-700 transition font-medium disabled:opacity-50 disabled:cursor-not-allowed",
+                            r#type: "submit",
+                            disabled: pending() || code().trim().len() != 6,
+                            if pending() { "Checking..." } else { "Activate" }
+                        }
+                    }
+                }
+            },
+            Some(Err(e)) => rsx! {
+                p { class: "text-sm text-red-600", "Could not start the setup: {e}" }
+            },
+            None => rsx! {
+                div { class: "mx-auto w-[200px] h-[200px] bg-slate-100 rounded animate-pulse" }
+            },
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod stat_card;
@@ -0,0 +1,22 @@
+use dioxus::prelude::*;
+
+/// Small KPI card showing a single number with a caption.
+#[component]
+pub fn StatCard(
+    title: String,
+    #[props(!optional)] value: Option<i64>,
+    #[props(default)] accent: String,
+) -> Element {
+    let accent = if accent.is_empty() { "text-blue-600".to_string() } else { accent };
+
+    rsx! {
+        div { class: "bg-white rounded-lg shadow p-6 flex flex-col gap-2 border border-blue-100",
+            span { class: "text-xs font-bold text-blue-700 uppercase", "{title}" }
+            if let Some(value) = value {
+                span { class: "text-4xl font-bold {accent}", "{value}" }
+            } else {
+                div { class: "h-10 w-20 bg-slate-100 rounded animate-pulse" }
+            }
+        }
+    }
+}
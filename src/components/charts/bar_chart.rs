@@ -0,0 +1,78 @@
+use dioxus::prelude::*;
+
+use super::ChartPoint;
+
+const WIDTH: i64 = 480;
+const LABEL_WIDTH: i64 = 170;
+const VALUE_WIDTH: i64 = 40;
+const ROW_HEIGHT: i64 = 30;
+const BAR_HEIGHT: i64 = 18;
+
+/// Horizontal bar chart rendered as plain SVG (one row per point).
+#[component]
+pub fn BarChart(points: Vec<ChartPoint>) -> Element {
+    if points.is_empty() {
+        return rsx! {
+            p { class: "text-sm text-slate-500 py-8 text-center", "No data available." }
+        };
+    }
+
+    let max = points.iter().map(|p| p.value).max().unwrap_or(0).max(1);
+    let bar_area = WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+    let height = points.len() as i64 * ROW_HEIGHT;
+    let label_x = LABEL_WIDTH - 8;
+
+    // (point, text baseline, bar top, bar width, value x)
+    let rows: Vec<(ChartPoint, i64, i64, i64, i64)> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let top = i as i64 * ROW_HEIGHT;
+            let bar_width = p.value * bar_area / max;
+            (p.clone(), top + ROW_HEIGHT / 2, top + (ROW_HEIGHT - BAR_HEIGHT) / 2, bar_width, LABEL_WIDTH + bar_width + 6)
+        })
+        .collect();
+
+    rsx! {
+        svg {
+            xmlns: "http://www.w3.org/2000/svg",
+            view_box: "0 0 {WIDTH} {height}",
+            class: "w-full h-auto",
+            role: "img",
+
+            for (point, middle, top, bar_width, value_x) in rows {
+                g { key: "{point.label}",
+                    // LABEL
+                    text {
+                        x: "{label_x}",
+                        y: "{middle}",
+                        text_anchor: "end",
+                        dominant_baseline: "middle",
+                        font_size: "12",
+                        fill: "#334155",
+                        "{point.label}"
+                    }
+                    // BAR
+                    rect {
+                        x: "{LABEL_WIDTH}",
+                        y: "{top}",
+                        width: "{bar_width}",
+                        height: "{BAR_HEIGHT}",
+                        rx: "3",
+                        fill: "#3b82f6",
+                    }
+                    // VALUE
+                    text {
+                        x: "{value_x}",
+                        y: "{middle}",
+                        dominant_baseline: "middle",
+                        font_size: "12",
+                        font_weight: "bold",
+                        fill: "#1d4ed8",
+                        "{point.value}"
+                    }
+                }
+            }
+        }
+    }
+}
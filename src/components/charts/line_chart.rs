@@ -0,0 +1,107 @@
+use dioxus::prelude::*;
+
+use super::ChartPoint;
+
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 240.0;
+const PAD_LEFT: f64 = 40.0;
+const PAD_RIGHT: f64 = 16.0;
+const PAD_TOP: f64 = 16.0;
+const PAD_BOTTOM: f64 = 32.0;
+
+/// Line chart rendered as plain SVG, points are spaced evenly along the x axis.
+#[component]
+pub fn LineChart(points: Vec<ChartPoint>) -> Element {
+    if points.is_empty() {
+        return rsx! {
+            p { class: "text-sm text-slate-500 py-8 text-center", "No data available." }
+        };
+    }
+
+    let max = points.iter().map(|p| p.value).max().unwrap_or(0).max(1) as f64;
+    let plot_w = WIDTH - PAD_LEFT - PAD_RIGHT;
+    let plot_h = HEIGHT - PAD_TOP - PAD_BOTTOM;
+    let step = if points.len() > 1 { plot_w / (points.len() - 1) as f64 } else { 0.0 };
+
+    let coords: Vec<(f64, f64)> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let x = PAD_LEFT + i as f64 * step;
+            let y = PAD_TOP + plot_h - (p.value as f64 / max) * plot_h;
+            (x, y)
+        })
+        .collect();
+
+    let polyline = coords
+        .iter()
+        .map(|(x, y)| format!("{x:.1},{y:.1}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // Horizontal grid lines at 0, 50% and 100% of the maximum
+    let grid = [0.0, 0.5, 1.0].map(|f| (PAD_TOP + plot_h - f * plot_h, (f * max).round() as i64));
+
+    let grid_right = WIDTH - PAD_RIGHT;
+    let axis_label_x = PAD_LEFT - 6.0;
+    let x_label_y = HEIGHT - 10.0;
+
+    // Only label every n-th point so the x axis stays readable
+    let label_every = points.len().div_ceil(12).max(1);
+
+    rsx! {
+        svg {
+            xmlns: "http://www.w3.org/2000/svg",
+            view_box: "0 0 {WIDTH} {HEIGHT}",
+            class: "w-full h-auto",
+            role: "img",
+
+            // GRID
+            for (y, value) in grid {
+                line {
+                    x1: "{PAD_LEFT}",
+                    x2: "{grid_right}",
+                    y1: "{y:.1}",
+                    y2: "{y:.1}",
+                    stroke: "#e2e8f0",
+                    stroke_width: "1",
+                }
+                text {
+                    x: "{axis_label_x}",
+                    y: "{y:.1}",
+                    text_anchor: "end",
+                    dominant_baseline: "middle",
+                    font_size: "11",
+                    fill: "#64748b",
+                    "{value}"
+                }
+            }
+
+            // LINE
+            polyline {
+                points: "{polyline}",
+                fill: "none",
+                stroke: "#2563eb",
+                stroke_width: "2.5",
+                stroke_linejoin: "round",
+            }
+
+            // POINTS + X LABELS
+            for (i, (point, (x, y))) in points.iter().zip(coords.iter()).enumerate() {
+                g { key: "{point.label}",
+                    circle { cx: "{x:.1}", cy: "{y:.1}", r: "3.5", fill: "#2563eb" }
+                    if i % label_every == 0 || i + 1 == points.len() {
+                        text {
+                            x: "{x:.1}",
+                            y: "{x_label_y}",
+                            text_anchor: "middle",
+                            font_size: "11",
+                            fill: "#64748b",
+                            "{point.label}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
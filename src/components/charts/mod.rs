@@ -0,0 +1,9 @@
+pub mod bar_chart;
+pub mod line_chart;
+
+/// A single labelled value, shared by all chart components.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartPoint {
+    pub label: String,
+    pub value: i64,
+}
@@ -0,0 +1,153 @@
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::form_state::FormState;
+
+// Keeps the input ids of two open forms apart
+static NEXT_FORM_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Dialog around a [`FormState`]: one labelled input per field with its
+/// message, a form-level server error and Cancel / submit buttons. Esc and a
+/// click on the backdrop close it, Enter submits, and Tab cycles within it.
+/// Submit is disabled while the values are invalid or a save is running.
+#[component]
+pub fn FormModal(
+    form: FormState,
+    #[props(into)] title: String,
+    #[props(into, default)] description: String,
+    #[props(into)] submit_label: String,
+    on_close: EventHandler<()>,
+    on_submit: EventHandler<()>,
+) -> Element {
+    let id = use_hook(|| format!("form-{}", NEXT_FORM_ID.fetch_add(1, Ordering::Relaxed)));
+    let fields = form.fields();
+    let count = fields.len();
+    let submit_disabled = !form.is_valid() || form.is_pending();
+
+    // Focusable elements by position: the inputs, then Cancel, then submit
+    let mut mounted = use_signal(HashMap::<usize, Rc<MountedData>>::new);
+    let mut focused = use_signal(|| 0usize);
+    let last = if submit_disabled { count } else { count + 1 };
+    let focus = move |index: usize| {
+        if let Some(el) = mounted.peek().get(&index).cloned() {
+            spawn(async move {
+                let _ = el.set_focus(true).await;
+            });
+        }
+    };
+
+    let onkeydown = move |evt: KeyboardEvent| match evt.key() {
+        Key::Escape => on_close.call(()),
+        // On Cancel, Enter stays the button's own click
+        Key::Enter if focused() != count => {
+            evt.prevent_default();
+            if submit_disabled {
+                form.show_messages();
+            } else {
+                on_submit.call(());
+            }
+        }
+        Key::Tab => {
+            let backwards = evt.modifiers().contains(Modifiers::SHIFT);
+            if backwards && focused() == 0 {
+                evt.prevent_default();
+                focus(last);
+            } else if !backwards && focused() >= last {
+                evt.prevent_default();
+                focus(0);
+            }
+        }
+        _ => {}
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-50 flex items-center justify-center bg-black/40 backdrop-blur-sm",
+            onclick: move |_| on_close.call(()),
+
+            div {
+                class: "bg-white rounded-2xl shadow-2xl w-full max-w-[520px] mx-4 overflow-hidden",
+                role: "dialog",
+                aria_modal: "true",
+                aria_labelledby: "{id}-title",
+                onclick: move |e| e.stop_propagation(),
+                onkeydown,
+
+                // HEADER
+                div { class: "px-8 py-5 border-b",
+                    h2 { id: "{id}-title", class: "text-lg font-semibold text-slate-800", "{title}" }
+                    if !description.is_empty() {
+                        p { class: "text-sm text-slate-500 mt-1", "{description}" }
+                    }
+                }
+
+                // BODY
+                div { class: "px-8 py-6 flex flex-col gap-5",
+                    for (index, field) in fields.into_iter().enumerate() {
+                        div { key: "{field.name}", class: "flex flex-col gap-1",
+                            label {
+                                r#for: "{id}-{field.name}",
+                                class: "text-sm font-medium text-slate-700",
+                                "{field.label} ",
+                                if field.required {
+                                    span { class: "text-red-500", "*" }
+                                }
+                            }
+                            input {
+                                id: "{id}-{field.name}",
+                                class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                r#type: field.input_type,
+                                required: field.required,
+                                aria_invalid: form.message(field.name).is_some(),
+                                value: form.value(field.name),
+                                oninput: move |e| form.set(field.name, e.value()),
+                                onfocus: move |_| focused.set(index),
+                                onmounted: move |e| {
+                                    mounted.write().insert(index, e.data());
+                                    if index == 0 {
+                                        focus(0);
+                                    }
+                                }
+                            }
+                            if let Some(message) = form.message(field.name) {
+                                span { class: "text-xs text-red-500", "{message}" }
+                            }
+                        }
+                    }
+
+                    if let Some(message) = form.server_error() {
+                        p { class: "text-sm text-red-600", role: "alert", "{message}" }
+                    }
+                }
+
+                // FOOTER
+                div { class: "px-8 py-5 bg-slate-50 flex justify-end gap-3",
+                    button {
+                        r#type: "button",
+                        class: "px-4 py-2 rounded-lg border border-slate-300 text-slate-600 hover:bg-slate-100 transition",
+                        onclick: move |_| on_close.call(()),
+                        onfocus: move |_| focused.set(count),
+                        onmounted: move |e| {
+                            mounted.write().insert(count, e.data());
+                        },
+                        "Cancel"
+                    }
+                    button {
+                        r#type: "button",
+                        class: "px-5 py-2 rounded-lg bg-blue-600 text-white font-semibold hover:bg-blue-700 transition shadow-md disabled:opacity-50 disabled:cursor-not-allowed",
+                        disabled: submit_disabled,
+                        aria_busy: form.is_pending(),
+                        onclick: move |_| on_submit.call(()),
+                        onfocus: move |_| focused.set(count + 1),
+                        onmounted: move |e| {
+                            mounted.write().insert(count + 1, e.data());
+                        },
+                        "{submit_label}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Values, validation and submission of a form, kept apart from its markup.
+//!
+//! A [`FormSpec`] lists the [`Field`]s plus how to validate the values and how
+//! to read a failed save; [`use_form`] turns it into a [`FormState`] that a
+//! [`super::form_modal::FormModal`] renders.
+
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::future::Future;
+use std::rc::Rc;
+
+use crate::models::employee::FieldError;
+
+/// One input of a form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    // Key of the value, also used for the matching FieldError
+    pub name: &'static str,
+    pub label: &'static str,
+    pub input_type: &'static str,
+    pub required: bool,
+}
+
+impl Field {
+    pub fn new(name: &'static str, label: &'static str) -> Self {
+        Self { name, label, input_type: "text", required: false }
+    }
+
+    pub fn input_type(mut self, input_type: &'static str) -> Self {
+        self.input_type = input_type;
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+}
+
+/// The values of a form by field name; a missing one reads as empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormValues(HashMap<&'static str, String>);
+
+impl FormValues {
+    pub fn get(&self, name: &str) -> &str {
+        self.0.get(name).map(String::as_str).unwrap_or_default()
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.0.insert(name, value.into());
+        self
+    }
+}
+
+type Validator = Rc<dyn Fn(&FormValues) -> Vec<FieldError>>;
+type ServerErrorMap = Rc<dyn Fn(&ServerFnError) -> Option<FieldError>>;
+
+/// What a form consists of.
+#[derive(Clone)]
+pub struct FormSpec {
+    pub fields: Vec<Field>,
+    validate: Validator,
+    server_errors: ServerErrorMap,
+}
+
+impl FormSpec {
+    pub fn new(fields: Vec<Field>) -> Self {
+        Self { fields, validate: Rc::new(|_| Vec::new()), server_errors: Rc::new(|_| None) }
+    }
+
+    /// Checks the values, one message per failed field.
+    pub fn validate(mut self, validate: impl Fn(&FormValues) -> Vec<FieldError> + 'static) -> Self {
+        self.validate = Rc::new(validate);
+        self
+    }
+
+    /// Pins a failed save to a field (e.g. a taken email). Errors it returns
+    /// `None` for are shown above the buttons.
+    pub fn server_errors(mut self, map: impl Fn(&ServerFnError) -> Option<FieldError> + 'static) -> Self {
+        self.server_errors = Rc::new(map);
+        self
+    }
+}
+
+/// Signals behind a form. Copy, so every event handler can take it by value.
+#[derive(Clone, Copy)]
+pub struct FormState {
+    spec: CopyValue<FormSpec>,
+    values: Signal<FormValues>,
+    // What the form was opened with; the values differ from it when dirty
+    initial: Signal<FormValues>,
+    // Set by a submit, shows the messages of fields not touched yet
+    attempted: Signal<bool>,
+    pub pending: Signal<bool>,
+    server_field_errors: Signal<Vec<FieldError>>,
+    server_error: Signal<Option<String>>,
+}
+
+impl PartialEq for FormState {
+    fn eq(&self, other: &Self) -> bool {
+        self.spec == other.spec && self.values == other.values
+    }
+}
+
+pub fn use_form(spec: impl FnOnce() -> FormSpec) -> FormState {
+    FormState {
+        spec: use_hook(|| CopyValue::new(spec())),
+        values: use_signal(FormValues::default),
+        initial: use_signal(FormValues::default),
+        attempted: use_signal(|| false),
+        pending: use_signal(|| false),
+        server_field_errors: use_signal(Vec::new),
+        server_error: use_signal(|| None),
+    }
+}
+
+impl FormState {
+    pub fn fields(&self) -> Vec<Field> {
+        self.spec.read().fields.clone()
+    }
+
+    pub fn value(&self, name: &str) -> String {
+        self.values.read().get(name).to_string()
+    }
+
+    pub fn values(&self) -> FormValues {
+        self.values.read().clone()
+    }
+
+    pub fn set(mut self, name: &'static str, value: String) {
+        self.values.write().0.insert(name, value);
+        self.server_field_errors.write().retain(|e| e.field != name);
+    }
+
+    /// Starts over from `values`, which then count as unchanged.
+    pub fn reset(mut self, values: FormValues) {
+        self.initial.set(values.clone());
+        self.values.set(values);
+        self.attempted.set(false);
+        self.server_field_errors.set(Vec::new());
+        self.server_error.set(None);
+    }
+
+    pub fn clear(self) {
+        self.reset(FormValues::default());
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        *self.values.read() != *self.initial.read()
+    }
+
+    pub fn field_dirty(&self, name: &str) -> bool {
+        self.values.read().get(name) != self.initial.read().get(name)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (self.spec.read().validate)(&self.values.read()).is_empty()
+    }
+
+    pub fn is_pending(&self) -> bool {
+        (self.pending)()
+    }
+
+    /// The message to show under `name`: what the server said about it, else
+    /// what validation says once the field was changed or a submit was tried.
+    pub fn message(&self, name: &str) -> Option<String> {
+        if let Some(e) = self.server_field_errors.read().iter().find(|e| e.field == name) {
+            return Some(e.message.clone());
+        }
+        if !(self.attempted)() && !self.field_dirty(name) {
+            return None;
+        }
+        let errors = (self.spec.read().validate)(&self.values.read());
+        errors.into_iter().find(|e| e.field == name).map(|e| e.message)
+    }
+
+    /// Shows the messages of the fields not touched yet, as a submit does.
+    pub fn show_messages(mut self) {
+        self.attempted.set(true);
+    }
+
+    /// A failed save that belongs to no field.
+    pub fn server_error(&self) -> Option<String> {
+        (self.server_error)()
+    }
+
+    /// Runs `save` with the values if they are valid and no save is running.
+    /// Returns whether it went through; if not, its error is kept for display.
+    pub async fn submit<Fut>(mut self, save: impl FnOnce(FormValues) -> Fut) -> bool
+    where
+        Fut: Future<Output = Result<(), ServerFnError>>,
+    {
+        self.attempted.set(true);
+        if *self.pending.peek() || !self.is_valid() {
+            return false;
+        }
+
+        self.pending.set(true);
+        self.server_field_errors.set(Vec::new());
+        self.server_error.set(None);
+        let result = save(self.values()).await;
+        self.pending.set(false);
+
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                let field_error = (self.spec.read().server_errors)(&e);
+                match field_error {
+                    Some(field_error) => self.server_field_errors.write().push(field_error),
+                    None => self.server_error.set(Some(e.to_string())),
+                }
+                false
+            }
+        }
+    }
+}
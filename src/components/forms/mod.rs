@@ -0,0 +1,2 @@
+pub mod form_modal;
+pub mod form_state;
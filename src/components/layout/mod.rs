@@ -1,2 +1,3 @@
 pub mod navbar;
 pub mod footer;
+pub mod toast;
@@ -1,11 +1,13 @@
 use crate::Route;
+use crate::components::auth::{login_dialog::LoginDialog, use_auth};
+use crate::components::layout::toast::ToastStack;
 use dioxus::prelude::*;
 
 #[component]
 pub fn Navbar() -> Element {
-    // THIS WAS MISSING: 
     // This signal creates a reactive piece of state (boolean) to track if the dialog is open.
     let mut show_login = use_signal(|| false);
+    let mut auth = use_auth();
 
     rsx! {
         nav { class: "bg-slate-900 text-white mb-8 shadow-md",
@@ -37,16 +39,94 @@ pub fn Navbar() -> Element {
                                 }
                             }
                         }
+
+                        // "Admin" Dropdown, only for ROLE_ADMIN
+                        if auth.is_admin() {
+                            div { class: "relative group py-4",
+                                span { class: "cursor-pointer hover:text-blue-300 flex items-center gap-1",
+                                    "Admin"
+                                    span { class: "text-[10px]", "▼" }
+                                }
+                                div { class: "absolute hidden group-hover:block bg-slate-800 min-w-[150px] shadow-xl rounded-b border-t-2 border-blue-500",
+                                    Link {
+                                        to: Route::UsersView {},
+                                        class: "block px-4 py-2 hover:bg-slate-700 hover:text-blue-300",
+                                        "Users"
+                                    }
+                                    Link {
+                                        to: Route::ApiTokensView {},
+                                        class: "block px-4 py-2 hover:bg-slate-700 hover:text-blue-300",
+                                        "API Tokens"
+                                    }
+                                    Link {
+                                        to: Route::WebhooksView {},
+                                        class: "block px-4 py-2 hover:bg-slate-700 hover:text-blue-300",
+                                        "Webhooks"
+                                    }
+                                    Link {
+                                        to: Route::EmailTemplatesView {},
+                                        class: "block px-4 py-2 hover:bg-slate-700 hover:text-blue-300",
+                                        "Email Templates"
+                                    }
+                                    Link {
+                                        to: Route::DirectorySyncView {},
+                                        class: "block px-4 py-2 hover:bg-slate-700 hover:text-blue-300",
+                                        "Directory Sync"
+                                    }
+                                    Link {
+                                        to: Route::JobsView {},
+                                        class: "block px-4 py-2 hover:bg-slate-700 hover:text-blue-300",
+                                        "Jobs"
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
 
                 // Right side: Auth
                 div {
-                    // Changed from Link to button to trigger the modal instead of a page change
-                    button { 
-                        class: "bg-blue-600 hover:bg-blue-500 text-white px-4 py-2 rounded transition",
-                        onclick: move |_| show_login.set(true),
-                        "Login"
+                    // No server to log in to in the offline desktop build
+                    if cfg!(feature = "offline") {
+                        span { class: "text-sm text-slate-400", "Offline" }
+                    } else if let Some(user) = auth.user.read().as_ref() {
+                        div { class: "flex items-center gap-4",
+                            // Account dropdown
+                            div { class: "relative group py-4",
+                                span { class: "cursor-pointer text-sm text-slate-300 hover:text-blue-300 flex items-center gap-1",
+                                    "{user.username}"
+                                    span { class: "text-[10px]", "▼" }
+                                }
+                                div { class: "absolute right-0 hidden group-hover:block bg-slate-800 min-w-[200px] shadow-xl rounded-b border-t-2 border-blue-500",
+                                    Link {
+                                        to: Route::ChangePasswordView {},
+                                        class: "block px-4 py-2 hover:bg-slate-700 hover:text-blue-300",
+                                        "Change password"
+                                    }
+                                    Link {
+                                        to: Route::TwoFactorView {},
+                                        class: "block px-4 py-2 hover:bg-slate-700 hover:text-blue-300",
+                                        "Two-factor authentication"
+                                    }
+                                }
+                            }
+                            button {
+                                class: "border border-slate-600 hover:border-blue-400 hover:text-blue-300 px-4 py-2 rounded transition",
+                                onclick: move |_| async move {
+                                    if crate::server::auth::logout().await.is_ok() {
+                                        auth.user.set(None);
+                                    }
+                                },
+                                "Logout"
+                            }
+                        }
+                    } else {
+                        // Button instead of Link to trigger the modal instead of a page change
+                        button { 
+                            class: "bg-blue-600 hover:bg-blue-500 text-white px-4 py-2 rounded transition",
+                            onclick: move |_| show_login.set(true),
+                            "Login"
+                        }
                     }
                 }
             }
@@ -55,28 +135,13 @@ pub fn Navbar() -> Element {
         // LOGIN DIALOG
         // This block only renders if show_login is true
         if show_login() {
-            div { 
-                class: "fixed inset-0 bg-black/60 flex items-center justify-center z-50",
-                onclick: move |_| show_login.set(false),
-
-                div { 
-                    class: "bg-white text-slate-900 p-8 rounded-lg shadow-2xl max-w-sm w-full",
-                    // Prevents closing the dialog when clicking inside the white box
-                    onclick: |e| e.stop_propagation(),
-
-                    h2 { class: "text-2xl font-bold mb-4", "Login" }
-                    p { class: "mb-6 text-slate-600", "This is your login dialog placeholder." }
-                    
-                    button { 
-                        class: "w-full bg-blue-600 text-white py-2 rounded hover:bg-blue-700 transition font-medium",
-                        onclick: move |_| show_login.set(false),
-                        "Close"
-                    }
-                }
-            }
+            LoginDialog { on_close: move |_| show_login.set(false) }
         }
 
         // This is where the views (Home, EmployeeView) are rendered
         Outlet::<Route> {}
+
+        // Feedback after saving, deleting etc., on top of every view
+        ToastStack {}
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,171 @@
+//! Short notices after an operation, shared through the context.
+//!
+//! [`use_toast_provider`] in `App` provides [`Toasts`]; any component gets it
+//! with [`use_toasts`] and shows a [`Toast`]. The layout renders them stacked in
+//! the bottom right corner with [`ToastStack`]; each one goes away on its own.
+
+use dioxus::dioxus_core::spawn_forever;
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::components::timer::sleep;
+
+// Older ones are dropped beyond this
+const MAX_TOASTS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Success,
+    Error,
+}
+
+impl ToastKind {
+    // Errors stay longer, they usually need reading
+    fn duration(&self) -> Duration {
+        match self {
+            ToastKind::Error => Duration::from_secs(8),
+            ToastKind::Success => Duration::from_secs(5),
+        }
+    }
+
+    fn class(&self) -> &'static str {
+        match self {
+            ToastKind::Success => "border-green-500",
+            ToastKind::Error => "border-red-500",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Toast {
+    id: u64,
+    pub kind: ToastKind,
+    pub message: String,
+    // Shown as an "Undo" button, dismisses the toast when clicked
+    undo: Option<Rc<dyn Fn()>>,
+}
+
+impl Toast {
+    fn new(kind: ToastKind, message: impl Into<String>) -> Self {
+        Self { id: 0, kind, message: message.into(), undo: None }
+    }
+
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(ToastKind::Success, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(ToastKind::Error, message)
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Offers to take the operation back. Runs in the toast stack's scope, so
+    /// it should not rely on the component that showed the toast still being there.
+    pub fn undo(mut self, undo: impl Fn() + 'static) -> Self {
+        self.undo = Some(Rc::new(undo));
+        self
+    }
+}
+
+/// The toasts on screen. Copy, so every event handler can take it by value.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Toasts {
+    pub items: Signal<Vec<Toast>>,
+    next_id: CopyValue<u64>,
+}
+
+pub fn use_toast_provider() -> Toasts {
+    use_context_provider(|| Toasts { items: Signal::new(Vec::new()), next_id: CopyValue::new(0) })
+}
+
+pub fn use_toasts() -> Toasts {
+    use_context::<Toasts>()
+}
+
+impl Toasts {
+    /// Shows `toast` until it times out or is closed.
+    pub fn show(mut self, mut toast: Toast) {
+        let id = {
+            let mut next_id = self.next_id.write();
+            *next_id += 1;
+            *next_id
+        };
+        toast.id = id;
+        let duration = toast.kind.duration();
+
+        let mut items = self.items.write();
+        items.push(toast);
+        if items.len() > MAX_TOASTS {
+            items.remove(0);
+        }
+        drop(items);
+
+        // In the root scope, so closing the dialog that showed it does not cancel the timer
+        spawn_forever(async move {
+            sleep(duration).await;
+            self.dismiss(id);
+        });
+    }
+
+    pub fn success(self, message: impl Into<String>) {
+        self.show(Toast::success(message));
+    }
+
+    pub fn error(self, message: impl Into<String>) {
+        self.show(Toast::error(message));
+    }
+
+    pub fn dismiss(mut self, id: u64) {
+        self.items.write().retain(|t| t.id != id);
+    }
+
+    /// Runs the undo action of toast `id`, if it still has one, and closes it.
+    pub fn undo(self, id: u64) {
+        let undo = self.items.peek().iter().find(|t| t.id == id).and_then(|t| t.undo.clone());
+        if let Some(undo) = undo {
+            undo();
+        }
+        self.dismiss(id);
+    }
+}
+
+/// The toasts, newest at the bottom.
+#[component]
+pub fn ToastStack() -> Element {
+    let toasts = use_toasts();
+
+    rsx! {
+        div {
+            class: "fixed bottom-4 right-4 z-[60] flex flex-col gap-2 w-[calc(100%-2rem)] sm:w-96",
+            aria_live: "polite",
+
+            for (id, toast) in (toasts.items)().into_iter().map(|t| (t.id(), t)) {
+                div {
+                    key: "{id}",
+                    class: "flex items-center gap-3 bg-white rounded-lg shadow-lg border-l-4 px-4 py-3 {toast.kind.class()}",
+                    role: if toast.kind == ToastKind::Error { "alert" } else { "status" },
+
+                    p { class: "flex-1 text-sm text-slate-700", "{toast.message}" }
+
+                    if toast.undo.is_some() {
+                        button {
+                            class: "text-sm font-semibold text-blue-600 hover:text-blue-800",
+                            onclick: move |_| toasts.undo(id),
+                            "Undo"
+                        }
+                    }
+                    button {
+                        class: "text-slate-400 hover:text-slate-600",
+                        aria_label: "Dismiss",
+                        onclick: move |_| toasts.dismiss(id),
+                        "✕"
+                    }
+                }
+            }
+        }
+    }
+}
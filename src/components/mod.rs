@@ -1,3 +1,7 @@
 pub mod layout;
 pub mod tables;
-
+pub mod charts;
+pub mod cards;
+pub mod auth;
+pub mod forms;
+pub mod timer;
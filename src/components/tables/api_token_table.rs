@@ -0,0 +1,262 @@
+use dioxus::prelude::*;
+use crate::models::auth::{ApiTokenKind, IssueApiTokenRequest, ALL_SCOPES, SCOPE_EMPLOYEES_READ};
+use crate::server::tokens::MAX_TOKEN_LIFETIME_DAYS;
+
+#[component]
+pub fn ApiTokenTable() -> Element {
+    // ISSUE MODAL STATE
+    let mut show_issue_modal = use_signal(|| false);
+    let mut username = use_signal(String::new);
+    let mut name = use_signal(String::new);
+    let mut kind = use_signal(|| ApiTokenKind::Service);
+    let mut scopes = use_signal(|| vec![SCOPE_EMPLOYEES_READ.to_string()]);
+    let mut expires_in_days = use_signal(|| 90i32);
+    let mut issue_error = use_signal(|| None::<String>);
+
+    // The raw token is shown exactly once after issuing
+    let mut issued_secret = use_signal(|| None::<String>);
+
+    let mut tokens_resource = use_resource(crate::server::tokens::list_api_tokens);
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full relative",
+
+            // HEADER
+            div { class: "flex justify-between items-center px-2",
+                p { class: "text-sm text-slate-500",
+                    "Tokens authenticate scripts via "
+                    code { class: "bg-slate-100 px-1 rounded", "Authorization: Bearer <token>" }
+                }
+                button {
+                    class: "bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-6 rounded-lg shadow-md transition-all",
+                    onclick: move |_| {
+                        issue_error.set(None);
+                        show_issue_modal.set(true);
+                    },
+                    "Issue Token"
+                }
+            }
+
+            if let Some(secret) = issued_secret() {
+                div { class: "bg-emerald-50 border border-emerald-200 rounded-lg px-4 py-3 flex flex-col gap-2",
+                    span { class: "text-sm font-semibold text-emerald-800",
+                        "Copy the new token now, it will not be shown again:"
+                    }
+                    code { class: "font-mono text-sm break-all bg-white border rounded px-2 py-1", "{secret}" }
+                    button {
+                        class: "self-end text-sm text-emerald-700 hover:underline",
+                        onclick: move |_| issued_secret.set(None),
+                        "Dismiss"
+                    }
+                }
+            }
+
+            // TABLE
+            div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                table { class: "min-w-full divide-y divide-slate-200",
+                    thead { class: "bg-blue-50",
+                        tr {
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Name" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "User" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Kind" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Token" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Scopes" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Expires" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Last used" }
+                            th { class: "px-6 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Status" }
+                        }
+                    }
+                    tbody { class: "divide-y divide-slate-200 bg-white",
+                        match &*tokens_resource.read() {
+                            Some(Ok(tokens)) if tokens.is_empty() => rsx! {
+                                tr {
+                                    td { colspan: "8", class: "px-6 py-8 text-center text-slate-500", "No API tokens issued yet." }
+                                }
+                            },
+                            Some(Ok(tokens)) => rsx! {
+                                for token in tokens.iter() {
+                                    tr { class: "h-14 hover:bg-blue-50 transition-colors", key: "{token.id}",
+                                        td { class: "px-6 py-3 text-sm font-medium", "{token.name}" }
+                                        td { class: "px-6 py-3 text-sm", "{token.username}" }
+                                        td { class: "px-6 py-3 text-sm", "{token.kind}" }
+                                        td { class: "px-6 py-3 text-sm font-mono", "{token.token_prefix}…" }
+                                        td { class: "px-6 py-3 text-xs", {token.scopes.join(", ")} }
+                                        td { class: "px-6 py-3 text-sm", {token.expires_at.format("%Y-%m-%d").to_string()} }
+                                        td { class: "px-6 py-3 text-sm",
+                                            match token.last_used_at {
+                                                Some(at) => rsx! { {at.format("%Y-%m-%d %H:%M").to_string()} },
+                                                None => rsx! { span { class: "text-slate-400", "never" } },
+                                            }
+                                        }
+                                        td { class: "px-6 py-3 text-center",
+                                            if token.is_active() {
+                                                button {
+                                                    class: "px-3 py-1 text-sm rounded-md text-red-600 hover:bg-red-50 transition",
+                                                    onclick: {
+                                                        let id = token.id;
+                                                        move |_| async move {
+                                                            if crate::server::tokens::revoke_api_token(id).await.is_ok() {
+                                                                tokens_resource.restart();
+                                                            }
+                                                        }
+                                                    },
+                                                    "Revoke"
+                                                }
+                                            } else if token.revoked_at.is_some() {
+                                                span { class: "text-xs font-semibold text-slate-400 uppercase", "Revoked" }
+                                            } else {
+                                                span { class: "text-xs font-semibold text-slate-400 uppercase", "Expired" }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Err(e)) => rsx! {
+                                tr {
+                                    td { colspan: "8", class: "px-6 py-8 text-center text-red-600", "Could not load tokens: {e}" }
+                                }
+                            },
+                            None => rsx! {
+                                for _ in 0..3 {
+                                    tr {
+                                        td { colspan: "8", class: "px-6 py-4",
+                                            div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                                        }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+
+            // ISSUE TOKEN MODAL
+            if show_issue_modal() {
+                div {
+                    class: "fixed inset-0 z-50 flex items-center justify-center bg-black/40 backdrop-blur-sm",
+                    onclick: move |_| show_issue_modal.set(false),
+
+                    div {
+                        class: "bg-white rounded-2xl shadow-2xl w-[520px] overflow-hidden",
+                        onclick: move |e| e.stop_propagation(),
+
+                        // HEADER
+                        div { class: "px-8 py-5 border-b",
+                            h2 { class: "text-lg font-semibold text-slate-800", "Issue API Token" }
+                            p { class: "text-sm text-slate-500 mt-1",
+                                "The token acts as the selected user, limited to the chosen scopes."
+                            }
+                        }
+
+                        // BODY
+                        div { class: "px-8 py-6 flex flex-col gap-5",
+
+                            div { class: "flex flex-col gap-1",
+                                label { class: "text-sm font-medium text-slate-700", "Token name" }
+                                input {
+                                    class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                    placeholder: "payroll-sync",
+                                    value: "{name}",
+                                    oninput: move |e| name.set(e.value())
+                                }
+                            }
+
+                            div { class: "flex flex-col gap-1",
+                                label { class: "text-sm font-medium text-slate-700", "User" }
+                                input {
+                                    class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                    placeholder: "username",
+                                    value: "{username}",
+                                    oninput: move |e| username.set(e.value())
+                                }
+                            }
+
+                            div { class: "flex gap-4",
+                                div { class: "flex flex-col gap-1 flex-1",
+                                    label { class: "text-sm font-medium text-slate-700", "Kind" }
+                                    select {
+                                        class: "border rounded-lg px-3 py-2 outline-none",
+                                        onchange: move |e| kind.set(if e.value() == "personal" { ApiTokenKind::Personal } else { ApiTokenKind::Service }),
+                                        option { value: "service", selected: kind() == ApiTokenKind::Service, "Service" }
+                                        option { value: "personal", selected: kind() == ApiTokenKind::Personal, "Personal" }
+                                    }
+                                }
+                                div { class: "flex flex-col gap-1 flex-1",
+                                    label { class: "text-sm font-medium text-slate-700", "Expires in (days)" }
+                                    input {
+                                        class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                        r#type: "number",
+                                        min: "1",
+                                        max: "{MAX_TOKEN_LIFETIME_DAYS}",
+                                        value: "{expires_in_days}",
+                                        oninput: move |e| {
+                                            if let Ok(days) = e.value().parse::<i32>() {
+                                                expires_in_days.set(days);
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            div { class: "flex flex-col gap-1",
+                                label { class: "text-sm font-medium text-slate-700", "Scopes" }
+                                for scope in ALL_SCOPES {
+                                    label { class: "flex items-center gap-2 text-sm",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: scopes.read().iter().any(|s| s == scope),
+                                            onchange: move |e| {
+                                                if e.checked() {
+                                                    scopes.write().push(scope.to_string());
+                                                } else {
+                                                    scopes.write().retain(|s| s != scope);
+                                                }
+                                            }
+                                        }
+                                        code { "{scope}" }
+                                    }
+                                }
+                            }
+
+                            if let Some(message) = issue_error() {
+                                span { class: "text-sm text-red-600", "{message}" }
+                            }
+                        }
+
+                        // FOOTER
+                        div { class: "px-8 py-5 bg-slate-50 flex justify-end gap-3",
+                            button {
+                                class: "px-4 py-2 rounded-lg border border-slate-300 text-slate-600 hover:bg-slate-100 transition",
+                                onclick: move |_| show_issue_modal.set(false),
+                                "Cancel"
+                            }
+                            button {
+                                class: "px-5 py-2 rounded-lg bg-blue-600 text-white font-semibold hover:bg-blue-700 transition shadow-md disabled:opacity-50 disabled:cursor-not-allowed",
+                                disabled: name().trim().is_empty() || username().trim().is_empty() || scopes.read().is_empty(),
+                                onclick: move |_| async move {
+                                    let req = IssueApiTokenRequest {
+                                        username: username(),
+                                        name: name(),
+                                        kind: kind(),
+                                        scopes: scopes(),
+                                        expires_in_days: expires_in_days(),
+                                    };
+                                    match crate::server::tokens::issue_api_token(req).await {
+                                        Ok(issued) => {
+                                            issued_secret.set(Some(issued.secret));
+                                            name.set(String::new());
+                                            show_issue_modal.set(false);
+                                            tokens_resource.restart();
+                                        }
+                                        Err(e) => issue_error.set(Some(e.to_string())),
+                                    }
+                                },
+                                "Issue Token"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,466 @@
+//! Paged, searchable, sortable table over any row type.
+//!
+//! [`use_table_state`] keeps the page, page size, search and sort in signals
+//! and loads the current page through a fetch function while a [`DataTable`]
+//! renders it from [`Column`] definitions plus optional [`RowAction`] buttons.
+//! Other layouts (e.g. the employee cards) can read the same state.
+
+use dioxus::prelude::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::components::timer::sleep;
+use crate::models::employee::SortDirection;
+
+pub const PAGE_SIZES: [i64; 3] = [5, 10, 20];
+
+// Typing pause after which the search is applied
+pub const SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+pub type FetchFuture<T> = Pin<Box<dyn Future<Output = Result<TablePage<T>, ServerFnError>>>>;
+
+/// A row with a stable identity, used as its key.
+pub trait TableRow: Clone + PartialEq + 'static {
+    fn row_key(&self) -> String;
+}
+
+/// What the table asks its fetch function for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableQuery {
+    pub page: i64,
+    pub page_size: i64,
+    // Trimmed, `None` when empty
+    pub search: Option<String>,
+    // A sortable column's key and the direction
+    pub sort: Option<(&'static str, SortDirection)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TablePage<T> {
+    pub rows: Vec<T>,
+    pub total_count: i64,
+}
+
+/// Footer of the table: "1-5 of 22" and which page buttons are usable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pagination {
+    pub text: String,
+    pub prev_disabled: bool,
+    pub next_disabled: bool,
+    pub total_pages: i64,
+}
+
+/// Signals behind a table. Copy, so every event handler can take it by value.
+pub struct TableState<T: 'static> {
+    pub page: Signal<i64>,
+    pub page_size: Signal<i64>,
+    // What is typed into the search box
+    pub search: Signal<String>,
+    // The trimmed search the rows are loaded for, `search` once typing paused
+    pub search_query: Signal<String>,
+    pub sort: Signal<Option<(&'static str, SortDirection)>>,
+    // Bumped by reload(); everything showing these rows reloads on it
+    pub revision: Signal<u64>,
+    pub rows: Resource<Result<TablePage<T>, ServerFnError>>,
+    // Set while a DataTable shows the rows; other layouts load their own way
+    active: Signal<bool>,
+}
+
+// Not derived: that would require T: Copy
+impl<T> Clone for TableState<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TableState<T> {}
+
+impl<T> PartialEq for TableState<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows && self.page == other.page && self.revision == other.revision
+    }
+}
+
+/// Loads pages through `fetch` while a [`DataTable`] shows them, again whenever
+/// the query or anything `fetch` itself reads from signals changes.
+pub fn use_table_state<T>(initial_page_size: i64, fetch: impl Fn(TableQuery) -> FetchFuture<T> + 'static) -> TableState<T> {
+    let mut page = use_signal(|| 1i64);
+    let page_size = use_signal(|| initial_page_size);
+    let search = use_signal(String::new);
+    let mut search_query = use_signal(String::new);
+    let sort = use_signal(|| None);
+    let revision = use_signal(|| 0u64);
+    let active = use_signal(|| false);
+
+    // Restarted by every keystroke, so the search only applies once typing pauses
+    use_resource(move || {
+        let term = search.read().trim().to_string();
+        async move {
+            sleep(SEARCH_DEBOUNCE).await;
+            if *search_query.peek() != term {
+                search_query.set(term);
+                page.set(1);
+            }
+        }
+    });
+
+    let rows = use_resource(move || {
+        let _revision = revision();
+        let term = search_query();
+        let query = TableQuery {
+            page: page(),
+            page_size: page_size(),
+            search: if term.is_empty() { None } else { Some(term) },
+            sort: sort(),
+        };
+        let load = active().then(|| fetch(query));
+        async move {
+            match load {
+                Some(load) => load.await,
+                None => std::future::pending().await,
+            }
+        }
+    });
+
+    TableState { page, page_size, search, search_query, sort, revision, rows, active }
+}
+
+impl<T> TableState<T> {
+    pub fn pagination(&self) -> Pagination {
+        let (page, size) = ((self.page)(), (self.page_size)());
+        match &*self.rows.read() {
+            Some(Ok(res)) => {
+                let start = (page - 1) * size + 1;
+                let end = (start + size - 1).min(res.total_count);
+                let total_pages = ((res.total_count + size - 1) / size).max(1);
+                Pagination {
+                    text: format!("{}-{} of {}", start, end, res.total_count),
+                    prev_disabled: page <= 1,
+                    next_disabled: page >= total_pages,
+                    total_pages,
+                }
+            }
+            _ => Pagination { text: "Loading...".to_string(), prev_disabled: true, next_disabled: true, total_pages: 1 },
+        }
+    }
+
+    /// Sets the search box; the rows follow after [`SEARCH_DEBOUNCE`], from page 1.
+    pub fn set_search(mut self, term: String) {
+        self.search.set(term);
+    }
+
+    pub fn set_page_size(mut self, size: i64) {
+        self.page_size.set(size);
+        self.page.set(1);
+    }
+
+    pub fn go_to_page(mut self, page: i64) {
+        self.page.set(page);
+    }
+
+    /// Sorts by `key`, ascending first, flipping the direction on repeated calls.
+    pub fn toggle_sort(mut self, key: &'static str) {
+        let dir = match (self.sort)() {
+            Some((current, SortDirection::Asc)) if current == key => SortDirection::Desc,
+            _ => SortDirection::Asc,
+        };
+        self.sort.set(Some((key, dir)));
+        self.page.set(1);
+    }
+
+    /// Loads the current page again; a no-op once the table is gone, e.g. when
+    /// an undo from a toast finishes after navigating away.
+    pub fn reload(mut self) {
+        if let Ok(mut revision) = self.revision.try_write() {
+            *revision += 1;
+        }
+    }
+
+    /// Reloads after one row was deleted, stepping back if that emptied the last page.
+    pub fn reload_after_removal(mut self) {
+        let total = self.rows.read().as_ref().and_then(|r| r.as_ref().ok()).map(|r| r.total_count);
+        if let Some(total) = total {
+            let size = (self.page_size)();
+            let max_page = ((total.saturating_sub(1) + size - 1) / size).max(1);
+            if (self.page)() > max_page {
+                self.page.set(max_page);
+            }
+        }
+        self.reload();
+    }
+}
+
+/// One column: header, how a row's cell looks and, if sortable, the key passed
+/// to the fetch function in [`TableQuery::sort`].
+pub struct Column<T> {
+    pub header: &'static str,
+    // Width and alignment classes for the header and cells
+    pub class: &'static str,
+    pub sort_key: Option<&'static str>,
+    pub cell: Rc<dyn Fn(&T) -> Element>,
+}
+
+impl<T> Column<T> {
+    pub fn new(header: &'static str, cell: impl Fn(&T) -> Element + 'static) -> Self {
+        Self { header, class: "", sort_key: None, cell: Rc::new(cell) }
+    }
+
+    pub fn class(mut self, class: &'static str) -> Self {
+        self.class = class;
+        self
+    }
+
+    pub fn sortable(mut self, key: &'static str) -> Self {
+        self.sort_key = Some(key);
+        self
+    }
+}
+
+impl<T> Clone for Column<T> {
+    fn clone(&self) -> Self {
+        Self { header: self.header, class: self.class, sort_key: self.sort_key, cell: self.cell.clone() }
+    }
+}
+
+impl<T> PartialEq for Column<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.header == other.header
+            && self.class == other.class
+            && self.sort_key == other.sort_key
+            && Rc::ptr_eq(&self.cell, &other.cell)
+    }
+}
+
+/// An icon button in the actions column.
+pub struct RowAction<T> {
+    // Also the button's accessible name
+    pub label: &'static str,
+    pub class: &'static str,
+    pub icon: fn() -> Element,
+    pub on_click: Rc<dyn Fn(&T)>,
+}
+
+impl<T> RowAction<T> {
+    pub fn new(label: &'static str, icon: fn() -> Element, on_click: impl Fn(&T) + 'static) -> Self {
+        Self { label, class: "text-blue-500 hover:text-blue-700 hover:bg-blue-50", icon, on_click: Rc::new(on_click) }
+    }
+
+    pub fn class(mut self, class: &'static str) -> Self {
+        self.class = class;
+        self
+    }
+}
+
+impl<T> Clone for RowAction<T> {
+    fn clone(&self) -> Self {
+        Self { label: self.label, class: self.class, icon: self.icon, on_click: self.on_click.clone() }
+    }
+}
+
+impl<T> PartialEq for RowAction<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label && self.class == other.class && Rc::ptr_eq(&self.on_click, &other.on_click)
+    }
+}
+
+const TH_CLASS: &str = "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase";
+
+/// Search box (with `children` next to it, e.g. an add button), the table with
+/// skeleton, error and empty rows, and the paginator.
+#[component]
+pub fn DataTable<T: TableRow>(
+    state: TableState<T>,
+    columns: Vec<Column<T>>,
+    #[props(default)] actions: Vec<RowAction<T>>,
+    #[props(into, default = "Search...".to_string())] search_placeholder: String,
+    #[props(into, default = "Nothing found.".to_string())] empty_text: String,
+    #[props(into, default = "items".to_string())] noun: String,
+    children: Element,
+) -> Element {
+    // The rows load only while a table shows them
+    let mut active = state.active;
+    use_effect(move || active.set(true));
+    use_drop(move || {
+        if let Ok(mut active) = active.try_write() {
+            *active = false;
+        }
+    });
+
+    let Pagination { text: pagination_text, prev_disabled, next_disabled, total_pages } = state.pagination();
+    let colspan = columns.len() + usize::from(!actions.is_empty());
+    let search = state.search;
+    let page_size = state.page_size;
+    let sort = (state.sort)();
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full relative",
+
+            // HEADER
+            div { class: "flex justify-between items-center px-2",
+                input {
+                    class: "border rounded-lg px-4 py-2 w-64 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none",
+                    placeholder: "{search_placeholder}",
+                    value: "{search}",
+                    oninput: move |evt| state.set_search(evt.value())
+                }
+                {children}
+            }
+
+            // TABLE
+            div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                table { class: "min-w-full table-fixed divide-y divide-slate-200",
+                    thead { class: "bg-blue-50",
+                        tr {
+                            for column in columns.iter() {
+                                if let Some(key) = column.sort_key {
+                                    th {
+                                        class: "{column.class} {TH_CLASS}",
+                                        aria_sort: match sort {
+                                            Some((k, SortDirection::Asc)) if k == key => "ascending",
+                                            Some((k, SortDirection::Desc)) if k == key => "descending",
+                                            _ => "none",
+                                        },
+                                        button {
+                                            class: "flex items-center gap-1 uppercase font-bold hover:text-blue-900",
+                                            onclick: move |_| state.toggle_sort(key),
+                                            "{column.header}"
+                                            span { class: "w-3 text-[10px]",
+                                                match sort {
+                                                    Some((k, SortDirection::Asc)) if k == key => "▲",
+                                                    Some((k, SortDirection::Desc)) if k == key => "▼",
+                                                    _ => "",
+                                                }
+                                            }
+                                        }
+                                    }
+                                } else {
+                                    th { class: "{column.class} {TH_CLASS}", "{column.header}" }
+                                }
+                            }
+                            if !actions.is_empty() {
+                                th { class: "w-32 px-6 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Actions" }
+                            }
+                        }
+                    }
+                    tbody { class: "divide-y divide-slate-200 bg-white",
+                        match &*state.rows.read() {
+                            Some(Ok(page)) if page.rows.is_empty() => rsx! {
+                                tr {
+                                    td { colspan: "{colspan}", class: "px-6 py-8 text-center text-slate-500", "{empty_text}" }
+                                }
+                            },
+                            Some(Ok(page)) => rsx! {
+                                for row in page.rows.iter() {
+                                    tr { class: "h-16 hover:bg-blue-50 transition-colors", key: "{row.row_key()}",
+                                        for column in columns.iter() {
+                                            td { class: "px-6 py-4 text-sm", {(column.cell)(row)} }
+                                        }
+                                        if !actions.is_empty() {
+                                            td { class: "px-6 py-4 text-center",
+                                                div { class: "flex items-center justify-center gap-2 h-full",
+                                                    for action in actions.iter() {
+                                                        button {
+                                                            class: "p-2 rounded-md transition {action.class}",
+                                                            aria_label: "{action.label}",
+                                                            title: "{action.label}",
+                                                            onclick: {
+                                                                let on_click = action.on_click.clone();
+                                                                let row = row.clone();
+                                                                move |_| on_click(&row)
+                                                            },
+                                                            {(action.icon)()}
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Err(e)) => rsx! {
+                                tr {
+                                    td { colspan: "{colspan}", class: "px-6 py-8 text-center text-red-600",
+                                        "Could not load {noun}: {e}"
+                                    }
+                                }
+                            },
+                            None => rsx! {
+                                for _ in 0..(page_size().min(10)) {
+                                    tr {
+                                        td { colspan: "{colspan}", class: "px-6 py-4",
+                                            div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                                        }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+
+            // PAGINATOR
+            div { class: "flex items-center justify-end px-4 py-3 bg-white border border-blue-100 rounded-lg text-sm text-slate-600 shadow-sm min-h-[60px] w-full",
+
+                div { class: "flex items-center justify-end w-48 gap-2",
+                    span { "Items per page:" }
+                    select {
+                        class: "border-b-2 border-blue-500 bg-transparent font-bold text-blue-600 outline-none w-12",
+                        value: "{page_size}",
+                        onchange: move |evt| {
+                            if let Ok(new_size) = evt.value().parse::<i64>() {
+                                state.set_page_size(new_size);
+                            }
+                        },
+                        for size in PAGE_SIZES {
+                            option { value: "{size}", "{size}" }
+                        }
+                    }
+                }
+
+                div { class: "w-40 text-center font-mono font-medium text-blue-700", "{pagination_text}" }
+
+                div { class: "flex items-center justify-end gap-2",
+
+                    // First/last page only pay off with a few pages
+                    if total_pages > 3 {
+                        button {
+                            class: "px-3 py-1 text-blue-600 font-bold hover:bg-blue-50 rounded transition disabled:text-slate-300 disabled:hover:bg-transparent",
+                            disabled: prev_disabled,
+                            onclick: move |_| state.go_to_page(1),
+                            title: "First page",
+                            "⟪"
+                        }
+                    }
+
+                    button {
+                        class: "w-20 px-2 py-1 text-blue-600 font-bold text-right hover:bg-blue-50 rounded transition disabled:text-slate-300 disabled:hover:bg-transparent",
+                        disabled: prev_disabled,
+                        onclick: move |_| state.go_to_page((state.page)() - 1),
+                        "PREV"
+                    }
+
+                    div { class: "w-[2px] h-4 bg-blue-200" }
+
+                    button {
+                        class: "w-20 px-2 py-1 text-blue-600 font-bold text-left hover:bg-blue-50 rounded transition disabled:text-slate-300 disabled:hover:bg-transparent",
+                        disabled: next_disabled,
+                        onclick: move |_| state.go_to_page((state.page)() + 1),
+                        "NEXT"
+                    }
+
+                    if total_pages > 3 {
+                        button {
+                            class: "px-3 py-1 text-blue-600 font-bold hover:bg-blue-50 rounded transition disabled:text-slate-300 disabled:hover:bg-transparent",
+                            disabled: next_disabled,
+                            onclick: move |_| state.go_to_page(total_pages),
+                            title: "Last page",
+                            "⟫"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use dioxus::prelude::*;
+use std::collections::BTreeMap;
+
+use crate::models::email::{render, EmailTemplate, SaveEmailTemplateRequest};
+
+#[component]
+pub fn EmailTemplateTable() -> Element {
+    // EDIT MODAL STATE
+    let mut editing = use_signal(|| None::<EmailTemplate>);
+    let mut subject = use_signal(String::new);
+    let mut body = use_signal(String::new);
+    let mut save_error = use_signal(|| None::<String>);
+
+    // TEST MAIL STATE
+    let mut test_to = use_signal(String::new);
+    let mut test_result = use_signal(|| None::<Result<String, String>>);
+
+    let mut templates_resource = use_resource(crate::server::mail::list_email_templates);
+
+    // Live preview with each variable shown as <name>
+    let preview = editing().map(|template| {
+        let vars: BTreeMap<String, String> = template
+            .variables
+            .iter()
+            .map(|name| (name.clone(), format!("<{}>", name)))
+            .collect();
+        (render(&subject(), &vars), render(&body(), &vars))
+    });
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full relative",
+
+            // HEADER
+            div { class: "flex justify-between items-center px-2",
+                p { class: "text-sm text-slate-500",
+                    "Use "
+                    code { class: "bg-slate-100 px-1 rounded", "{{{{variable}}}}" }
+                    " placeholders from the list of each template."
+                }
+            }
+
+            // TABLE
+            div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                table { class: "min-w-full divide-y divide-slate-200",
+                    thead { class: "bg-blue-50",
+                        tr {
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Template" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Subject" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Variables" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Last changed" }
+                            th { class: "w-32 px-6 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Actions" }
+                        }
+                    }
+                    tbody { class: "divide-y divide-slate-200 bg-white",
+                        match &*templates_resource.read() {
+                            Some(Ok(templates)) if templates.is_empty() => rsx! {
+                                tr {
+                                    td { colspan: "5", class: "px-6 py-8 text-center text-slate-500", "No email templates found." }
+                                }
+                            },
+                            Some(Ok(templates)) => rsx! {
+                                for template in templates.iter() {
+                                    tr { class: "h-14 hover:bg-blue-50 transition-colors", key: "{template.key}",
+                                        td { class: "px-6 py-3",
+                                            div { class: "text-sm font-mono", "{template.key}" }
+                                            div { class: "text-xs text-slate-500", "{template.description}" }
+                                        }
+                                        td { class: "px-6 py-3 text-sm", "{template.subject}" }
+                                        td { class: "px-6 py-3 text-xs font-mono", {template.variables.join(", ")} }
+                                        td { class: "px-6 py-3 text-sm whitespace-nowrap", {template.updated_at.format("%Y-%m-%d %H:%M").to_string()} }
+                                        td { class: "px-6 py-3 text-center",
+                                            button {
+                                                class: "px-3 py-1 text-sm rounded-md text-blue-600 hover:bg-blue-50 transition",
+                                                onclick: {
+                                                    let template = template.clone();
+                                                    move |_| {
+                                                        subject.set(template.subject.clone());
+                                                        body.set(template.body.clone());
+                                                        save_error.set(None);
+                                                        test_result.set(None);
+                                                        editing.set(Some(template.clone()));
+                                                    }
+                                                },
+                                                "Edit"
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Err(e)) => rsx! {
+                                tr {
+                                    td { colspan: "5", class: "px-6 py-8 text-center text-red-600", "Could not load email templates: {e}" }
+                                }
+                            },
+                            None => rsx! {
+                                tr {
+                                    td { colspan: "5", class: "px-6 py-4",
+                                        div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+
+            // EDIT MODAL
+            if let Some(template) = editing() {
+                div {
+                    class: "fixed inset-0 z-50 flex items-center justify-center bg-black/40 backdrop-blur-sm",
+                    onclick: move |_| editing.set(None),
+
+                    div {
+                        class: "bg-white rounded-2xl shadow-2xl w-[720px] max-h-[90vh] overflow-y-auto",
+                        onclick: move |e| e.stop_propagation(),
+
+                        // HEADER
+                        div { class: "px-8 py-5 border-b",
+                            h2 { class: "text-lg font-semibold text-slate-800", "Edit " code { "{template.key}" } }
+                            p { class: "text-sm text-slate-500", "{template.description}" }
+                        }
+
+                        // BODY
+                        div { class: "px-8 py-6 flex flex-col gap-5",
+                            div { class: "flex flex-wrap gap-2",
+                                for name in template.variables.iter() {
+                                    code { class: "text-xs bg-blue-50 text-blue-700 px-2 py-0.5 rounded", {format!("{{{{{}}}}}", name)} }
+                                }
+                            }
+                            div { class: "flex flex-col gap-1",
+                                label { class: "text-sm font-medium text-slate-700", "Subject" }
+                                input {
+                                    class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                    value: "{subject}",
+                                    oninput: move |e| subject.set(e.value())
+                                }
+                            }
+                            div { class: "flex flex-col gap-1",
+                                label { class: "text-sm font-medium text-slate-700", "Body" }
+                                textarea {
+                                    class: "border rounded-lg px-4 py-2 h-48 font-mono text-sm focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                    value: "{body}",
+                                    oninput: move |e| body.set(e.value())
+                                }
+                            }
+                            if let Some((preview_subject, preview_body)) = preview {
+                                div { class: "flex flex-col gap-1",
+                                    label { class: "text-sm font-medium text-slate-700", "Preview" }
+                                    div { class: "border rounded-lg bg-slate-50 px-4 py-3 text-sm",
+                                        div { class: "font-semibold mb-2", "{preview_subject}" }
+                                        pre { class: "whitespace-pre-wrap font-sans", "{preview_body}" }
+                                    }
+                                }
+                            }
+                            div { class: "flex flex-col gap-1",
+                                label { class: "text-sm font-medium text-slate-700", "Send saved version as test" }
+                                div { class: "flex gap-2",
+                                    input {
+                                        class: "flex-1 border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                        r#type: "email",
+                                        placeholder: "you@example.com",
+                                        value: "{test_to}",
+                                        oninput: move |e| test_to.set(e.value())
+                                    }
+                                    button {
+                                        class: "px-4 py-2 rounded-lg border border-slate-300 text-slate-600 hover:bg-slate-100 transition disabled:opacity-50",
+                                        disabled: test_to().trim().is_empty(),
+                                        onclick: {
+                                            let key = template.key.clone();
+                                            move |_| {
+                                                let key = key.clone();
+                                                async move {
+                                                    let result = crate::server::mail::send_test_email(key, test_to()).await;
+                                                    test_result.set(Some(result.map(|_| format!("Test mail sent to {}", test_to())).map_err(|e| e.to_string())));
+                                                }
+                                            }
+                                        },
+                                        "Send test"
+                                    }
+                                }
+                                match test_result() {
+                                    Some(Ok(message)) => rsx! { span { class: "text-sm text-emerald-700", "{message}" } },
+                                    Some(Err(message)) => rsx! { span { class: "text-sm text-red-600", "{message}" } },
+                                    None => rsx! {},
+                                }
+                            }
+                            if let Some(message) = save_error() {
+                                span { class: "text-sm text-red-600", "{message}" }
+                            }
+                        }
+
+                        // FOOTER
+                        div { class: "px-8 py-5 bg-slate-50 flex justify-end gap-3",
+                            button {
+                                class: "px-4 py-2 rounded-lg border border-slate-300 text-slate-600 hover:bg-slate-100 transition",
+                                onclick: move |_| editing.set(None),
+                                "Cancel"
+                            }
+                            button {
+                                class: "px-5 py-2 rounded-lg bg-blue-600 text-white font-semibold hover:bg-blue-700 transition shadow-md disabled:opacity-50 disabled:cursor-not-allowed",
+                                disabled: subject().trim().is_empty() || body().trim().is_empty(),
+                                onclick: {
+                                    let key = template.key.clone();
+                                    move |_| {
+                                        let key = key.clone();
+                                        async move {
+                                            let req = SaveEmailTemplateRequest { subject: subject(), body: body() };
+                                            match crate::server::mail::update_email_template(key, req).await {
+                                                Ok(()) => {
+                                                    editing.set(None);
+                                                    templates_resource.restart();
+                                                }
+                                                Err(e) => save_error.set(Some(e.to_string())),
+                                            }
+                                        }
+                                    }
+                                },
+                                "Save Template"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
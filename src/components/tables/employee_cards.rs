@@ -0,0 +1,151 @@
+use dioxus::prelude::*;
+
+use super::employee_data::{use_employee_feed, EmployeeFeed, EmployeeTableState};
+
+// Horizontal travel that counts as a swipe, in CSS pixels
+const SWIPE_DISTANCE: f64 = 60.0;
+
+/// Phone layout of the employee list: one card per employee under a sticky
+/// search bar, loading more as the end scrolls into view. Swiping a card to the
+/// left, long-pressing it or its ⋯ button shows Edit and Delete; the dialogs
+/// are the table's [`super::employee_table::EmployeeModals`].
+#[component]
+pub fn EmployeeCardList(table: EmployeeTableState) -> Element {
+    let feed = use_employee_feed(table);
+    let EmployeeFeed { employees, total_count, loading, error, .. } = feed;
+    let search_term = table.list.search;
+
+    // Card whose actions are shown, and where a swipe started
+    let mut revealed = use_signal(|| None::<i64>);
+    let mut swipe_start = use_signal(|| None::<(i64, f64)>);
+
+    rsx! {
+        div { class: "flex flex-col gap-3 w-full",
+
+            // SEARCH, stays on top while scrolling
+            div { class: "sticky top-0 z-10 flex gap-2 px-3 py-3 bg-white/95 backdrop-blur border-b border-slate-200",
+                input {
+                    class: "flex-1 min-w-0 border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none",
+                    r#type: "search",
+                    placeholder: "Search employees...",
+                    value: "{search_term}",
+                    oninput: move |evt| table.list.set_search(evt.value())
+                }
+                button {
+                    class: "bg-blue-600 hover:bg-blue-700 text-white font-bold px-4 rounded-lg shadow-md",
+                    aria_label: "Add employee",
+                    onclick: move |_| table.open_add(),
+                    "+"
+                }
+            }
+
+            if let Some(total) = total_count() {
+                p { class: "px-3 text-xs text-slate-500", "{employees.read().len()} of {total}" }
+            }
+
+            ul { class: "flex flex-col gap-2 px-3",
+                for emp in employees() {
+                    li {
+                        key: "{emp.id}",
+                        class: "rounded-xl bg-white shadow overflow-hidden select-none touch-pan-y",
+                        onpointerdown: {
+                            let id = emp.id;
+                            move |evt: PointerEvent| swipe_start.set(Some((id, evt.client_coordinates().x)))
+                        },
+                        onpointerup: {
+                            let id = emp.id;
+                            move |evt: PointerEvent| {
+                                if let Some((start_id, start_x)) = swipe_start() {
+                                    let dx = evt.client_coordinates().x - start_x;
+                                    if start_id == id && dx <= -SWIPE_DISTANCE {
+                                        revealed.set(Some(id));
+                                    } else if start_id == id && dx >= SWIPE_DISTANCE {
+                                        revealed.set(None);
+                                    }
+                                }
+                                swipe_start.set(None);
+                            }
+                        },
+                        // Long-press opens the context menu on touch screens
+                        oncontextmenu: {
+                            let id = emp.id;
+                            move |evt: MouseEvent| {
+                                evt.prevent_default();
+                                revealed.set(Some(id));
+                            }
+                        },
+
+                        div { class: "flex items-center gap-3 p-4",
+                            div { class: "flex-1 min-w-0",
+                                p { class: "font-semibold text-slate-800 truncate", "{emp.first_name} {emp.last_name}" }
+                                p { class: "text-sm text-slate-500 truncate", "{emp.email}" }
+                            }
+                            button {
+                                class: "p-2 text-slate-500 hover:bg-slate-100 rounded-md",
+                                aria_label: "Show actions",
+                                aria_expanded: "{revealed() == Some(emp.id)}",
+                                onclick: {
+                                    let id = emp.id;
+                                    move |_| revealed.set(if revealed() == Some(id) { None } else { Some(id) })
+                                },
+                                "⋯"
+                            }
+                        }
+
+                        if revealed() == Some(emp.id) {
+                            div { class: "flex border-t border-slate-100 text-sm font-semibold",
+                                button {
+                                    class: "flex-1 py-3 text-blue-600 hover:bg-blue-50",
+                                    onclick: {
+                                        let emp = emp.clone();
+                                        move |_| {
+                                            revealed.set(None);
+                                            table.open_edit(&emp);
+                                        }
+                                    },
+                                    "Edit"
+                                }
+                                button {
+                                    class: "flex-1 py-3 text-red-600 hover:bg-red-50 border-l border-slate-100",
+                                    onclick: {
+                                        let emp = emp.clone();
+                                        move |_| {
+                                            revealed.set(None);
+                                            table.ask_delete(&emp);
+                                        }
+                                    },
+                                    "Delete"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(e) = error() {
+                p { class: "px-3 py-6 text-center text-red-600", "Could not load employees: {e}" }
+            } else if total_count() == Some(0) {
+                p { class: "px-3 py-6 text-center text-slate-500", "No employees found." }
+            } else if loading() {
+                for _ in 0..3 {
+                    div { class: "mx-3 h-20 rounded-xl bg-slate-100 animate-pulse" }
+                }
+            } else if !feed.complete() {
+                // Loads the next page as soon as it scrolls into view; the button is the fallback
+                div {
+                    class: "px-3 pb-6",
+                    onvisible: move |evt| {
+                        if evt.is_intersecting().unwrap_or(false) {
+                            spawn(feed.load_more());
+                        }
+                    },
+                    button {
+                        class: "w-full py-3 text-blue-600 font-bold rounded-lg border border-blue-100 bg-white",
+                        onclick: move |_| feed.load_more(),
+                        "Load more"
+                    }
+                }
+            }
+        }
+    }
+}
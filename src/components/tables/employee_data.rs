@@ -0,0 +1,445 @@
+//! State and data access of the employee list, kept apart from its markup.
+//!
+//! [`use_employee_table`] owns the modal forms and a [`TableState`] and loads
+//! through an [`EmployeeBackend`]: the server functions in the app, a local
+//! SQLite file in the offline desktop build, a fake in the component tests.
+//! Provide another one with [`use_employee_backend_provider`].
+
+use dioxus::prelude::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use super::data_table::{use_table_state, TablePage, TableQuery, TableRow, TableState};
+use crate::components::forms::form_state::{use_form, Field, FormSpec, FormState, FormValues};
+use crate::components::layout::toast::{use_toasts, Toast, Toasts};
+use crate::components::auth::use_auth;
+#[cfg(feature = "offline")]
+use crate::server::employees;
+use crate::models::employee::{
+    CreateEmployeeRequest, Employee, EmployeeRequest, EmployeeResponse, EmployeeSortField, FieldError,
+    UpdateEmployeeRequest,
+};
+
+pub type BackendFuture<T> = Pin<Box<dyn Future<Output = Result<T, ServerFnError>>>>;
+
+/// Where the employee list loads and saves.
+pub trait EmployeeBackend {
+    fn list(&self, req: EmployeeRequest) -> BackendFuture<EmployeeResponse>;
+    fn add(&self, req: CreateEmployeeRequest) -> BackendFuture<()>;
+    fn update(&self, req: UpdateEmployeeRequest) -> BackendFuture<()>;
+    fn delete(&self, id: i64) -> BackendFuture<()>;
+    /// Undoes a recent delete, with the same id and fields
+    fn restore(&self, id: i64) -> BackendFuture<()>;
+}
+
+/// The `#[server]` functions, used unless a component above provides another backend.
+pub struct ServerBackend;
+
+impl EmployeeBackend for ServerBackend {
+    fn list(&self, req: EmployeeRequest) -> BackendFuture<EmployeeResponse> {
+        Box::pin(crate::server::get_employees(req))
+    }
+
+    fn add(&self, req: CreateEmployeeRequest) -> BackendFuture<()> {
+        Box::pin(crate::server::add_employee(req))
+    }
+
+    fn update(&self, req: UpdateEmployeeRequest) -> BackendFuture<()> {
+        Box::pin(crate::server::update_employee(req))
+    }
+
+    fn delete(&self, id: i64) -> BackendFuture<()> {
+        Box::pin(crate::server::delete_employee(id))
+    }
+
+    fn restore(&self, id: i64) -> BackendFuture<()> {
+        Box::pin(crate::server::restore_employee(id))
+    }
+}
+
+/// The local SQLite file of the offline desktop build, through the same
+/// service functions the server functions call. There is no login offline.
+#[cfg(feature = "offline")]
+pub struct LocalBackend;
+
+#[cfg(feature = "offline")]
+impl EmployeeBackend for LocalBackend {
+    fn list(&self, req: EmployeeRequest) -> BackendFuture<EmployeeResponse> {
+        Box::pin(async move {
+            let pool = local_db().await?;
+            Ok(employees::list(&pool, &req).await?)
+        })
+    }
+
+    fn add(&self, req: CreateEmployeeRequest) -> BackendFuture<()> {
+        Box::pin(async move {
+            let pool = local_db().await?;
+            employees::create(&pool, &req).await?;
+            Ok(())
+        })
+    }
+
+    fn update(&self, req: UpdateEmployeeRequest) -> BackendFuture<()> {
+        Box::pin(async move {
+            let pool = local_db().await?;
+            employees::update(&pool, &req).await?;
+            Ok(())
+        })
+    }
+
+    fn delete(&self, id: i64) -> BackendFuture<()> {
+        Box::pin(async move {
+            let pool = local_db().await?;
+            employees::delete(&pool, id).await?;
+            Ok(())
+        })
+    }
+
+    fn restore(&self, id: i64) -> BackendFuture<()> {
+        Box::pin(async move {
+            let pool = local_db().await?;
+            employees::restore(&pool, id).await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "offline")]
+async fn local_db() -> Result<sqlx::SqlitePool, ServerFnError> {
+    employees::sqlite::connect_local()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Local database failed: {}", e)))
+}
+
+/// Writes the local employees as CSV next to the database file, ready for
+/// `employee import` on a server. Returns the file and the number of rows.
+#[cfg(feature = "offline")]
+pub async fn export_local() -> Result<(std::path::PathBuf, usize), ServerFnError> {
+    let pool = local_db().await?;
+    let path = employees::sqlite::path().with_extension("csv");
+    let file = std::fs::File::create(&path).map_err(|e| ServerFnError::new(format!("{}: {}", path.display(), e)))?;
+    let count = employees::export_csv(&pool, file).await.map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok((path, count))
+}
+
+#[derive(Clone)]
+struct BackendContext(Rc<dyn EmployeeBackend>);
+
+// The app only swaps the backend in the offline build, the tests always
+/// Makes `backend` the one employee lists below this component use.
+#[cfg(any(test, feature = "offline"))]
+pub fn use_employee_backend_provider(backend: impl FnOnce() -> Rc<dyn EmployeeBackend>) {
+    use_context_provider(|| BackendContext(backend()));
+}
+
+/// The provided backend, or [`ServerBackend`]
+pub fn use_employee_backend() -> Rc<dyn EmployeeBackend> {
+    use_hook(|| try_consume_context::<BackendContext>().map(|c| c.0).unwrap_or_else(|| Rc::new(ServerBackend)))
+}
+
+/// Signals behind an employee list. Copy, so every event handler can take it by value.
+#[derive(Clone, Copy, PartialEq)]
+pub struct EmployeeTableState {
+    // Page, search, sort and the loaded page; its revision bumps after every successful write
+    pub list: TableState<Employee>,
+
+    pub show_add_modal: Signal<bool>,
+    pub add_form: FormState,
+
+    pub show_edit_modal: Signal<bool>,
+    pub edit_id: Signal<Option<i64>>,
+    pub edit_form: FormState,
+
+    pub show_delete_modal: Signal<bool>,
+    pub delete_target: Signal<Option<Employee>>,
+
+    backend: CopyValue<Rc<dyn EmployeeBackend>>,
+    toasts: Toasts,
+}
+
+impl TableRow for Employee {
+    fn row_key(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+fn sort_field(key: &str) -> EmployeeSortField {
+    [EmployeeSortField::Id, EmployeeSortField::FirstName, EmployeeSortField::LastName, EmployeeSortField::Email]
+        .into_iter()
+        .find(|field| field.as_str() == key)
+        .unwrap_or_default()
+}
+
+pub fn use_employee_table() -> EmployeeTableState {
+    let backend = use_employee_backend();
+    let backend = use_hook(|| CopyValue::new(backend));
+
+    // Reading the user subscribes the list, so it reloads on login/logout
+    let auth = use_auth();
+
+    let list = use_table_state(5, move |query: TableQuery| {
+        let _user = auth.user.read().clone();
+        let (sort_by, sort_dir) = query.sort.map(|(key, dir)| (sort_field(key), dir)).unwrap_or_default();
+        let req = EmployeeRequest {
+            page: query.page,
+            page_size: query.page_size,
+            search_term: query.search,
+            sort_by,
+            sort_dir,
+        };
+        let list = backend.read().list(req);
+        Box::pin(async move {
+            let res = list.await?;
+            Ok::<_, ServerFnError>(TablePage { rows: res.employees, total_count: res.total_count })
+        })
+    });
+
+    EmployeeTableState {
+        list,
+        show_add_modal: use_signal(|| false),
+        add_form: use_form(employee_form),
+        show_edit_modal: use_signal(|| false),
+        edit_id: use_signal(|| None),
+        edit_form: use_form(employee_form),
+        show_delete_modal: use_signal(|| false),
+        delete_target: use_signal(|| None),
+        backend,
+        toasts: use_toasts(),
+    }
+}
+
+/// First name, last name and email, checked like the server checks them. A
+/// taken email (409) is shown under the email field.
+fn employee_form() -> FormSpec {
+    FormSpec::new(vec![
+        Field::new("first_name", "First name").required(),
+        Field::new("last_name", "Last name").required(),
+        Field::new("email", "Email").input_type("email").required(),
+    ])
+    .validate(|values| create_request(values).validate().err().unwrap_or_default())
+    .server_errors(|e| match e {
+        ServerFnError::ServerError { code: 409, message, .. } => {
+            Some(FieldError { field: "email".to_string(), message: message.clone() })
+        }
+        _ => None,
+    })
+}
+
+fn create_request(values: &FormValues) -> CreateEmployeeRequest {
+    CreateEmployeeRequest {
+        first_name: values.get("first_name").to_string(),
+        last_name: values.get("last_name").to_string(),
+        email: values.get("email").to_string(),
+    }
+}
+
+impl EmployeeTableState {
+    pub fn open_add(mut self) {
+        self.show_add_modal.set(true);
+    }
+
+    pub fn close_add(mut self) {
+        self.show_add_modal.set(false);
+    }
+
+    /// Saves the add form; on success clears and closes it and reloads the list.
+    pub async fn save_new(mut self) {
+        let backend = self.backend;
+        let saved = self.add_form.submit(move |values| backend.read().add(create_request(&values))).await;
+        if saved {
+            self.add_form.clear();
+            self.show_add_modal.set(false);
+            self.list.reload();
+            self.toasts.success("Employee added");
+        }
+    }
+
+    pub fn open_edit(mut self, employee: &Employee) {
+        self.edit_id.set(Some(employee.id));
+        self.edit_form.reset(
+            FormValues::default()
+                .with("first_name", employee.first_name.clone())
+                .with("last_name", employee.last_name.clone())
+                .with("email", employee.email.clone()),
+        );
+        self.show_edit_modal.set(true);
+    }
+
+    pub fn close_edit(mut self) {
+        self.show_edit_modal.set(false);
+    }
+
+    /// Saves the edit form; on success closes it and reloads the list. An
+    /// unchanged form just closes.
+    pub async fn save_edit(mut self) {
+        let Some(id) = (self.edit_id)() else { return };
+        if !self.edit_form.is_dirty() {
+            self.show_edit_modal.set(false);
+            return;
+        }
+
+        let backend = self.backend;
+        let saved = self
+            .edit_form
+            .submit(move |values| {
+                let CreateEmployeeRequest { first_name, last_name, email } = create_request(&values);
+                backend.read().update(UpdateEmployeeRequest { id, first_name, last_name, email })
+            })
+            .await;
+        if saved {
+            self.show_edit_modal.set(false);
+            self.list.reload();
+            self.toasts.success("Employee updated");
+        }
+    }
+
+    pub fn ask_delete(mut self, employee: &Employee) {
+        self.delete_target.set(Some(employee.clone()));
+        self.show_delete_modal.set(true);
+    }
+
+    pub fn cancel_delete(mut self) {
+        self.show_delete_modal.set(false);
+        self.delete_target.set(None);
+    }
+
+    /// Deletes the employee asked about and reloads the list. The dialog closes
+    /// either way; a toast tells how it went and offers to undo.
+    pub async fn confirm_delete(self) {
+        if let Some(employee) = (self.delete_target)() {
+            let delete = self.backend.read().delete(employee.id);
+            match delete.await {
+                Ok(()) => {
+                    self.list.reload_after_removal();
+                    self.toasts.show(Toast::success("Employee deleted").undo(self.restore(employee.id)));
+                }
+                Err(e) => self.toasts.error(format!("Could not delete the employee: {}", e)),
+            }
+        }
+        self.cancel_delete();
+    }
+
+    /// Exports the local file as CSV and tells where it went.
+    #[cfg(feature = "offline")]
+    pub async fn export_csv(self) {
+        match export_local().await {
+            Ok((path, count)) => self.toasts.success(format!("Exported {} employees to {}", count, path.display())),
+            Err(e) => self.toasts.error(format!("Export failed: {}", e)),
+        }
+    }
+
+    // Brings a deleted employee back as it was, id included
+    fn restore(self, id: i64) -> impl Fn() {
+        let backend = Rc::clone(&self.backend.read());
+        let (list, toasts) = (self.list, self.toasts);
+        move || {
+            let restore = backend.restore(id);
+            spawn(async move {
+                match restore.await {
+                    Ok(()) => {
+                        list.reload();
+                        toasts.success("Employee restored");
+                    }
+                    Err(e) => toasts.error(format!("Could not restore the employee: {}", e)),
+                }
+            });
+        }
+    }
+}
+
+// Rows fetched per step of the card list's infinite scroll
+pub const FEED_PAGE_SIZE: i64 = 20;
+
+/// The employees of an [`EmployeeTableState`] as one growing list, for layouts
+/// that scroll instead of paging. Follows the table's search and sort, and
+/// starts over when either changes or a write went through.
+#[derive(Clone, Copy, PartialEq)]
+pub struct EmployeeFeed {
+    pub employees: Signal<Vec<Employee>>,
+    pub total_count: Signal<Option<i64>>,
+    pub loading: Signal<bool>,
+    pub error: Signal<Option<String>>,
+    next_page: Signal<i64>,
+    // Lets a response that arrives after a restart be dropped
+    generation: Signal<u64>,
+    table: EmployeeTableState,
+}
+
+pub fn use_employee_feed(table: EmployeeTableState) -> EmployeeFeed {
+    let feed = EmployeeFeed {
+        employees: use_signal(Vec::new),
+        total_count: use_signal(|| None),
+        loading: use_signal(|| false),
+        error: use_signal(|| None),
+        next_page: use_signal(|| 1),
+        generation: use_signal(|| 0),
+        table,
+    };
+
+    use_effect(move || {
+        let _search = table.list.search_query.read();
+        let _sort = table.list.sort.read();
+        let _revision = table.list.revision.read();
+        feed.restart();
+        spawn(feed.load_more());
+    });
+
+    feed
+}
+
+impl EmployeeFeed {
+    /// Whether every matching employee is loaded
+    pub fn complete(&self) -> bool {
+        self.total_count.read().is_some_and(|total| self.employees.read().len() as i64 >= total)
+    }
+
+    fn restart(mut self) {
+        *self.generation.write() += 1;
+        self.employees.set(Vec::new());
+        self.total_count.set(None);
+        self.loading.set(false);
+        self.error.set(None);
+        self.next_page.set(1);
+    }
+
+    /// Appends the next page unless one is already on its way or all are there.
+    pub async fn load_more(mut self) {
+        if *self.loading.peek() || self.complete_peek() {
+            return;
+        }
+        let generation = *self.generation.peek();
+        self.loading.set(true);
+
+        let search = self.table.list.search_query.peek().clone();
+        let (sort_by, sort_dir) =
+            (*self.table.list.sort.peek()).map(|(key, dir)| (sort_field(key), dir)).unwrap_or_default();
+        let req = EmployeeRequest {
+            page: *self.next_page.peek(),
+            page_size: FEED_PAGE_SIZE,
+            search_term: if search.is_empty() { None } else { Some(search) },
+            sort_by,
+            sort_dir,
+        };
+        let list = self.table.backend.peek().list(req);
+        let result = list.await;
+
+        if *self.generation.peek() != generation {
+            return;
+        }
+        self.loading.set(false);
+        match result {
+            Ok(res) => {
+                self.employees.write().extend(res.employees);
+                self.total_count.set(Some(res.total_count));
+                *self.next_page.write() += 1;
+            }
+            Err(e) => self.error.set(Some(e.to_string())),
+        }
+    }
+
+    // complete() without subscribing the caller
+    fn complete_peek(&self) -> bool {
+        self.total_count.peek().is_some_and(|total| self.employees.peek().len() as i64 >= total)
+    }
+}
@@ -0,0 +1,470 @@
+//! [`EmployeeTableView`] and [`EmployeeCardList`] rendered headlessly on a
+//! `VirtualDom` over a fake backend, with the modals and toasts. The tests act through
+//! [`EmployeeTableState`], the same calls the buttons make, and check the
+//! server-rendered HTML.
+
+use dioxus::dioxus_core::NoOpMutations;
+use dioxus::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::rc::Rc;
+use std::time::Duration;
+
+use super::data_table::SEARCH_DEBOUNCE;
+use super::employee_data::{
+    use_employee_backend_provider, use_employee_table, BackendFuture, EmployeeBackend, EmployeeTableState,
+};
+use super::employee_cards::EmployeeCardList;
+use super::employee_table::{EmployeeModals, EmployeeTableView};
+use crate::components::auth::AuthState;
+use crate::components::layout::toast::{use_toast_provider, ToastStack, Toasts};
+use crate::models::employee::{
+    CreateEmployeeRequest, Employee, EmployeeRequest, EmployeeResponse, SortDirection, UpdateEmployeeRequest,
+};
+
+#[derive(Default)]
+struct FakeBackend {
+    employees: RefCell<Vec<Employee>>,
+    // What delete kept for restore
+    deleted: RefCell<Vec<Employee>>,
+    list_calls: Cell<usize>,
+    fail_writes: Cell<bool>,
+}
+
+impl FakeBackend {
+    fn with(count: i64) -> Rc<Self> {
+        let employees = (1..=count)
+            .map(|id| Employee {
+                id,
+                first_name: format!("First{}", id),
+                last_name: format!("Last{}", id),
+                email: format!("employee{}@example.com", id),
+            })
+            .collect();
+        Rc::new(Self { employees: RefCell::new(employees), ..Default::default() })
+    }
+
+    fn write(&self, change: impl FnOnce(&mut Vec<Employee>)) -> BackendFuture<()> {
+        if self.fail_writes.get() {
+            return Box::pin(std::future::ready(Err(ServerFnError::new("write failed"))));
+        }
+        change(&mut self.employees.borrow_mut());
+        Box::pin(std::future::ready(Ok(())))
+    }
+}
+
+impl EmployeeBackend for FakeBackend {
+    fn list(&self, req: EmployeeRequest) -> BackendFuture<EmployeeResponse> {
+        self.list_calls.set(self.list_calls.get() + 1);
+        let search = req.search_term.unwrap_or_default().to_lowercase();
+        let mut matching: Vec<Employee> = self
+            .employees
+            .borrow()
+            .iter()
+            .filter(|e| format!("{} {} {}", e.first_name, e.last_name, e.email).to_lowercase().contains(&search))
+            .cloned()
+            .collect();
+        // By id is enough to tell the directions apart
+        if req.sort_dir == SortDirection::Desc {
+            matching.reverse();
+        }
+        let employees = matching
+            .iter()
+            .skip(((req.page - 1) * req.page_size) as usize)
+            .take(req.page_size as usize)
+            .cloned()
+            .collect();
+        let res = EmployeeResponse { employees, total_count: matching.len() as i64 };
+        Box::pin(std::future::ready(Ok(res)))
+    }
+
+    fn add(&self, req: CreateEmployeeRequest) -> BackendFuture<()> {
+        if self.employees.borrow().iter().any(|e| e.email == req.email) {
+            let taken = ServerFnError::ServerError {
+                message: "An employee with this email already exists".to_string(),
+                code: 409,
+                details: None,
+            };
+            return Box::pin(std::future::ready(Err(taken)));
+        }
+        self.write(|employees| {
+            let id = employees.iter().map(|e| e.id).max().unwrap_or(0) + 1;
+            employees.push(Employee { id, first_name: req.first_name, last_name: req.last_name, email: req.email });
+        })
+    }
+
+    fn update(&self, req: UpdateEmployeeRequest) -> BackendFuture<()> {
+        self.write(|employees| {
+            let employee = employees.iter_mut().find(|e| e.id == req.id).unwrap();
+            *employee = Employee { id: req.id, first_name: req.first_name, last_name: req.last_name, email: req.email };
+        })
+    }
+
+    fn delete(&self, id: i64) -> BackendFuture<()> {
+        self.write(|employees| {
+            let index = employees.iter().position(|e| e.id == id).unwrap();
+            self.deleted.borrow_mut().push(employees.remove(index));
+        })
+    }
+
+    fn restore(&self, id: i64) -> BackendFuture<()> {
+        self.write(|employees| {
+            let mut deleted = self.deleted.borrow_mut();
+            let index = deleted.iter().position(|e| e.id == id).unwrap();
+            employees.push(deleted.remove(index));
+            employees.sort_by_key(|e| e.id);
+        })
+    }
+}
+
+#[derive(Clone)]
+struct HarnessProps {
+    backend: Rc<FakeBackend>,
+    table: Rc<Cell<Option<EmployeeTableState>>>,
+    toasts: Rc<Cell<Option<Toasts>>>,
+    cards: bool,
+}
+
+#[allow(non_snake_case)]
+fn Harness(props: HarnessProps) -> Element {
+    use_context_provider(|| AuthState { user: Signal::new(None) });
+    props.toasts.set(Some(use_toast_provider()));
+    let backend = props.backend.clone();
+    use_employee_backend_provider(move || backend as Rc<dyn EmployeeBackend>);
+
+    let table = use_employee_table();
+    props.table.set(Some(table));
+    rsx! {
+        if props.cards {
+            EmployeeCardList { table }
+        } else {
+            EmployeeTableView { table }
+        }
+        EmployeeModals { table }
+        ToastStack {}
+    }
+}
+
+struct Rendered {
+    dom: VirtualDom,
+    backend: Rc<FakeBackend>,
+    table: EmployeeTableState,
+    toasts: Toasts,
+}
+
+impl Rendered {
+    async fn new(backend: Rc<FakeBackend>) -> Self {
+        Self::render(backend, false).await
+    }
+
+    async fn cards(backend: Rc<FakeBackend>) -> Self {
+        Self::render(backend, true).await
+    }
+
+    async fn render(backend: Rc<FakeBackend>, cards: bool) -> Self {
+        let (table, toasts) = (Rc::new(Cell::new(None)), Rc::new(Cell::new(None)));
+        let props = HarnessProps { backend: backend.clone(), table: table.clone(), toasts: toasts.clone(), cards };
+        let mut dom = VirtualDom::new_with_props(Harness, props);
+        dom.rebuild_in_place();
+        let table = table.get().expect("harness rendered");
+        let toasts = toasts.get().expect("harness rendered");
+
+        let mut rendered = Self { dom, backend, table, toasts };
+        rendered.settle().await;
+        rendered
+    }
+
+    /// Runs tasks and re-renders until nothing is left to do
+    async fn settle(&mut self) {
+        while tokio::time::timeout(Duration::from_millis(20), self.dom.wait_for_work()).await.is_ok() {
+            self.dom.render_immediate(&mut NoOpMutations);
+        }
+    }
+
+    async fn act(&mut self, action: impl FnOnce(EmployeeTableState)) {
+        let table = self.table;
+        self.dom.in_runtime(|| action(table));
+        self.settle().await;
+    }
+
+    /// Types `term` into the search box and waits until it applies
+    async fn search(&mut self, term: &str) {
+        let table = self.table;
+        self.dom.in_runtime(|| table.list.set_search(term.to_string()));
+        self.settle().await;
+        tokio::time::sleep(SEARCH_DEBOUNCE).await;
+        self.settle().await;
+    }
+
+    /// For the actions that call the backend, which run as tasks like click handlers do
+    async fn act_async<F: Future<Output = ()> + 'static>(&mut self, action: impl FnOnce(EmployeeTableState) -> F) {
+        let table = self.table;
+        self.dom.in_scope(ScopeId::APP, || spawn(action(table)));
+        self.settle().await;
+    }
+
+    fn html(&self) -> String {
+        dioxus_ssr::render(&self.dom)
+    }
+
+    /// Whether the button labelled `label` carries the disabled attribute
+    fn disabled(&self, label: &str) -> bool {
+        let html = self.html();
+        let end = html.find(&format!(">{}</button>", label)).unwrap_or_else(|| panic!("no {} button", label));
+        let start = html[..end].rfind("<button").unwrap();
+        html[start..end].contains(" disabled=")
+    }
+
+    fn fill_add_form(&self, first_name: &str, last_name: &str, email: &str) {
+        let form = self.table.add_form;
+        self.dom.in_runtime(|| {
+            form.set("first_name", first_name.to_string());
+            form.set("last_name", last_name.to_string());
+            form.set("email", email.to_string());
+        });
+    }
+}
+
+#[tokio::test]
+async fn first_and_last_page_show_their_range() {
+    let mut page = Rendered::new(FakeBackend::with(22)).await;
+
+    assert!(page.html().contains(">1-5 of 22<"));
+    assert!(page.disabled("PREV"));
+    assert!(!page.disabled("NEXT"));
+
+    page.act(|t| t.list.go_to_page(5)).await;
+
+    assert!(page.html().contains(">21-22 of 22<"));
+    assert!(page.html().contains("employee22@example.com"));
+    assert!(!page.disabled("PREV"));
+    assert!(page.disabled("NEXT"));
+}
+
+#[tokio::test]
+async fn page_size_and_search_go_back_to_the_first_page() {
+    let mut page = Rendered::new(FakeBackend::with(22)).await;
+    page.act(|t| t.list.go_to_page(3)).await;
+
+    page.act(|t| t.list.set_page_size(10)).await;
+    assert!(page.html().contains(">1-10 of 22<"));
+
+    page.act(|t| t.list.go_to_page(2)).await;
+    page.search("last2").await;
+    assert!(page.html().contains(">1-4 of 4<"));
+    assert!(page.disabled("NEXT"));
+}
+
+#[tokio::test]
+async fn an_empty_result_says_so() {
+    let mut page = Rendered::new(FakeBackend::with(3)).await;
+
+    page.search("nobody").await;
+
+    assert!(page.html().contains("No employees found."));
+    assert!(page.disabled("PREV") && page.disabled("NEXT"));
+}
+
+#[tokio::test]
+async fn add_modal_opens_validates_and_closes() {
+    let mut page = Rendered::new(FakeBackend::with(3)).await;
+    assert!(!page.html().contains("Add New Employee"));
+
+    page.act(|t| t.open_add()).await;
+    assert!(page.html().contains("Add New Employee"));
+    assert!(page.disabled("Save Employee"));
+
+    page.fill_add_form("Ana", "Silva", "ana@example.com");
+    page.settle().await;
+    assert!(!page.disabled("Save Employee"));
+
+    page.act(|t| t.close_add()).await;
+    assert!(!page.html().contains("Add New Employee"));
+}
+
+#[tokio::test]
+async fn adding_reloads_the_list() {
+    let mut page = Rendered::new(FakeBackend::with(22)).await;
+    let loads = page.backend.list_calls.get();
+
+    page.act(|t| t.open_add()).await;
+    page.fill_add_form("Ana", "Silva", "ana@example.com");
+    page.act_async(|t| t.save_new()).await;
+
+    assert!(!page.html().contains("Add New Employee"));
+    assert!(page.html().contains(">1-5 of 23<"));
+    assert!(page.html().contains("Employee added"));
+    assert_eq!(page.backend.list_calls.get(), loads + 1);
+}
+
+#[tokio::test]
+async fn a_failed_save_keeps_the_modal_open() {
+    let mut page = Rendered::new(FakeBackend::with(3)).await;
+    page.backend.fail_writes.set(true);
+    let loads = page.backend.list_calls.get();
+
+    page.act(|t| t.open_add()).await;
+    page.fill_add_form("Ana", "Silva", "ana@example.com");
+    page.act_async(|t| t.save_new()).await;
+
+    assert!(page.html().contains("Add New Employee"));
+    assert!(page.html().contains("write failed"));
+    assert_eq!(page.backend.list_calls.get(), loads);
+}
+
+#[tokio::test]
+async fn messages_show_once_a_field_was_changed() {
+    let mut page = Rendered::new(FakeBackend::with(3)).await;
+    page.act(|t| t.open_add()).await;
+    assert!(!page.html().contains("Please enter a valid email address"));
+
+    page.fill_add_form("Ana", "Silva", "ana");
+    page.settle().await;
+    assert!(page.html().contains("Please enter a valid email address"));
+    assert!(!page.html().contains("First name is required"));
+}
+
+#[tokio::test]
+async fn a_taken_email_is_shown_on_the_email_field() {
+    let mut page = Rendered::new(FakeBackend::with(3)).await;
+
+    page.act(|t| t.open_add()).await;
+    page.fill_add_form("Ana", "Silva", "employee2@example.com");
+    page.act_async(|t| t.save_new()).await;
+
+    assert!(page.html().contains("An employee with this email already exists"));
+    assert!(page.html().contains("Add New Employee"));
+    assert_eq!(page.backend.employees.borrow().len(), 3);
+}
+
+#[tokio::test]
+async fn editing_reloads_the_list() {
+    let mut page = Rendered::new(FakeBackend::with(3)).await;
+    let employee = page.backend.employees.borrow()[0].clone();
+
+    page.act(|t| t.open_edit(&employee)).await;
+    assert!(page.html().contains("Edit Employee"));
+    assert!(!page.disabled("Update Employee"));
+
+    let form = page.table.edit_form;
+    page.dom.in_runtime(|| form.set("last_name", "Souza".to_string()));
+    page.act_async(|t| t.save_edit()).await;
+
+    assert!(!page.html().contains("Edit Employee"));
+    assert!(page.html().contains(">Souza<"));
+}
+
+#[tokio::test]
+async fn deleting_the_last_row_of_a_page_steps_back() {
+    let mut page = Rendered::new(FakeBackend::with(21)).await;
+    page.act(|t| t.list.go_to_page(5)).await;
+    assert!(page.html().contains(">21-21 of 21<"));
+
+    let last = page.backend.employees.borrow()[20].clone();
+    page.act(|t| t.ask_delete(&last)).await;
+    assert!(page.html().contains("Confirm deletion"));
+    page.act_async(|t| t.confirm_delete()).await;
+
+    assert!(!page.html().contains("Confirm deletion"));
+    assert!(page.html().contains(">16-20 of 20<"));
+    assert!(page.disabled("NEXT"));
+}
+
+#[tokio::test]
+async fn cancelling_a_delete_changes_nothing() {
+    let mut page = Rendered::new(FakeBackend::with(3)).await;
+    let loads = page.backend.list_calls.get();
+
+    let first = page.backend.employees.borrow()[0].clone();
+    page.act(|t| t.ask_delete(&first)).await;
+    page.act(|t| t.cancel_delete()).await;
+
+    assert!(!page.html().contains("Confirm deletion"));
+    assert_eq!(page.backend.employees.borrow().len(), 3);
+    assert_eq!(page.backend.list_calls.get(), loads);
+}
+
+#[tokio::test]
+async fn a_delete_can_be_undone_from_its_toast() {
+    let mut page = Rendered::new(FakeBackend::with(3)).await;
+    let before = page.backend.employees.borrow().clone();
+    let second = before[1].clone();
+
+    page.act(|t| t.ask_delete(&second)).await;
+    page.act_async(|t| t.confirm_delete()).await;
+    assert!(page.html().contains("Employee deleted"));
+    assert!(page.html().contains(">Undo</button>"));
+    assert!(!page.html().contains("employee2@example.com"));
+    assert_eq!(page.backend.employees.borrow().len(), 2);
+
+    let toasts = page.toasts;
+    let id = page.dom.in_runtime(|| toasts.items.peek()[0].id());
+    page.dom.in_scope(ScopeId::APP, || toasts.undo(id));
+    page.settle().await;
+
+    assert!(!page.html().contains("Employee deleted"));
+    assert!(page.html().contains("Employee restored"));
+    assert!(page.html().contains("employee2@example.com"));
+    // The same employee, not a copy: id and every field as before
+    assert_eq!(*page.backend.employees.borrow(), before);
+}
+
+#[tokio::test]
+async fn a_failed_delete_says_so() {
+    let mut page = Rendered::new(FakeBackend::with(3)).await;
+    page.backend.fail_writes.set(true);
+    let first = page.backend.employees.borrow()[0].clone();
+
+    page.act(|t| t.ask_delete(&first)).await;
+    page.act_async(|t| t.confirm_delete()).await;
+
+    assert!(!page.html().contains("Confirm deletion"));
+    assert!(page.html().contains("Could not delete the employee"));
+    assert!(!page.html().contains(">Undo</button>"));
+}
+
+#[tokio::test]
+async fn cards_show_the_first_batch_and_follow_search_and_writes() {
+    let mut page = Rendered::cards(FakeBackend::with(45)).await;
+    assert!(page.html().contains(">20 of 45<"));
+    assert!(page.html().contains(">Load more</button>"));
+
+    page.search("last4").await;
+    assert!(page.html().contains(">7 of 7<"));
+    assert!(!page.html().contains(">Load more</button>"));
+
+    page.search("").await;
+    page.act(|t| t.open_add()).await;
+    page.fill_add_form("Ana", "Silva", "ana@example.com");
+    page.act_async(|t| t.save_new()).await;
+    assert!(page.html().contains(">20 of 46<"));
+}
+
+#[tokio::test]
+async fn typing_a_search_loads_once_it_pauses() {
+    let mut page = Rendered::new(FakeBackend::with(22)).await;
+    let loads = page.backend.list_calls.get();
+
+    for term in ["l", "la", "las", "last2"] {
+        page.act(|t| t.list.set_search(term.to_string())).await;
+    }
+    assert_eq!(page.backend.list_calls.get(), loads);
+    assert!(page.html().contains(">1-5 of 22<"));
+
+    page.search("last2").await;
+    assert_eq!(page.backend.list_calls.get(), loads + 1);
+    assert!(page.html().contains(">1-4 of 4<"));
+}
+
+#[tokio::test]
+async fn cards_load_only_their_feed_in_the_table_order() {
+    let mut page = Rendered::cards(FakeBackend::with(45)).await;
+    assert_eq!(page.backend.list_calls.get(), 1);
+
+    page.act(|t| t.list.toggle_sort("id")).await;
+    page.act(|t| t.list.toggle_sort("id")).await;
+    let html = page.html();
+    let first = html.find("employee45@example.com").expect("newest shown");
+    assert!(html.find("employee26@example.com").is_some_and(|last| first < last));
+    assert!(!html.contains("employee25@example.com"));
+}
@@ -0,0 +1,155 @@
+use dioxus::prelude::*;
+
+use crate::components::cards::stat_card::StatCard;
+use crate::models::job::{JOB_DEAD, JOB_QUEUED, JOB_RUNNING, JOB_SUCCEEDED};
+
+fn status_badge(status: &str) -> &'static str {
+    match status {
+        JOB_SUCCEEDED => "bg-emerald-100 text-emerald-700",
+        JOB_RUNNING => "bg-blue-100 text-blue-700",
+        JOB_DEAD => "bg-red-100 text-red-700",
+        _ => "bg-slate-100 text-slate-600",
+    }
+}
+
+/// Queue overview with a status filter; dead jobs can be retried or discarded.
+#[component]
+pub fn JobTable() -> Element {
+    let mut status_filter = use_signal(String::new);
+    let mut action_error = use_signal(|| None::<String>);
+
+    let mut stats_resource = use_resource(crate::server::jobs::job_stats);
+    let mut jobs_resource = use_resource(move || async move {
+        let status = status_filter();
+        crate::server::jobs::list_jobs(if status.is_empty() { None } else { Some(status) }).await
+    });
+
+    let stats = stats_resource.read().as_ref().and_then(|r| r.as_ref().ok()).cloned();
+    let count = move |f: fn(&crate::models::job::JobStats) -> i64| stats.as_ref().map(f);
+
+    let mut refresh = move || {
+        stats_resource.restart();
+        jobs_resource.restart();
+    };
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full",
+            // STATS
+            div { class: "grid grid-cols-2 md:grid-cols-4 gap-4",
+                StatCard { title: "Queued", value: count(|s| s.queued) }
+                StatCard { title: "Running", value: count(|s| s.running) }
+                StatCard { title: "Succeeded", value: count(|s| s.succeeded), accent: "text-emerald-600" }
+                StatCard { title: "Dead", value: count(|s| s.dead), accent: "text-red-500" }
+            }
+
+            // HEADER
+            div { class: "flex justify-between items-center px-2",
+                select {
+                    class: "px-3 py-2 border border-blue-300 rounded-lg focus:ring-2 focus:ring-blue-400",
+                    value: "{status_filter}",
+                    onchange: move |e| status_filter.set(e.value()),
+                    option { value: "", "All statuses" }
+                    for status in [JOB_QUEUED, JOB_RUNNING, JOB_SUCCEEDED, JOB_DEAD] {
+                        option { value: status, "{status}" }
+                    }
+                }
+                button {
+                    class: "px-4 py-2 rounded-lg border border-slate-300 text-slate-600 hover:bg-slate-100 transition",
+                    onclick: move |_| refresh(),
+                    "Refresh"
+                }
+            }
+
+            if let Some(err) = action_error() {
+                div { class: "px-4 py-2 rounded bg-red-50 text-red-600 text-sm", "{err}" }
+            }
+
+            // TABLE
+            div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                table { class: "min-w-full divide-y divide-slate-200",
+                    thead { class: "bg-blue-50",
+                        tr {
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "ID" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Kind" }
+                            th { class: "px-4 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Status" }
+                            th { class: "px-4 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Attempts" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Run at" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Last error" }
+                            th { class: "px-4 py-3 text-right text-xs font-bold text-blue-700 uppercase", "Actions" }
+                        }
+                    }
+                    tbody { class: "divide-y divide-slate-200 bg-white",
+                        match &*jobs_resource.read() {
+                            Some(Ok(jobs)) if jobs.is_empty() => rsx! {
+                                tr {
+                                    td { colspan: "7", class: "px-6 py-8 text-center text-slate-500", "No jobs." }
+                                }
+                            },
+                            Some(Ok(jobs)) => rsx! {
+                                for job in jobs.iter() {
+                                    tr { class: "hover:bg-blue-50 transition-colors", key: "{job.id}",
+                                        td { class: "px-4 py-2 text-sm font-mono", "#{job.id}" }
+                                        td { class: "px-4 py-2 text-xs font-mono", title: "{job.payload}", "{job.kind}" }
+                                        td { class: "px-4 py-2 text-sm text-center",
+                                            span { class: format!("px-2 py-0.5 rounded text-xs font-semibold {}", status_badge(&job.status)), "{job.status}" }
+                                        }
+                                        td { class: "px-4 py-2 text-sm text-center", "{job.attempts} / {job.max_attempts}" }
+                                        td { class: "px-4 py-2 text-sm whitespace-nowrap", {job.run_at.format("%Y-%m-%d %H:%M:%S").to_string()} }
+                                        td { class: "px-4 py-2 text-xs text-red-600 max-w-xs truncate",
+                                            title: job.last_error.clone().unwrap_or_default(),
+                                            {job.last_error.clone().unwrap_or_default()}
+                                        }
+                                        td { class: "px-4 py-2 text-sm text-right",
+                                            if job.status == JOB_DEAD {
+                                                div { class: "flex justify-end gap-3",
+                                                    button {
+                                                        class: "text-xs text-blue-600 hover:underline",
+                                                        onclick: {
+                                                            let id = job.id;
+                                                            move |_| async move {
+                                                                match crate::server::jobs::retry_job(id).await {
+                                                                    Ok(_) => { action_error.set(None); refresh(); }
+                                                                    Err(e) => action_error.set(Some(e.to_string())),
+                                                                }
+                                                            }
+                                                        },
+                                                        "Retry"
+                                                    }
+                                                    button {
+                                                        class: "text-xs text-red-600 hover:underline",
+                                                        onclick: {
+                                                            let id = job.id;
+                                                            move |_| async move {
+                                                                match crate::server::jobs::delete_job(id).await {
+                                                                    Ok(_) => { action_error.set(None); refresh(); }
+                                                                    Err(e) => action_error.set(Some(e.to_string())),
+                                                                }
+                                                            }
+                                                        },
+                                                        "Delete"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Err(e)) => rsx! {
+                                tr {
+                                    td { colspan: "7", class: "px-6 py-8 text-center text-red-600", "Could not load jobs: {e}" }
+                                }
+                            },
+                            None => rsx! {
+                                tr {
+                                    td { colspan: "7", class: "px-6 py-4",
+                                        div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use dioxus::prelude::*;
+
+use crate::models::ldap::{LdapChangeKind, LdapSyncPreview};
+
+fn kind_badge(kind: LdapChangeKind) -> &'static str {
+    match kind {
+        LdapChangeKind::Create => "bg-emerald-100 text-emerald-700",
+        LdapChangeKind::Update => "bg-blue-100 text-blue-700",
+        LdapChangeKind::Missing => "bg-red-100 text-red-700",
+        LdapChangeKind::Returned => "bg-amber-100 text-amber-700",
+    }
+}
+
+/// Reads the directory on demand, shows the diff and queues the sync for it.
+#[component]
+pub fn LdapSyncPreviewTable(on_applied: EventHandler<()>) -> Element {
+    let mut preview = use_signal(|| None::<LdapSyncPreview>);
+    let mut pending = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut notice = use_signal(|| None::<String>);
+
+    let load_preview = move |_| async move {
+        pending.set(true);
+        error.set(None);
+        notice.set(None);
+        match crate::server::ldap::preview_ldap_sync().await {
+            Ok(p) => preview.set(Some(p)),
+            Err(ServerFnError::ServerError { message, .. }) => error.set(Some(message)),
+            Err(e) => error.set(Some(e.to_string())),
+        }
+        pending.set(false);
+    };
+
+    let apply = move |_| async move {
+        let Some(fingerprint) = preview().map(|p| p.fingerprint) else { return };
+        pending.set(true);
+        match crate::server::ldap::apply_ldap_sync(fingerprint).await {
+            Ok(job_id) => {
+                preview.set(None);
+                notice.set(Some(format!("Sync queued as job #{}", job_id)));
+                on_applied.call(());
+            }
+            Err(e) => error.set(Some(e.to_string())),
+        }
+        pending.set(false);
+    };
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full",
+            // HEADER
+            div { class: "flex justify-between items-center px-2",
+                h3 { class: "text-lg font-semibold text-slate-700", "Preview" }
+                div { class: "flex gap-3",
+                    button {
+                        class: "px-4 py-2 rounded-lg border border-slate-300 text-slate-600 hover:bg-slate-100 transition disabled:opacity-50",
+                        disabled: pending(),
+                        onclick: load_preview,
+                        if pending() { "Reading directory..." } else { "Preview sync" }
+                    }
+                    if let Some(diff) = preview() {
+                        button {
+                            class: "px-4 py-2 rounded-lg bg-blue-600 text-white hover:bg-blue-700 transition disabled:opacity-50",
+                            disabled: pending() || diff.changes.is_empty(),
+                            onclick: apply,
+                            {format!("Apply {} changes", diff.changes.len())}
+                        }
+                    }
+                }
+            }
+
+            if let Some(err) = error() {
+                div { class: "px-4 py-2 rounded bg-red-50 text-red-600 text-sm", "{err}" }
+            }
+            if let Some(message) = notice() {
+                div { class: "px-4 py-2 rounded bg-emerald-50 text-emerald-700 text-sm", "{message}" }
+            }
+
+            if let Some(diff) = preview() {
+                p { class: "text-sm text-slate-500 px-2",
+                    {format!("{} employees are up to date, {} directory entries were skipped.", diff.unchanged, diff.skipped.len())}
+                }
+
+                // TABLE
+                div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                    table { class: "min-w-full divide-y divide-slate-200",
+                        thead { class: "bg-blue-50",
+                            tr {
+                                th { class: "px-4 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Change" }
+                                th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Email" }
+                                th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Name" }
+                                th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Directory entry" }
+                            }
+                        }
+                        tbody { class: "divide-y divide-slate-200 bg-white",
+                            if diff.changes.is_empty() {
+                                tr {
+                                    td { colspan: "4", class: "px-6 py-8 text-center text-slate-500", "Nothing to change." }
+                                }
+                            }
+                            for change in diff.changes.iter() {
+                                tr { class: "hover:bg-blue-50 transition-colors", key: "{change.email}",
+                                    td { class: "px-4 py-2 text-sm text-center",
+                                        span { class: format!("px-2 py-0.5 rounded text-xs font-semibold {}", kind_badge(change.kind)), {change.kind.as_str()} }
+                                    }
+                                    td { class: "px-4 py-2 text-sm", "{change.email}" }
+                                    td { class: "px-4 py-2 text-sm",
+                                        match (&change.old_first_name, &change.old_last_name) {
+                                            (Some(old_first), Some(old_last)) if *old_first != change.first_name || *old_last != change.last_name => rsx! {
+                                                span { class: "text-slate-400 line-through mr-2", "{old_first} {old_last}" }
+                                                span { "{change.first_name} {change.last_name}" }
+                                            },
+                                            _ => rsx! { "{change.first_name} {change.last_name}" },
+                                        }
+                                    }
+                                    td { class: "px-4 py-2 text-xs font-mono text-slate-500 max-w-xs truncate",
+                                        title: change.dn.clone().unwrap_or_default(),
+                                        {change.dn.clone().unwrap_or_default()}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !diff.skipped.is_empty() {
+                    details { class: "px-2 text-sm text-slate-600",
+                        summary { class: "cursor-pointer", {format!("Skipped entries ({})", diff.skipped.len())} }
+                        ul { class: "mt-2 flex flex-col gap-1",
+                            for entry in diff.skipped.iter() {
+                                li { key: "{entry.dn}",
+                                    span { class: "font-mono text-xs", "{entry.dn}" }
+                                    span { class: "text-red-600", " {entry.reason}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use dioxus::prelude::*;
+
+fn status_badge(status: &str) -> &'static str {
+    match status {
+        "succeeded" => "bg-emerald-100 text-emerald-700",
+        "failed" => "bg-red-100 text-red-700",
+        _ => "bg-blue-100 text-blue-700",
+    }
+}
+
+/// Recent LDAP syncs with their counts; `refresh` reloads after a new one was queued.
+#[component]
+pub fn LdapSyncRunTable(refresh: ReadSignal<u32>) -> Element {
+    let runs_resource = use_resource(move || async move {
+        refresh();
+        crate::server::ldap::list_ldap_sync_runs().await
+    });
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full",
+            h3 { class: "text-lg font-semibold text-slate-700 px-2", "History" }
+
+            div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                table { class: "min-w-full divide-y divide-slate-200",
+                    thead { class: "bg-blue-50",
+                        tr {
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Started" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "By" }
+                            th { class: "px-4 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Status" }
+                            th { class: "px-4 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Created" }
+                            th { class: "px-4 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Updated" }
+                            th { class: "px-4 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Missing" }
+                            th { class: "px-4 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Returned" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Error" }
+                        }
+                    }
+                    tbody { class: "divide-y divide-slate-200 bg-white",
+                        match &*runs_resource.read() {
+                            Some(Ok(runs)) if runs.is_empty() => rsx! {
+                                tr {
+                                    td { colspan: "8", class: "px-6 py-8 text-center text-slate-500", "No syncs yet." }
+                                }
+                            },
+                            Some(Ok(runs)) => rsx! {
+                                for run in runs.iter() {
+                                    tr { class: "hover:bg-blue-50 transition-colors", key: "{run.id}",
+                                        td { class: "px-4 py-2 text-sm whitespace-nowrap", {run.started_at.format("%Y-%m-%d %H:%M:%S").to_string()} }
+                                        td { class: "px-4 py-2 text-sm", "{run.triggered_by}" }
+                                        td { class: "px-4 py-2 text-sm text-center",
+                                            span { class: format!("px-2 py-0.5 rounded text-xs font-semibold {}", status_badge(&run.status)), "{run.status}" }
+                                        }
+                                        td { class: "px-4 py-2 text-sm text-center", "{run.created}" }
+                                        td { class: "px-4 py-2 text-sm text-center", "{run.updated}" }
+                                        td { class: "px-4 py-2 text-sm text-center", "{run.missing}" }
+                                        td { class: "px-4 py-2 text-sm text-center", "{run.returned}" }
+                                        td { class: "px-4 py-2 text-xs text-red-600 max-w-xs truncate",
+                                            title: run.error.clone().unwrap_or_default(),
+                                            {run.error.clone().unwrap_or_default()}
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Err(e)) => rsx! {
+                                tr {
+                                    td { colspan: "8", class: "px-6 py-8 text-center text-red-600", "Could not load syncs: {e}" }
+                                }
+                            },
+                            None => rsx! {
+                                tr {
+                                    td { colspan: "8", class: "px-6 py-4",
+                                        div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use dioxus::prelude::*;
+
+/// Employees the last syncs no longer found in the directory. They stay in
+/// `employee` until someone decides what to do with them.
+#[component]
+pub fn MissingEmployeeTable(refresh: ReadSignal<u32>) -> Element {
+    let missing_resource = use_resource(move || async move {
+        refresh();
+        crate::server::ldap::list_missing_employees().await
+    });
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full",
+            h3 { class: "text-lg font-semibold text-slate-700 px-2", "Missing from the directory" }
+
+            div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                table { class: "min-w-full divide-y divide-slate-200",
+                    thead { class: "bg-blue-50",
+                        tr {
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Name" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Email" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Last directory entry" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Missing since" }
+                        }
+                    }
+                    tbody { class: "divide-y divide-slate-200 bg-white",
+                        match &*missing_resource.read() {
+                            Some(Ok(employees)) if employees.is_empty() => rsx! {
+                                tr {
+                                    td { colspan: "4", class: "px-6 py-8 text-center text-slate-500", "Everyone synced is still in the directory." }
+                                }
+                            },
+                            Some(Ok(employees)) => rsx! {
+                                for employee in employees.iter() {
+                                    tr { class: "hover:bg-blue-50 transition-colors", key: "{employee.id}",
+                                        td { class: "px-4 py-2 text-sm", "{employee.first_name} {employee.last_name}" }
+                                        td { class: "px-4 py-2 text-sm", "{employee.email}" }
+                                        td { class: "px-4 py-2 text-xs font-mono text-slate-500", {employee.ldap_dn.clone().unwrap_or_default()} }
+                                        td { class: "px-4 py-2 text-sm whitespace-nowrap", {employee.missing_since.format("%Y-%m-%d %H:%M").to_string()} }
+                                    }
+                                }
+                            },
+                            Some(Err(e)) => rsx! {
+                                tr {
+                                    td { colspan: "4", class: "px-6 py-8 text-center text-red-600", "Could not load employees: {e}" }
+                                }
+                            },
+                            None => rsx! {
+                                tr {
+                                    td { colspan: "4", class: "px-6 py-4",
+                                        div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1 +1,16 @@
-pub mod employee_table;
\ No newline at end of file
+pub mod data_table;
+pub mod employee_cards;
+pub mod employee_data;
+pub mod employee_table;
+#[cfg(test)]
+mod employee_table_tests;
+pub mod api_token_table;
+pub mod webhook_table;
+pub mod webhook_delivery_table;
+pub mod job_table;
+pub mod email_template_table;
+pub mod user_table;
+pub mod role_policy_table;
+pub mod ldap_sync_preview;
+pub mod ldap_sync_run_table;
+pub mod missing_employee_table;
@@ -0,0 +1,74 @@
+use dioxus::prelude::*;
+
+/// Per-role switch that makes two-factor authentication mandatory.
+#[component]
+pub fn RolePolicyTable() -> Element {
+    let mut action_error = use_signal(|| None::<String>);
+    let mut policies_resource = use_resource(crate::server::two_factor::list_role_policies);
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full",
+            h3 { class: "text-lg font-semibold text-slate-700 px-2", "Two-factor policy" }
+
+            if let Some(err) = action_error() {
+                div { class: "px-4 py-2 rounded bg-red-50 text-red-600 text-sm", "{err}" }
+            }
+
+            div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                table { class: "min-w-full divide-y divide-slate-200",
+                    thead { class: "bg-blue-50",
+                        tr {
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Role" }
+                            th { class: "px-6 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Require 2FA" }
+                        }
+                    }
+                    tbody { class: "divide-y divide-slate-200 bg-white",
+                        match &*policies_resource.read() {
+                            Some(Ok(policies)) => rsx! {
+                                for policy in policies.iter() {
+                                    tr { class: "h-12 hover:bg-blue-50 transition-colors", key: "{policy.name}",
+                                        td { class: "px-6 py-2 text-sm font-mono", "{policy.name}" }
+                                        td { class: "px-6 py-2 text-center",
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: policy.require_two_factor,
+                                                onchange: {
+                                                    let role = policy.name.clone();
+                                                    move |e: FormEvent| {
+                                                        let role = role.clone();
+                                                        async move {
+                                                            match crate::server::two_factor::set_role_two_factor(role, e.checked()).await {
+                                                                Ok(_) => action_error.set(None),
+                                                                Err(e) => action_error.set(Some(e.to_string())),
+                                                            }
+                                                            policies_resource.restart();
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Err(e)) => rsx! {
+                                tr {
+                                    td { colspan: "2", class: "px-6 py-8 text-center text-red-600", "Could not load roles: {e}" }
+                                }
+                            },
+                            None => rsx! {
+                                tr {
+                                    td { colspan: "2", class: "px-6 py-4",
+                                        div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+            p { class: "text-sm text-slate-500 px-2",
+                "Users with a required role have to set up an authenticator at their next login."
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use dioxus::prelude::*;
+
+/// Login accounts with their lock state. Admins can lift temporary locks and
+/// enable or disable accounts; the two are independent.
+#[component]
+pub fn UserTable() -> Element {
+    let mut locked_only = use_signal(|| false);
+    let mut action_error = use_signal(|| None::<String>);
+
+    let mut users_resource = use_resource(crate::server::users::list_users);
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full",
+
+            // HEADER
+            div { class: "flex justify-between items-center px-2",
+                label { class: "flex items-center gap-2 text-sm text-slate-600",
+                    input {
+                        r#type: "checkbox",
+                        checked: locked_only(),
+                        onchange: move |e| locked_only.set(e.checked())
+                    }
+                    "Only locked accounts"
+                }
+                button {
+                    class: "px-4 py-2 rounded-lg border border-slate-300 text-slate-600 hover:bg-slate-100 transition",
+                    onclick: move |_| users_resource.restart(),
+                    "Refresh"
+                }
+            }
+
+            if let Some(err) = action_error() {
+                div { class: "px-4 py-2 rounded bg-red-50 text-red-600 text-sm", "{err}" }
+            }
+
+            // TABLE
+            div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                table { class: "min-w-full divide-y divide-slate-200",
+                    thead { class: "bg-blue-50",
+                        tr {
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Username" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Email" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Roles" }
+                            th { class: "px-6 py-3 text-center text-xs font-bold text-blue-700 uppercase", "2FA" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Last login" }
+                            th { class: "px-6 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Failed logins" }
+                            th { class: "px-6 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Status" }
+                            th { class: "w-64 px-6 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Actions" }
+                        }
+                    }
+                    tbody { class: "divide-y divide-slate-200 bg-white",
+                        match &*users_resource.read() {
+                            Some(Ok(users)) => {
+                                let users: Vec<_> = users.iter().filter(|u| !locked_only() || u.is_locked()).cloned().collect();
+                                rsx! {
+                                    if users.is_empty() {
+                                        tr {
+                                            td { colspan: "8", class: "px-6 py-8 text-center text-slate-500", "No matching accounts." }
+                                        }
+                                    }
+                                    for user in users {
+                                        tr { class: "h-14 hover:bg-blue-50 transition-colors", key: "{user.id}",
+                                            td { class: "px-6 py-3 text-sm font-medium", "{user.username}" }
+                                            td { class: "px-6 py-3 text-sm", {user.email.clone().unwrap_or_default()} }
+                                            td { class: "px-6 py-3 text-xs", {user.roles.join(", ")} }
+                                            td { class: "px-6 py-3 text-sm text-center",
+                                                if user.two_factor_enabled { "✔" } else { span { class: "text-slate-400", "—" } }
+                                            }
+                                            td { class: "px-6 py-3 text-sm whitespace-nowrap",
+                                                match user.last_login_at {
+                                                    Some(at) => rsx! { {at.format("%Y-%m-%d %H:%M").to_string()} },
+                                                    None => rsx! { span { class: "text-slate-400", "never" } },
+                                                }
+                                            }
+                                            td { class: "px-6 py-3 text-sm text-center", "{user.failed_logins}" }
+                                            td { class: "px-6 py-3 text-center",
+                                                div { class: "flex flex-col items-center gap-1",
+                                                    if user.enabled {
+                                                        span { class: "px-2 py-0.5 rounded bg-emerald-100 text-emerald-700 text-xs font-semibold", "enabled" }
+                                                    } else {
+                                                        span { class: "px-2 py-0.5 rounded bg-slate-200 text-slate-600 text-xs font-semibold", "disabled" }
+                                                    }
+                                                    if let Some(until) = user.locked_until.filter(|_| user.is_locked()) {
+                                                        span { class: "px-2 py-0.5 rounded bg-red-100 text-red-700 text-xs font-semibold",
+                                                            {format!("locked until {}", until.format("%H:%M"))}
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            td { class: "px-6 py-3 text-center",
+                                                div { class: "flex items-center justify-center gap-2",
+                                                    if user.is_locked() || user.failed_logins > 0 {
+                                                        button {
+                                                            class: "px-3 py-1 text-sm rounded-md text-blue-600 hover:bg-blue-50 transition",
+                                                            onclick: {
+                                                                let id = user.id;
+                                                                move |_| async move {
+                                                                    match crate::server::users::unlock_user(id).await {
+                                                                        Ok(_) => { action_error.set(None); users_resource.restart(); }
+                                                                        Err(e) => action_error.set(Some(e.to_string())),
+                                                                    }
+                                                                }
+                                                            },
+                                                            "Unlock"
+                                                        }
+                                                    }
+                                                    if user.two_factor_enabled {
+                                                        button {
+                                                            class: "px-3 py-1 text-sm rounded-md text-amber-700 hover:bg-amber-50 transition",
+                                                            title: "For users who lost their authenticator and recovery codes",
+                                                            onclick: {
+                                                                let id = user.id;
+                                                                move |_| async move {
+                                                                    match crate::server::two_factor::reset_user_two_factor(id).await {
+                                                                        Ok(_) => { action_error.set(None); users_resource.restart(); }
+                                                                        Err(e) => action_error.set(Some(e.to_string())),
+                                                                    }
+                                                                }
+                                                            },
+                                                            "Reset 2FA"
+                                                        }
+                                                    }
+                                                    button {
+                                                        class: if user.enabled {
+                                                            "px-3 py-1 text-sm rounded-md text-red-600 hover:bg-red-50 transition"
+                                                        } else {
+                                                            "px-3 py-1 text-sm rounded-md text-emerald-700 hover:bg-emerald-50 transition"
+                                                        },
+                                                        onclick: {
+                                                            let id = user.id;
+                                                            let enable = !user.enabled;
+                                                            move |_| async move {
+                                                                match crate::server::users::set_user_enabled(id, enable).await {
+                                                                    Ok(_) => { action_error.set(None); users_resource.restart(); }
+                                                                    Err(e) => action_error.set(Some(e.to_string())),
+                                                                }
+                                                            }
+                                                        },
+                                                        if user.enabled { "Disable" } else { "Enable" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            Some(Err(e)) => rsx! {
+                                tr {
+                                    td { colspan: "8", class: "px-6 py-8 text-center text-red-600", "Could not load users: {e}" }
+                                }
+                            },
+                            None => rsx! {
+                                tr {
+                                    td { colspan: "8", class: "px-6 py-4",
+                                        div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
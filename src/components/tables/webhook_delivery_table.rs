@@ -0,0 +1,107 @@
+use dioxus::prelude::*;
+
+const ATTEMPT_LIMIT: i64 = 100;
+
+/// Delivery log: one row per HTTP attempt, newest first.
+#[component]
+pub fn WebhookDeliveryTable() -> Element {
+    let mut attempts_resource = use_resource(|| crate::server::webhooks::list_webhook_attempts(ATTEMPT_LIMIT));
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full",
+            div { class: "flex justify-between items-center px-2",
+                h3 { class: "text-lg font-semibold text-slate-700", "Delivery log" }
+                button {
+                    class: "px-4 py-2 rounded-lg border border-slate-300 text-slate-600 hover:bg-slate-100 transition",
+                    onclick: move |_| attempts_resource.restart(),
+                    "Refresh"
+                }
+            }
+
+            div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                table { class: "min-w-full divide-y divide-slate-200",
+                    thead { class: "bg-blue-50",
+                        tr {
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Time" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Webhook" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Event" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Delivery" }
+                            th { class: "px-4 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Attempt" }
+                            th { class: "px-4 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Result" }
+                            th { class: "px-4 py-3 text-right text-xs font-bold text-blue-700 uppercase", "Duration" }
+                            th { class: "px-4 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Delivery status" }
+                        }
+                    }
+                    tbody { class: "divide-y divide-slate-200 bg-white",
+                        match &*attempts_resource.read() {
+                            Some(Ok(attempts)) if attempts.is_empty() => rsx! {
+                                tr {
+                                    td { colspan: "8", class: "px-6 py-8 text-center text-slate-500", "No deliveries yet." }
+                                }
+                            },
+                            Some(Ok(attempts)) => rsx! {
+                                for attempt in attempts.iter() {
+                                    tr { class: "hover:bg-blue-50 transition-colors", key: "{attempt.id}",
+                                        td { class: "px-4 py-2 text-sm whitespace-nowrap", {attempt.attempted_at.format("%Y-%m-%d %H:%M:%S").to_string()} }
+                                        td { class: "px-4 py-2 text-sm", "{attempt.subscription_name}" }
+                                        td { class: "px-4 py-2 text-xs font-mono", "{attempt.event}" }
+                                        td { class: "px-4 py-2 text-sm font-mono", "#{attempt.delivery_id}" }
+                                        td { class: "px-4 py-2 text-sm text-center", "{attempt.attempt}" }
+                                        td { class: "px-4 py-2 text-sm text-center",
+                                            if attempt.succeeded() {
+                                                span { class: "px-2 py-0.5 rounded bg-emerald-100 text-emerald-700 text-xs font-semibold",
+                                                    {attempt.response_status.unwrap_or_default().to_string()}
+                                                }
+                                            } else {
+                                                span {
+                                                    class: "px-2 py-0.5 rounded bg-red-100 text-red-700 text-xs font-semibold",
+                                                    title: attempt.error.clone().unwrap_or_default(),
+                                                    match attempt.response_status {
+                                                        Some(status) => rsx! { "{status}" },
+                                                        None => rsx! { "error" },
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        td { class: "px-4 py-2 text-sm text-right", "{attempt.duration_ms} ms" }
+                                        td { class: "px-4 py-2 text-sm",
+                                            div { class: "flex items-center gap-2",
+                                                span { "{attempt.delivery_status}" }
+                                                if attempt.delivery_status == "failed" {
+                                                    button {
+                                                        class: "text-xs text-blue-600 hover:underline",
+                                                        onclick: {
+                                                            let delivery_id = attempt.delivery_id;
+                                                            move |_| async move {
+                                                                if crate::server::webhooks::retry_webhook_delivery(delivery_id).await.is_ok() {
+                                                                    attempts_resource.restart();
+                                                                }
+                                                            }
+                                                        },
+                                                        "Retry"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Err(e)) => rsx! {
+                                tr {
+                                    td { colspan: "8", class: "px-6 py-8 text-center text-red-600", "Could not load delivery log: {e}" }
+                                }
+                            },
+                            None => rsx! {
+                                tr {
+                                    td { colspan: "8", class: "px-6 py-4",
+                                        div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
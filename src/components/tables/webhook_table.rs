@@ -0,0 +1,247 @@
+use dioxus::prelude::*;
+use crate::models::webhook::{SaveWebhookRequest, ALL_EVENTS};
+
+#[component]
+pub fn WebhookTable() -> Element {
+    // CREATE / EDIT MODAL STATE (edit_id is None while creating)
+    let mut show_modal = use_signal(|| false);
+    let mut edit_id = use_signal(|| None::<i64>);
+    let mut name = use_signal(String::new);
+    let mut url = use_signal(String::new);
+    let mut events = use_signal(|| ALL_EVENTS.map(String::from).to_vec());
+    let mut enabled = use_signal(|| true);
+    let mut save_error = use_signal(|| None::<String>);
+
+    // The signing secret is shown exactly once after creating
+    let mut created_secret = use_signal(|| None::<String>);
+
+    let mut webhooks_resource = use_resource(crate::server::webhooks::list_webhooks);
+
+    rsx! {
+        div { class: "flex flex-col gap-4 w-full relative",
+
+            // HEADER
+            div { class: "flex justify-between items-center px-2",
+                p { class: "text-sm text-slate-500",
+                    "Payloads are signed with HMAC-SHA256 in the "
+                    code { class: "bg-slate-100 px-1 rounded", "X-EMS-Signature" }
+                    " header."
+                }
+                button {
+                    class: "bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-6 rounded-lg shadow-md transition-all",
+                    onclick: move |_| {
+                        edit_id.set(None);
+                        name.set(String::new());
+                        url.set(String::new());
+                        events.set(ALL_EVENTS.map(String::from).to_vec());
+                        enabled.set(true);
+                        save_error.set(None);
+                        show_modal.set(true);
+                    },
+                    "Add Webhook"
+                }
+            }
+
+            if let Some(secret) = created_secret() {
+                div { class: "bg-emerald-50 border border-emerald-200 rounded-lg px-4 py-3 flex flex-col gap-2",
+                    span { class: "text-sm font-semibold text-emerald-800",
+                        "Signing secret for the new webhook, it will not be shown again:"
+                    }
+                    code { class: "font-mono text-sm break-all bg-white border rounded px-2 py-1", "{secret}" }
+                    button {
+                        class: "self-end text-sm text-emerald-700 hover:underline",
+                        onclick: move |_| created_secret.set(None),
+                        "Dismiss"
+                    }
+                }
+            }
+
+            // TABLE
+            div { class: "overflow-x-auto bg-white rounded-lg shadow",
+                table { class: "min-w-full divide-y divide-slate-200",
+                    thead { class: "bg-blue-50",
+                        tr {
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Name" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "URL" }
+                            th { class: "px-6 py-3 text-left text-xs font-bold text-blue-700 uppercase", "Events" }
+                            th { class: "px-6 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Enabled" }
+                            th { class: "w-40 px-6 py-3 text-center text-xs font-bold text-blue-700 uppercase", "Actions" }
+                        }
+                    }
+                    tbody { class: "divide-y divide-slate-200 bg-white",
+                        match &*webhooks_resource.read() {
+                            Some(Ok(hooks)) if hooks.is_empty() => rsx! {
+                                tr {
+                                    td { colspan: "5", class: "px-6 py-8 text-center text-slate-500", "No webhooks configured." }
+                                }
+                            },
+                            Some(Ok(hooks)) => rsx! {
+                                for hook in hooks.iter() {
+                                    tr { class: "h-14 hover:bg-blue-50 transition-colors", key: "{hook.id}",
+                                        td { class: "px-6 py-3 text-sm font-medium", "{hook.name}" }
+                                        td { class: "px-6 py-3 text-sm font-mono break-all", "{hook.url}" }
+                                        td { class: "px-6 py-3 text-xs", {hook.events.join(", ")} }
+                                        td { class: "px-6 py-3 text-center text-sm",
+                                            if hook.enabled { "✔" } else { span { class: "text-slate-400", "—" } }
+                                        }
+                                        td { class: "px-6 py-3 text-center",
+                                            div { class: "flex items-center justify-center gap-2",
+                                                button {
+                                                    class: "px-3 py-1 text-sm rounded-md text-blue-600 hover:bg-blue-50 transition",
+                                                    onclick: {
+                                                        let hook = hook.clone();
+                                                        move |_| {
+                                                            edit_id.set(Some(hook.id));
+                                                            name.set(hook.name.clone());
+                                                            url.set(hook.url.clone());
+                                                            events.set(hook.events.clone());
+                                                            enabled.set(hook.enabled);
+                                                            save_error.set(None);
+                                                            show_modal.set(true);
+                                                        }
+                                                    },
+                                                    "Edit"
+                                                }
+                                                button {
+                                                    class: "px-3 py-1 text-sm rounded-md text-red-600 hover:bg-red-50 transition",
+                                                    onclick: {
+                                                        let id = hook.id;
+                                                        move |_| async move {
+                                                            if crate::server::webhooks::delete_webhook(id).await.is_ok() {
+                                                                webhooks_resource.restart();
+                                                            }
+                                                        }
+                                                    },
+                                                    "Delete"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Err(e)) => rsx! {
+                                tr {
+                                    td { colspan: "5", class: "px-6 py-8 text-center text-red-600", "Could not load webhooks: {e}" }
+                                }
+                            },
+                            None => rsx! {
+                                tr {
+                                    td { colspan: "5", class: "px-6 py-4",
+                                        div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+
+            // CREATE / EDIT MODAL
+            if show_modal() {
+                div {
+                    class: "fixed inset-0 z-50 flex items-center justify-center bg-black/40 backdrop-blur-sm",
+                    onclick: move |_| show_modal.set(false),
+
+                    div {
+                        class: "bg-white rounded-2xl shadow-2xl w-[520px] overflow-hidden",
+                        onclick: move |e| e.stop_propagation(),
+
+                        // HEADER
+                        div { class: "px-8 py-5 border-b",
+                            h2 { class: "text-lg font-semibold text-slate-800",
+                                if edit_id().is_some() { "Edit Webhook" } else { "Add Webhook" }
+                            }
+                        }
+
+                        // BODY
+                        div { class: "px-8 py-6 flex flex-col gap-5",
+                            div { class: "flex flex-col gap-1",
+                                label { class: "text-sm font-medium text-slate-700", "Name" }
+                                input {
+                                    class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                    placeholder: "Payroll",
+                                    value: "{name}",
+                                    oninput: move |e| name.set(e.value())
+                                }
+                            }
+                            div { class: "flex flex-col gap-1",
+                                label { class: "text-sm font-medium text-slate-700", "Target URL" }
+                                input {
+                                    class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                    placeholder: "https://payroll.example.com/hooks/ems",
+                                    value: "{url}",
+                                    oninput: move |e| url.set(e.value())
+                                }
+                            }
+                            div { class: "flex flex-col gap-1",
+                                label { class: "text-sm font-medium text-slate-700", "Events" }
+                                for event in ALL_EVENTS {
+                                    label { class: "flex items-center gap-2 text-sm",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: events.read().iter().any(|e| e == event),
+                                            onchange: move |e| {
+                                                if e.checked() {
+                                                    events.write().push(event.to_string());
+                                                } else {
+                                                    events.write().retain(|x| x != event);
+                                                }
+                                            }
+                                        }
+                                        code { "{event}" }
+                                    }
+                                }
+                            }
+                            label { class: "flex items-center gap-2 text-sm font-medium text-slate-700",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: enabled(),
+                                    onchange: move |e| enabled.set(e.checked())
+                                }
+                                "Enabled"
+                            }
+                            if let Some(message) = save_error() {
+                                span { class: "text-sm text-red-600", "{message}" }
+                            }
+                        }
+
+                        // FOOTER
+                        div { class: "px-8 py-5 bg-slate-50 flex justify-end gap-3",
+                            button {
+                                class: "px-4 py-2 rounded-lg border border-slate-300 text-slate-600 hover:bg-slate-100 transition",
+                                onclick: move |_| show_modal.set(false),
+                                "Cancel"
+                            }
+                            button {
+                                class: "px-5 py-2 rounded-lg bg-blue-600 text-white font-semibold hover:bg-blue-700 transition shadow-md disabled:opacity-50 disabled:cursor-not-allowed",
+                                disabled: name().trim().is_empty() || url().trim().is_empty() || events.read().is_empty(),
+                                onclick: move |_| async move {
+                                    let req = SaveWebhookRequest {
+                                        name: name(),
+                                        url: url(),
+                                        events: events(),
+                                        enabled: enabled(),
+                                    };
+                                    let result = match edit_id() {
+                                        Some(id) => crate::server::webhooks::update_webhook(id, req).await,
+                                        None => crate::server::webhooks::create_webhook(req)
+                                            .await
+                                            .map(|created| created_secret.set(Some(created.secret))),
+                                    };
+                                    match result {
+                                        Ok(()) => {
+                                            show_modal.set(false);
+                                            webhooks_resource.restart();
+                                        }
+                                        Err(e) => save_error.set(Some(e.to_string())),
+                                    }
+                                },
+                                "Save Webhook"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! Waiting in components, which run in the browser as well as natively.
+
+use std::time::Duration;
+
+pub async fn sleep(duration: Duration) {
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+}
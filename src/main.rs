@@ -1,6 +1,6 @@
 use dioxus::prelude::*;
 
-use views::{Home, EmployeeView};
+use views::{Home, EmployeeView, ApiTokensView, WebhooksView, JobsView, EmailTemplatesView, ResetPasswordView, ChangePasswordView, UsersView, TwoFactorView, DirectorySyncView, SsoTwoFactorView};
 use components::layout::navbar::Navbar;
 
 mod components;
@@ -17,17 +17,123 @@ enum Route {
         Home {},
         #[route("/employees")]
         EmployeeView {},
+        #[route("/reset-password?:token")]
+        ResetPasswordView { token: String },
+        #[route("/account/password")]
+        ChangePasswordView {},
+        #[route("/account/two-factor")]
+        TwoFactorView {},
+        #[route("/sso/two-factor?:challenge&:step")]
+        SsoTwoFactorView { challenge: String, step: String },
+        #[route("/admin/users")]
+        UsersView {},
+        #[route("/admin/api-tokens")]
+        ApiTokensView {},
+        #[route("/admin/webhooks")]
+        WebhooksView {},
+        #[route("/admin/jobs")]
+        JobsView {},
+        #[route("/admin/email-templates")]
+        EmailTemplatesView {},
+        #[route("/admin/directory-sync")]
+        DirectorySyncView {},
 }
 
 const FAVICON: Asset = asset!("/assets/favicon.ico");
 const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 
 fn main() {
+    #[cfg(not(feature = "server"))]
     dioxus::launch(App);
+
+    #[cfg(feature = "server")]
+    {
+        use clap::Parser;
+
+        let cli = server::config::Cli::parse();
+        let config = match server::config::AppConfig::load(&cli) {
+            Ok(config) => server::config::init(config),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        };
+        if cli.print_config {
+            println!("{}", config);
+            return;
+        }
+        server::telemetry::init(&config.log);
+        tracing::debug!("configuration:\n{}", config);
+
+        if let Some(command) = &cli.command {
+            let runtime = tokio::runtime::Runtime::new().expect("failed to start the tokio runtime");
+            if let Err(e) = runtime.block_on(server::cli::run(command)) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+
+        // Served here rather than by dioxus::serve so that handlers get the peer
+        // address (ConnectInfo), which login throttling keys on. Without a
+        // configured address this is the one `dx serve` passes in IP/PORT.
+        let addr = config.server.bind_address.unwrap_or_else(dioxus::cli_config::fullstack_address_or_localhost);
+        let runtime = tokio::runtime::Runtime::new().expect("failed to start the tokio runtime");
+        if let Err(e) = runtime.block_on(serve(addr)) {
+            eprintln!("{:#}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+async fn serve(addr: std::net::SocketAddr) -> Result<(), anyhow::Error> {
+    let config = server::config::get();
+    let pool = server::db::connect_db().await?;
+    if config.database.run_migrations {
+        server::migrate::run(&pool).await?;
+    }
+
+    // Background workers draining the job queue (webhooks, emails, LDAP sync)
+    if config.jobs.workers > 0 {
+        server::jobs::runner::start(pool.clone(), config.jobs.workers);
+    }
+    // Stuck jobs and old rows are cleaned up on web-only nodes too
+    server::jobs::runner::start_maintenance(pool.clone());
+    server::ldap::sync::start_schedule(pool.clone());
+
+    // On the server we build the axum router ourselves so the REST API can be
+    // mounted next to the Dioxus app and its server functions.
+    let mut router = dioxus::server::router(App)
+        .merge(server::health::router(pool.clone(), config.features.metrics));
+    if config.features.rest_api {
+        router = router.nest("/api/v1", server::api::router(pool.clone()));
+    }
+    if config.features.sso {
+        router = router.nest("/auth/oidc", server::oidc::routes::router(pool));
+    }
+    if config.features.metrics {
+        server::metrics::handle();
+        router = router.layer(axum::middleware::from_fn(server::metrics::track));
+    }
+    // Outermost, so everything above runs inside the request span
+    let router = router.layer(axum::middleware::from_fn(server::telemetry::trace_request));
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!(%addr, "listening");
+    axum::serve(listener, router.into_make_service_with_connect_info::<std::net::SocketAddr>()).await?;
+    Ok(())
 }
 
 #[component]
 fn App() -> Element {
+    components::auth::use_auth_provider();
+    components::layout::toast::use_toast_provider();
+    #[cfg(feature = "offline")]
+    components::tables::employee_data::use_employee_backend_provider(|| {
+        std::rc::Rc::new(components::tables::employee_data::LocalBackend)
+    });
+
     // The `rsx!` macro lets us define HTML inside of rust. It expands to an Element with all of our HTML inside.
     rsx! {
         document::Link { rel: "icon", href: FAVICON }
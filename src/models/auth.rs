@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -9,6 +10,7 @@ pub struct User {
     pub username: String,
     pub password: String,
     pub enabled: bool,
+    pub email: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -23,4 +25,193 @@ pub struct Role {
 pub struct UserRole {
     pub user_id: i64,
     pub role_id: i64,
-}
\ No newline at end of file
+}
+
+// Role names from auth.roles
+pub const ROLE_EMPLOYEE: &str = "ROLE_EMPLOYEE";
+pub const ROLE_MANAGER: &str = "ROLE_MANAGER";
+pub const ROLE_ADMIN: &str = "ROLE_ADMIN";
+
+// Scopes limit what a caller may do. Signed-in users get the scopes of their
+// roles, API tokens get the intersection of their own scopes and their user's.
+pub const SCOPE_EMPLOYEES_READ: &str = "employees:read";
+pub const SCOPE_EMPLOYEES_WRITE: &str = "employees:write";
+pub const SCOPE_ADMIN: &str = "admin";
+
+pub const ALL_SCOPES: [&str; 3] = [SCOPE_EMPLOYEES_READ, SCOPE_EMPLOYEES_WRITE, SCOPE_ADMIN];
+
+/// Scopes granted by a set of role names.
+pub fn scopes_for_roles(roles: &[String]) -> Vec<String> {
+    let has = |role: &str| roles.iter().any(|r| r == role);
+    let mut scopes = Vec::new();
+
+    if has(ROLE_EMPLOYEE) || has(ROLE_MANAGER) || has(ROLE_ADMIN) {
+        scopes.push(SCOPE_EMPLOYEES_READ.to_string());
+    }
+    if has(ROLE_MANAGER) || has(ROLE_ADMIN) {
+        scopes.push(SCOPE_EMPLOYEES_WRITE.to_string());
+    }
+    if has(ROLE_ADMIN) {
+        scopes.push(SCOPE_ADMIN.to_string());
+    }
+    scopes
+}
+
+pub const PASSWORD_MIN_LEN: usize = 12;
+// bcrypt only looks at the first 72 bytes, longer passwords would be silently truncated
+pub const PASSWORD_MAX_LEN: usize = 72;
+
+// Long enough to pass the length rule but still on every wordlist
+const COMMON_PASSWORDS: [&str; 8] = [
+    "password1234", "123456789012", "qwertyuiop12", "changeme1234",
+    "welcome12345", "letmein12345", "password123456", "administrator1",
+];
+
+/// Everything `password` violates in the password policy, empty if it is acceptable.
+/// `username` may be empty when it isn't known (e.g. on the reset page).
+pub fn password_policy_errors(password: &str, username: &str) -> Vec<String> {
+    let mut errors = Vec::new();
+    let lower = password.to_lowercase();
+
+    if password.chars().count() < PASSWORD_MIN_LEN {
+        errors.push(format!("At least {} characters", PASSWORD_MIN_LEN));
+    }
+    if password.len() > PASSWORD_MAX_LEN {
+        errors.push(format!("At most {} bytes", PASSWORD_MAX_LEN));
+    }
+    if !password.chars().any(char::is_alphabetic) || !password.chars().any(|c| c.is_ascii_digit()) {
+        errors.push("At least one letter and one digit".to_string());
+    }
+    if !username.is_empty() && lower.contains(&username.to_lowercase()) {
+        errors.push("Must not contain the username".to_string());
+    }
+    if COMMON_PASSWORDS.contains(&lower.as_str()) {
+        errors.push("Too common".to_string());
+    }
+    errors
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+// The signed-in user as seen by the UI
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentUser {
+    pub id: i64,
+    pub username: String,
+    pub roles: Vec<String>,
+    pub scopes: Vec<String>,
+}
+
+impl CurrentUser {
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiTokenKind {
+    Personal,
+    Service,
+}
+
+impl ApiTokenKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiTokenKind::Personal => "personal",
+            ApiTokenKind::Service => "service",
+        }
+    }
+}
+
+// An API token as listed in the admin screen, the secret is never included
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiToken {
+    pub id: i64,
+    pub username: String,
+    pub name: String,
+    pub kind: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiToken {
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at > Utc::now()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueApiTokenRequest {
+    pub username: String,
+    pub name: String,
+    pub kind: ApiTokenKind,
+    pub scopes: Vec<String>,
+    pub expires_in_days: i32,
+}
+
+// Returned once after issuing, `secret` is the only copy of the raw token
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuedApiToken {
+    pub token: ApiToken,
+    pub secret: String,
+}
+
+// A login account as listed in the user admin screen
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAccount {
+    pub id: i64,
+    pub username: String,
+    pub email: Option<String>,
+    pub enabled: bool,
+    pub roles: Vec<String>,
+    pub two_factor_enabled: bool,
+    pub failed_logins: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+impl UserAccount {
+    pub fn is_locked(&self) -> bool {
+        self.locked_until.is_some_and(|until| until > Utc::now())
+    }
+}
+
+// Shown as "Sign in with <name>" in the login dialog when SSO is configured
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SsoProvider {
+    pub name: String,
+    pub login_url: String,
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+// Headline numbers for the dashboard cards
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkforceSummary {
+    pub headcount: i64,
+    pub new_hires_this_month: i64,
+    pub leavers_this_month: i64,
+}
+
+// Active employees grouped by department
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DepartmentHeadcount {
+    pub department: String,
+    pub headcount: i64,
+}
+
+// Headcount at the end of a month, `month` is formatted as YYYY-MM
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadcountTrendPoint {
+    pub month: String,
+    pub headcount: i64,
+}
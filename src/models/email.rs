@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Keys of the rows in public.email_templates
+pub const TEMPLATE_EMPLOYEE_WELCOME: &str = "employee.welcome";
+// Seeded and editable, but nothing sends it until there is a leave workflow
+#[allow(dead_code)]
+pub const TEMPLATE_LEAVE_APPROVED: &str = "leave.approved";
+pub const TEMPLATE_PASSWORD_RESET: &str = "password.reset";
+
+pub const SUBJECT_MAX_LEN: usize = 200;
+pub const BODY_MAX_LEN: usize = 10_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailTemplate {
+    pub key: String,
+    pub description: String,
+    pub subject: String,
+    pub body: String,
+    /// Variables the sender provides for this template, usable as `{{name}}`
+    pub variables: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveEmailTemplateRequest {
+    pub subject: String,
+    pub body: String,
+}
+
+/// Names of all `{{name}}` placeholders in `text`, in order of appearance.
+pub fn placeholders(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else { break };
+        names.push(after[..end].trim().to_string());
+        rest = &after[end + 2..];
+    }
+    names
+}
+
+/// Replaces `{{name}}` with the matching variable. Unknown placeholders are left as they are
+/// so a typo stays visible in the sent mail instead of silently disappearing.
+pub fn render(text: &str, vars: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        match vars.get(after[..end].trim()) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+// Column limits from public.employee
+pub const NAME_MAX_LEN: usize = 45;
+pub const EMAIL_MAX_LEN: usize = 100;
+
 // The request parameters
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -7,6 +11,49 @@ pub struct EmployeeRequest {
     pub page: i64,
     pub page_size: i64,
     pub search_term: Option<String>,
+    #[serde(default)]
+    pub sort_by: EmployeeSortField,
+    #[serde(default)]
+    pub sort_dir: SortDirection,
+}
+
+// Columns the employee list can be ordered by
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmployeeSortField {
+    #[default]
+    Id,
+    FirstName,
+    LastName,
+    Email,
+}
+
+impl EmployeeSortField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmployeeSortField::Id => "id",
+            EmployeeSortField::FirstName => "first_name",
+            EmployeeSortField::LastName => "last_name",
+            EmployeeSortField::Email => "email",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        }
+    }
 }
 
 // The paginated response wrapper
@@ -19,7 +66,7 @@ pub struct EmployeeResponse {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
-#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+#[cfg_attr(any(feature = "server", feature = "offline"), derive(sqlx::FromRow))]
 pub struct Employee {
     pub id: i64,
     pub first_name: String,
@@ -40,4 +87,49 @@ pub struct UpdateEmployeeRequest {
     pub first_name: String,
     pub last_name: String,
     pub email: String,
-}
\ No newline at end of file
+}
+
+// A single failed validation rule, `field` uses the request field name
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl CreateEmployeeRequest {
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        validate_employee_fields(&self.first_name, &self.last_name, &self.email)
+    }
+}
+
+impl UpdateEmployeeRequest {
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        validate_employee_fields(&self.first_name, &self.last_name, &self.email)
+    }
+}
+
+// Shared by the server functions, the REST API and the modals
+fn validate_employee_fields(first_name: &str, last_name: &str, email: &str) -> Result<(), Vec<FieldError>> {
+    let mut errors = Vec::new();
+    let mut fail = |field: &str, message: String| errors.push(FieldError { field: field.to_string(), message });
+
+    if first_name.trim().is_empty() {
+        fail("first_name", "First name is required".to_string());
+    } else if first_name.chars().count() > NAME_MAX_LEN {
+        fail("first_name", format!("First name must be at most {} characters", NAME_MAX_LEN));
+    }
+
+    if last_name.trim().is_empty() {
+        fail("last_name", "Last name is required".to_string());
+    } else if last_name.chars().count() > NAME_MAX_LEN {
+        fail("last_name", format!("Last name must be at most {} characters", NAME_MAX_LEN));
+    }
+
+    if !email.contains('@') {
+        fail("email", "Please enter a valid email address".to_string());
+    } else if email.chars().count() > EMAIL_MAX_LEN {
+        fail("email", format!("Email must be at most {} characters", EMAIL_MAX_LEN));
+    }
+
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
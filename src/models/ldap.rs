@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LdapChangeKind {
+    // In the directory, no employee with this email yet
+    Create,
+    // Matched by email, name or DN differ
+    Update,
+    // Synced before, no longer in the directory
+    Missing,
+    // Flagged as missing earlier, back in the directory
+    Returned,
+}
+
+impl LdapChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LdapChangeKind::Create => "create",
+            LdapChangeKind::Update => "update",
+            LdapChangeKind::Missing => "missing",
+            LdapChangeKind::Returned => "returned",
+        }
+    }
+}
+
+/// One line of the diff between the directory and `employee`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LdapChange {
+    pub kind: LdapChangeKind,
+    pub employee_id: Option<i64>,
+    pub dn: Option<String>,
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    // Current values for updates, so the preview can show old -> new
+    pub old_first_name: Option<String>,
+    pub old_last_name: Option<String>,
+}
+
+// A directory entry that can't become an employee (no mail, name too long, ...)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LdapSkippedEntry {
+    pub dn: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LdapSyncPreview {
+    pub changes: Vec<LdapChange>,
+    pub skipped: Vec<LdapSkippedEntry>,
+    pub unchanged: i64,
+    // Applying is refused if the diff no longer matches this
+    pub fingerprint: String,
+}
+
+// One sync as listed in the history
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LdapSyncRun {
+    pub id: i64,
+    pub triggered_by: String,
+    pub status: String,
+    pub created: i32,
+    pub updated: i32,
+    pub missing: i32,
+    pub returned: i32,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+// An employee flagged because they disappeared from the directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingEmployee {
+    pub id: i64,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub ldap_dn: Option<String>,
+    pub missing_since: DateTime<Utc>,
+}
@@ -1,2 +1,8 @@
 pub mod employee;
 pub mod auth;
+pub mod dashboard;
+pub mod webhook;
+pub mod job;
+pub mod email;
+pub mod two_factor;
+pub mod ldap;
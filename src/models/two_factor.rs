@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use super::auth::CurrentUser;
+
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+/// What the login dialog has to do after the password was accepted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum LoginResult {
+    SignedIn { user: CurrentUser },
+    /// Ask for a TOTP or recovery code and pass it with `challenge`
+    SecondFactorRequired { challenge: String },
+    /// A role of the user requires 2FA, which has to be set up before signing in
+    EnrollmentRequired { challenge: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpEnrollment {
+    /// Base32 secret for manual entry
+    pub secret: String,
+    pub otpauth_uri: String,
+    pub qr_svg: String,
+}
+
+// Returned once after confirming enrollment, the codes are not retrievable later
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpConfirmation {
+    pub recovery_codes: Vec<String>,
+    /// Set when enrollment was part of a login, the session is then started
+    pub user: Option<CurrentUser>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    /// One of the user's roles requires 2FA, so it can't be turned off
+    pub required: bool,
+    pub recovery_codes_left: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RolePolicy {
+    pub name: String,
+    pub require_two_factor: bool,
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Employee lifecycle events a webhook can subscribe to
+pub const EVENT_EMPLOYEE_CREATED: &str = "employee.created";
+pub const EVENT_EMPLOYEE_UPDATED: &str = "employee.updated";
+pub const EVENT_EMPLOYEE_DELETED: &str = "employee.deleted";
+// A delete was undone; the payload is the employee as it was before
+pub const EVENT_EMPLOYEE_RESTORED: &str = "employee.restored";
+
+pub const ALL_EVENTS: [&str; 4] =
+    [EVENT_EMPLOYEE_CREATED, EVENT_EMPLOYEE_UPDATED, EVENT_EMPLOYEE_DELETED, EVENT_EMPLOYEE_RESTORED];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookSubscription {
+    pub id: i64,
+    pub name: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveWebhookRequest {
+    pub name: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub enabled: bool,
+}
+
+// Returned once after creating a subscription, receivers need `secret` to verify signatures
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedWebhook {
+    pub subscription: WebhookSubscription,
+    pub secret: String,
+}
+
+// One HTTP attempt of a delivery, as shown in the delivery log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookAttempt {
+    pub id: i64,
+    pub delivery_id: i64,
+    pub subscription_name: String,
+    pub event: String,
+    pub attempt: i32,
+    pub attempted_at: DateTime<Utc>,
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i32,
+    pub delivery_status: String,
+}
+
+impl WebhookAttempt {
+    pub fn succeeded(&self) -> bool {
+        self.response_status.is_some_and(|s| (200..300).contains(&s))
+    }
+}
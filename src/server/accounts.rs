@@ -0,0 +1,199 @@
+//! Login account changes shared by SSO provisioning and the admin CLI.
+
+use dioxus::prelude::ServerFnError;
+use sqlx::{PgConnection, PgPool};
+use std::fmt;
+
+use super::security;
+use crate::models::auth::password_policy_errors;
+
+// Column limit from auth.users
+pub const USERNAME_MAX_LEN: usize = 50;
+
+#[derive(Debug)]
+pub enum AccountError {
+    Validation(Vec<String>),
+    DuplicateUsername,
+    NotFound,
+    UnknownRole(String),
+    Internal(String),
+    Database(sqlx::Error),
+}
+
+impl fmt::Display for AccountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountError::Validation(errors) => write!(f, "Validation failed: {}", errors.join(", ")),
+            AccountError::DuplicateUsername => write!(f, "A user with this username or email already exists"),
+            AccountError::NotFound => write!(f, "User not found"),
+            AccountError::UnknownRole(role) => write!(f, "Unknown role '{}'", role),
+            AccountError::Internal(e) => write!(f, "{}", e),
+            AccountError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AccountError {}
+
+impl From<sqlx::Error> for AccountError {
+    fn from(e: sqlx::Error) -> Self {
+        match &e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => AccountError::DuplicateUsername,
+            _ => AccountError::Database(e),
+        }
+    }
+}
+
+// hash_password only fails if the blocking task dies
+impl From<ServerFnError> for AccountError {
+    fn from(e: ServerFnError) -> Self {
+        AccountError::Internal(e.to_string())
+    }
+}
+
+impl From<AccountError> for ServerFnError {
+    fn from(e: AccountError) -> Self {
+        let code = match &e {
+            AccountError::Validation(_) | AccountError::UnknownRole(_) => 422,
+            AccountError::DuplicateUsername => 409,
+            AccountError::NotFound => 404,
+            AccountError::Internal(_) | AccountError::Database(_) => 500,
+        };
+        ServerFnError::ServerError { message: e.to_string(), code, details: None }
+    }
+}
+
+/// `admin` and `role_admin` both mean `ROLE_ADMIN`.
+pub fn role_name(name: &str) -> String {
+    let upper = name.trim().to_uppercase();
+    if upper.starts_with("ROLE_") {
+        upper
+    } else {
+        format!("ROLE_{}", upper)
+    }
+}
+
+fn check_password(password: &str, username: &str) -> Result<(), AccountError> {
+    let errors = password_policy_errors(password, username);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AccountError::Validation(errors))
+    }
+}
+
+/// Creates an enabled user with exactly `roles` and returns its id.
+pub async fn create(
+    pool: &PgPool,
+    username: &str,
+    email: Option<&str>,
+    password: &str,
+    roles: &[String],
+) -> Result<i64, AccountError> {
+    let username = username.trim();
+    let mut errors = Vec::new();
+    if username.is_empty() || username.chars().count() > USERNAME_MAX_LEN {
+        errors.push(format!("Username must be 1 to {} characters", USERNAME_MAX_LEN));
+    } else if username.chars().any(char::is_whitespace) {
+        errors.push("Username must not contain spaces".to_string());
+    }
+    if email.is_some_and(|e| !e.contains('@')) {
+        errors.push("Email is not valid".to_string());
+    }
+    errors.extend(password_policy_errors(password, username));
+    if !errors.is_empty() {
+        return Err(AccountError::Validation(errors));
+    }
+
+    let hash = security::hash_password(password).await?;
+    let mut tx = pool.begin().await?;
+
+    let user_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO auth.users (username, password, enabled, email)
+        VALUES ($1, $2, true, $3)
+        RETURNING id
+        "#,
+        username,
+        hash,
+        email.map(str::trim)
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sync_roles(&mut tx, user_id, roles).await?;
+    tx.commit().await?;
+    Ok(user_id)
+}
+
+/// Replaces the roles of `username` with exactly `roles`.
+pub async fn set_roles(pool: &PgPool, username: &str, roles: &[String]) -> Result<(), AccountError> {
+    let mut tx = pool.begin().await?;
+    let user_id = sqlx::query_scalar!("SELECT id FROM auth.users WHERE username = $1 FOR UPDATE", username.trim())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AccountError::NotFound)?;
+
+    sync_roles(&mut tx, user_id, roles).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Sets a new password, lifts a lock and ends the user's sessions.
+pub async fn set_password(pool: &PgPool, username: &str, password: &str) -> Result<(), AccountError> {
+    let username = username.trim();
+    check_password(password, username)?;
+    let hash = security::hash_password(password).await?;
+    let mut tx = pool.begin().await?;
+
+    let user_id = sqlx::query_scalar!(
+        r#"
+        UPDATE auth.users
+        SET password = $2, password_changed_at = now(), failed_logins = 0, locked_until = NULL
+        WHERE username = $1
+        RETURNING id
+        "#,
+        username,
+        hash
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(AccountError::NotFound)?;
+
+    sqlx::query!("DELETE FROM auth.sessions WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Leaves the user with exactly `roles`, which must all exist.
+pub async fn sync_roles(conn: &mut PgConnection, user_id: i64, roles: &[String]) -> Result<(), AccountError> {
+    let role_ids = sqlx::query!("SELECT id, name FROM auth.roles WHERE name = ANY($1)", roles)
+        .fetch_all(&mut *conn)
+        .await?;
+    if let Some(missing) = roles.iter().find(|r| !role_ids.iter().any(|row| &row.name == *r)) {
+        return Err(AccountError::UnknownRole(missing.clone()));
+    }
+    let ids: Vec<i64> = role_ids.iter().map(|row| row.id).collect();
+
+    sqlx::query!(
+        "DELETE FROM auth.user_roles WHERE user_id = $1 AND NOT (role_id = ANY($2))",
+        user_id,
+        &ids
+    )
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO auth.user_roles (user_id, role_id)
+        SELECT $1, unnest($2::BIGINT[])
+        ON CONFLICT DO NOTHING
+        "#,
+        user_id,
+        &ids
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
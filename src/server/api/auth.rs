@@ -0,0 +1,17 @@
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use sqlx::PgPool;
+
+use super::error::ApiError;
+use crate::server::security::{self, AuthError, Principal};
+
+/// Every REST endpoint needs an authenticated caller, usually via `Authorization: Bearer <token>`.
+impl FromRequestParts<PgPool> for Principal {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, pool: &PgPool) -> Result<Self, Self::Rejection> {
+        security::authenticate(pool, &parts.headers)
+            .await?
+            .ok_or_else(|| AuthError::Unauthenticated.into())
+    }
+}
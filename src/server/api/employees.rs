@@ -0,0 +1,130 @@
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use super::error::ApiError;
+use crate::models::auth::{SCOPE_EMPLOYEES_READ, SCOPE_EMPLOYEES_WRITE};
+use crate::models::employee::{
+    CreateEmployeeRequest, Employee, EmployeeRequest, EmployeeSortField, SortDirection,
+    UpdateEmployeeRequest,
+};
+use crate::server::employees;
+use crate::server::security::Principal;
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+
+// GET /employees?page=1&pageSize=20&search=foo&sort=last_name&order=desc
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListParams {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub search: Option<String>,
+    pub sort: Option<EmployeeSortField>,
+    pub order: Option<SortDirection>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmployeePage {
+    pub data: Vec<Employee>,
+    pub page: i64,
+    pub page_size: i64,
+    pub total_count: i64,
+    pub total_pages: i64,
+}
+
+pub async fn list(
+    State(pool): State<PgPool>,
+    principal: Principal,
+    Query(params): Query<ListParams>,
+) -> Result<Json<EmployeePage>, ApiError> {
+    principal.require_scope(SCOPE_EMPLOYEES_READ)?;
+
+    let page = params.page.unwrap_or(1);
+    let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    if page < 1 || !(1..=employees::MAX_PAGE_SIZE).contains(&page_size) {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("page must be >= 1 and pageSize between 1 and {}", employees::MAX_PAGE_SIZE),
+        ));
+    }
+
+    let req = EmployeeRequest {
+        page,
+        page_size,
+        search_term: params.search.filter(|s| !s.is_empty()),
+        sort_by: params.sort.unwrap_or_default(),
+        sort_dir: params.order.unwrap_or_default(),
+    };
+    let res = employees::list(&pool, &req).await?;
+
+    Ok(Json(EmployeePage {
+        data: res.employees,
+        page,
+        page_size,
+        total_count: res.total_count,
+        total_pages: ((res.total_count + page_size - 1) / page_size).max(1),
+    }))
+}
+
+pub async fn get(
+    State(pool): State<PgPool>,
+    principal: Principal,
+    Path(id): Path<i64>,
+) -> Result<Json<Employee>, ApiError> {
+    principal.require_scope(SCOPE_EMPLOYEES_READ)?;
+    Ok(Json(employees::find(&pool, id).await?))
+}
+
+pub async fn create(
+    State(pool): State<PgPool>,
+    principal: Principal,
+    Json(body): Json<CreateEmployeeRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    principal.require_scope(SCOPE_EMPLOYEES_WRITE)?;
+
+    let employee = employees::create(&pool, &body).await?;
+    let location = format!("/api/v1/employees/{}", employee.id);
+
+    Ok((StatusCode::CREATED, [(header::LOCATION, location)], Json(employee)))
+}
+
+pub async fn update(
+    State(pool): State<PgPool>,
+    principal: Principal,
+    Path(id): Path<i64>,
+    Json(body): Json<CreateEmployeeRequest>,
+) -> Result<Json<Employee>, ApiError> {
+    principal.require_scope(SCOPE_EMPLOYEES_WRITE)?;
+
+    let req = UpdateEmployeeRequest {
+        id,
+        first_name: body.first_name,
+        last_name: body.last_name,
+        email: body.email,
+    };
+    Ok(Json(employees::update(&pool, &req).await?))
+}
+
+pub async fn delete(
+    State(pool): State<PgPool>,
+    principal: Principal,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    principal.require_scope(SCOPE_EMPLOYEES_WRITE)?;
+    employees::delete(&pool, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn restore(
+    State(pool): State<PgPool>,
+    principal: Principal,
+    Path(id): Path<i64>,
+) -> Result<Json<Employee>, ApiError> {
+    principal.require_scope(SCOPE_EMPLOYEES_WRITE)?;
+    Ok(Json(employees::restore(&pool, id).await?))
+}
@@ -0,0 +1,59 @@
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+use crate::models::employee::FieldError;
+use crate::server::employees::EmployeeError;
+use crate::server::security::AuthError;
+
+/// JSON error body returned by every REST endpoint.
+#[derive(Debug, Serialize)]
+pub struct ApiError {
+    #[serde(skip)]
+    pub status: StatusCode,
+    pub error: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, error: impl Into<String>) -> Self {
+        Self { status, error: error.into(), details: Vec::new() }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        if self.status == StatusCode::UNAUTHORIZED {
+            return (self.status, [(header::WWW_AUTHENTICATE, "Bearer")], Json(self)).into_response();
+        }
+        (self.status, Json(self)).into_response()
+    }
+}
+
+impl From<EmployeeError> for ApiError {
+    fn from(e: EmployeeError) -> Self {
+        match e {
+            EmployeeError::Validation(details) => ApiError {
+                status: StatusCode::UNPROCESSABLE_ENTITY,
+                error: "Validation failed".to_string(),
+                details,
+            },
+            EmployeeError::DuplicateEmail => ApiError::new(StatusCode::CONFLICT, e.to_string()),
+            EmployeeError::NotFound => ApiError::new(StatusCode::NOT_FOUND, e.to_string()),
+            EmployeeError::Database(_) => ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+}
+
+impl From<AuthError> for ApiError {
+    fn from(e: AuthError) -> Self {
+        let status = match &e {
+            AuthError::Unauthenticated => StatusCode::UNAUTHORIZED,
+            AuthError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AuthError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(status, e.to_string())
+    }
+}
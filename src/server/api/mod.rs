@@ -0,0 +1,32 @@
+//! Versioned REST/JSON API for machine clients, mounted at `/api/v1`.
+//!
+//! The handlers reuse the queries and validation from [`crate::server::employees`],
+//! so the REST surface and the `#[server]` functions always behave the same.
+//! Callers authenticate with an API token sent as `Authorization: Bearer <token>`.
+
+use axum::http::header;
+use axum::routing::{get, post};
+use axum::Router;
+use sqlx::PgPool;
+
+pub mod auth;
+pub mod employees;
+pub mod error;
+
+const OPENAPI_JSON: &str = include_str!("openapi.json");
+
+pub fn router(pool: PgPool) -> Router {
+    Router::new()
+        .route("/employees", get(employees::list).post(employees::create))
+        .route(
+            "/employees/{id}",
+            get(employees::get).put(employees::update).delete(employees::delete),
+        )
+        .route("/employees/{id}/restore", post(employees::restore))
+        .route("/openapi.json", get(openapi))
+        .with_state(pool)
+}
+
+async fn openapi() -> ([(header::HeaderName, &'static str); 1], &'static str) {
+    ([(header::CONTENT_TYPE, "application/json")], OPENAPI_JSON)
+}
@@ -0,0 +1,125 @@
+use dioxus::prelude::*;
+use crate::models::auth::{CurrentUser, LoginRequest};
+use crate::models::two_factor::LoginResult;
+
+#[cfg(feature = "server")]
+use super::{db, login_guard, security, two_factor};
+
+/// Checks username and password. Users with 2FA (or whose role requires it) get a
+/// challenge for the second step instead of a session.
+#[server]
+pub async fn login(req: LoginRequest) -> Result<LoginResult, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+
+        let attempt_name = login_guard::normalize(&req.username);
+        let ip = login_guard::client_ip(&security::request_headers(), security::request_peer());
+        login_guard::check(&pool, &attempt_name, &ip).await?;
+
+        let user = sqlx::query!(
+            "SELECT id, username, password, enabled, locked_until FROM auth.users WHERE username = $1",
+            req.username.trim()
+        )
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        // A locked account is refused before the password is even checked, so
+        // guessing can't continue during the lock. Disabled accounts never get here
+        // with a lock message, they just fail like a wrong password.
+        if let Some(until) = user.as_ref().filter(|u| u.enabled).and_then(|u| u.locked_until) {
+            if until > chrono::Utc::now() {
+                return Err(login_guard::locked(until));
+            }
+        }
+
+        let valid = security::verify_password(&req.password, user.as_ref().map(|u| u.password.as_str())).await?;
+
+        let user = match user {
+            Some(u) if valid && u.enabled => u,
+            other => {
+                let locked_until = login_guard::record_failure(&pool, &attempt_name, &ip, other.map(|u| u.id))
+                    .await
+                    .map_err(|e| ServerFnError::new(e.to_string()))?;
+                if let Some(until) = locked_until {
+                    return Err(login_guard::locked(until));
+                }
+                return Err(ServerFnError::ServerError {
+                    message: "Invalid username or password".to_string(),
+                    code: 401,
+                    details: None,
+                });
+            }
+        };
+
+        // The failure counter is only reset once the second factor passed as well
+        let (enrolled, required) = two_factor::state(&pool, user.id)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        if enrolled || required {
+            let purpose = if enrolled { two_factor::PURPOSE_VERIFY } else { two_factor::PURPOSE_ENROLL };
+            let challenge = two_factor::create_challenge(&pool, user.id, purpose)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+            return Ok(if enrolled {
+                LoginResult::SecondFactorRequired { challenge }
+            } else {
+                LoginResult::EnrollmentRequired { challenge }
+            });
+        }
+
+        login_guard::record_success(&pool, &attempt_name, &ip, user.id)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let user = security::start_session(&pool, user.id, user.username).await?;
+        Ok(LoginResult::SignedIn { user })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+#[server]
+pub async fn logout() -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+
+        let headers = security::request_headers();
+        if let Some(raw) = security::cookie(&headers, security::SESSION_COOKIE) {
+            sqlx::query!("DELETE FROM auth.sessions WHERE token_hash = $1", security::hash_session_token(raw))
+                .execute(&pool)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+        }
+        security::clear_session_cookie();
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// The signed-in user, or `None` for anonymous visitors.
+#[server]
+pub async fn current_user() -> Result<Option<CurrentUser>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+
+        let principal = security::current_principal(&pool).await?;
+        Ok(principal.map(|p| p.to_current_user()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
@@ -0,0 +1,405 @@
+//! Portable backups of the `public` and `auth` schemas, without `pg_dump`.
+//!
+//! A backup is a directory with one JSON-lines file per table (each line is
+//! the row as `to_jsonb` renders it) and a `manifest.json` describing the
+//! format version, the schema version of the build that wrote it and every
+//! table's columns, row count and checksum.
+//!
+//! Restore refuses archives whose tables or columns differ from the target
+//! database, then replaces the data of every table in one transaction.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{Connection, PgConnection, PgPool};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use super::migrate::MIGRATIONS;
+
+pub const FORMAT: &str = "ems-backup";
+// Bump when the archive layout changes; restore rejects newer versions
+pub const FORMAT_VERSION: u32 = 1;
+const MANIFEST_FILE: &str = "manifest.json";
+const SCHEMAS: [&str; 2] = ["public", "auth"];
+// Describes the schema rather than the data, so it is neither saved nor restored
+const SKIPPED_TABLE: &str = "public.schema_migrations";
+const BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: String,
+    pub format_version: u32,
+    // Newest migration of the build that wrote the archive
+    pub schema_version: String,
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    pub tables: Vec<TableEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableEntry {
+    pub schema: String,
+    pub name: String,
+    pub file: String,
+    pub rows: u64,
+    pub sha256: String,
+    pub columns: Vec<Column>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Column {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub data_type: String,
+}
+
+impl TableEntry {
+    pub fn qualified_name(&self) -> String {
+        format!("{}.{}", self.schema, self.name)
+    }
+}
+
+#[derive(Debug)]
+pub enum BackupError {
+    Io(PathBuf, std::io::Error),
+    Database(sqlx::Error),
+    Format(String),
+    Incompatible(Vec<String>),
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackupError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            BackupError::Database(e) => write!(f, "Database error: {}", e),
+            BackupError::Format(msg) => write!(f, "Invalid archive: {}", msg),
+            BackupError::Incompatible(problems) => {
+                writeln!(f, "The archive does not match this database:")?;
+                for problem in problems {
+                    writeln!(f, "  - {}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for BackupError {}
+
+impl From<sqlx::Error> for BackupError {
+    fn from(e: sqlx::Error) -> Self {
+        BackupError::Database(e)
+    }
+}
+
+fn io_error(path: &Path) -> impl FnOnce(std::io::Error) -> BackupError + '_ {
+    move |e| BackupError::Io(path.to_path_buf(), e)
+}
+
+/// `"schema"."table"`, safe to splice into SQL
+fn quoted(schema: &str, name: &str) -> String {
+    format!("\"{}\".\"{}\"", schema.replace('"', "\"\""), name.replace('"', "\"\""))
+}
+
+fn quoted_column(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Tables of the backed-up schemas with their (non-generated) columns,
+/// ordered so that every table comes after the tables it references.
+async fn tables(conn: &mut PgConnection) -> Result<Vec<(String, String, Vec<Column>)>, sqlx::Error> {
+    let schemas = SCHEMAS.map(String::from);
+    let rows = sqlx::query!(
+        r#"
+        SELECT n.nspname::TEXT AS "schema!", c.relname::TEXT AS "table!",
+               a.attname::TEXT AS "column!", format_type(a.atttypid, a.atttypmod) AS "data_type!"
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum > 0 AND NOT a.attisdropped AND a.attgenerated = ''
+        WHERE c.relkind IN ('r', 'p') AND n.nspname::TEXT = ANY($1::TEXT[])
+        ORDER BY n.nspname, c.relname, a.attnum
+        "#,
+        &schemas
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut columns: BTreeMap<String, (String, String, Vec<Column>)> = BTreeMap::new();
+    for row in rows {
+        let key = format!("{}.{}", row.schema, row.table);
+        if key == SKIPPED_TABLE {
+            continue;
+        }
+        columns
+            .entry(key)
+            .or_insert_with(|| (row.schema.clone(), row.table.clone(), Vec::new()))
+            .2
+            .push(Column { name: row.column, data_type: row.data_type });
+    }
+
+    let references = sqlx::query!(
+        r#"
+        SELECT cn.nspname::TEXT || '.' || c.relname::TEXT AS "table!",
+               fn.nspname::TEXT || '.' || f.relname::TEXT AS "references!"
+        FROM pg_constraint k
+        JOIN pg_class c ON c.oid = k.conrelid
+        JOIN pg_namespace cn ON cn.oid = c.relnamespace
+        JOIN pg_class f ON f.oid = k.confrelid
+        JOIN pg_namespace fn ON fn.oid = f.relnamespace
+        WHERE k.contype = 'f' AND cn.nspname::TEXT = ANY($1::TEXT[])
+        "#,
+        &schemas
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut depends: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for r in references {
+        // Self-references don't affect the order between tables
+        if r.table != r.references && columns.contains_key(&r.references) {
+            depends.entry(r.table).or_default().insert(r.references);
+        }
+    }
+
+    // Repeatedly take every table whose references are all placed already;
+    // a cycle would end up last in name order and fail on insert
+    let mut ordered = Vec::with_capacity(columns.len());
+    let mut placed = BTreeSet::new();
+    while placed.len() < columns.len() {
+        let ready: Vec<String> = columns
+            .keys()
+            .filter(|t| !placed.contains(*t))
+            .filter(|t| depends.get(*t).is_none_or(|deps| deps.iter().all(|d| placed.contains(d))))
+            .cloned()
+            .collect();
+        let batch = if ready.is_empty() {
+            columns.keys().filter(|t| !placed.contains(*t)).cloned().collect()
+        } else {
+            ready
+        };
+        for table in batch {
+            placed.insert(table.clone());
+            ordered.push(table);
+        }
+    }
+    Ok(ordered.into_iter().filter_map(|t| columns.remove(&t)).collect())
+}
+
+/// Writes every table to `dir`, which must not exist or be empty. The rows
+/// are read in one repeatable-read transaction, so the archive is consistent.
+pub async fn backup(pool: &PgPool, dir: &Path) -> Result<Manifest, BackupError> {
+    if dir.exists() && fs::read_dir(dir).map_err(io_error(dir))?.next().is_some() {
+        let e = std::io::Error::new(std::io::ErrorKind::AlreadyExists, "directory is not empty");
+        return Err(BackupError::Io(dir.to_path_buf(), e));
+    }
+    fs::create_dir_all(dir).map_err(io_error(dir))?;
+
+    let mut conn = pool.acquire().await?;
+    let mut tx = conn.begin().await?;
+    sqlx::raw_sql("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY").execute(&mut *tx).await?;
+
+    let mut entries = Vec::new();
+    for (schema, name, columns) in tables(&mut tx).await? {
+        let file = format!("{}.{}.jsonl", schema, name);
+        let path = dir.join(&file);
+        let mut out = BufWriter::new(File::create(&path).map_err(io_error(&path))?);
+        let mut hasher = Sha256::new();
+        let mut rows = 0u64;
+
+        // A cursor keeps memory flat for big tables; it lives until the commit
+        sqlx::raw_sql(&format!(
+            "DECLARE backup_rows NO SCROLL CURSOR FOR SELECT to_jsonb(t)::TEXT FROM {} t",
+            quoted(&schema, &name)
+        ))
+        .execute(&mut *tx)
+        .await?;
+        loop {
+            let batch: Vec<String> = sqlx::query_scalar(&format!("FETCH {} FROM backup_rows", BATCH_SIZE))
+                .fetch_all(&mut *tx)
+                .await?;
+            for line in &batch {
+                hasher.update(line.as_bytes());
+                hasher.update(b"\n");
+                writeln!(out, "{}", line).map_err(io_error(&path))?;
+            }
+            rows += batch.len() as u64;
+            if batch.len() < BATCH_SIZE {
+                break;
+            }
+        }
+        sqlx::raw_sql("CLOSE backup_rows").execute(&mut *tx).await?;
+        out.flush().map_err(io_error(&path))?;
+
+        tracing::info!(table = %format!("{}.{}", schema, name), rows, "table exported");
+        entries.push(TableEntry { schema, name, file, rows, sha256: hex::encode(hasher.finalize()), columns });
+    }
+    tx.commit().await?;
+
+    let manifest = Manifest {
+        format: FORMAT.to_string(),
+        format_version: FORMAT_VERSION,
+        schema_version: schema_version().to_string(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Utc::now(),
+        tables: entries,
+    };
+    let path = dir.join(MANIFEST_FILE);
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| BackupError::Format(e.to_string()))?;
+    fs::write(&path, json).map_err(io_error(&path))?;
+    Ok(manifest)
+}
+
+fn schema_version() -> &'static str {
+    MIGRATIONS.last().map_or("", |m| m.version)
+}
+
+/// Reads and checks the manifest and every data file, without touching the database.
+pub fn read_archive(dir: &Path) -> Result<Manifest, BackupError> {
+    let path = dir.join(MANIFEST_FILE);
+    let text = fs::read_to_string(&path).map_err(io_error(&path))?;
+    let manifest: Manifest = serde_json::from_str(&text).map_err(|e| BackupError::Format(e.to_string()))?;
+
+    if manifest.format != FORMAT {
+        return Err(BackupError::Format(format!("not an {} archive", FORMAT)));
+    }
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(BackupError::Format(format!(
+            "format version {} is newer than this build understands ({})",
+            manifest.format_version, FORMAT_VERSION
+        )));
+    }
+
+    for table in &manifest.tables {
+        // Names come from the manifest; keep the files inside the archive
+        if table.file.contains(['/', '\\']) || table.file.starts_with('.') {
+            return Err(BackupError::Format(format!("bad file name '{}'", table.file)));
+        }
+        let path = dir.join(&table.file);
+        let mut hasher = Sha256::new();
+        let mut rows = 0u64;
+        for line in BufReader::new(File::open(&path).map_err(io_error(&path))?).lines() {
+            let line = line.map_err(io_error(&path))?;
+            hasher.update(line.as_bytes());
+            hasher.update(b"\n");
+            rows += 1;
+        }
+        if rows != table.rows || hex::encode(hasher.finalize()) != table.sha256 {
+            return Err(BackupError::Format(format!("{} is damaged or was modified", table.file)));
+        }
+    }
+    Ok(manifest)
+}
+
+/// Every difference between the archive's tables and the target database.
+fn compatibility_problems(manifest: &Manifest, target: &[(String, String, Vec<Column>)]) -> Vec<String> {
+    let target: BTreeMap<String, BTreeSet<&Column>> = target
+        .iter()
+        .map(|(schema, name, columns)| (format!("{}.{}", schema, name), columns.iter().collect()))
+        .collect();
+    let archived: BTreeSet<String> = manifest.tables.iter().map(TableEntry::qualified_name).collect();
+
+    let mut problems = Vec::new();
+    for table in &manifest.tables {
+        let name = table.qualified_name();
+        match target.get(&name) {
+            None => problems.push(format!("table {} does not exist in the database", name)),
+            Some(columns) => {
+                let expected: BTreeSet<&Column> = table.columns.iter().collect();
+                for c in expected.difference(columns) {
+                    problems.push(format!("{}.{} ({}) is not in the database", name, c.name, c.data_type));
+                }
+                for c in columns.difference(&expected) {
+                    problems.push(format!("{}.{} ({}) is not in the archive", name, c.name, c.data_type));
+                }
+            }
+        }
+    }
+    for name in target.keys().filter(|t| !archived.contains(*t)) {
+        problems.push(format!("table {} is not in the archive", name));
+    }
+    if !problems.is_empty() && manifest.schema_version != schema_version() {
+        problems.push(format!(
+            "the archive was written at schema version {}, this build is at {}",
+            manifest.schema_version,
+            schema_version()
+        ));
+    }
+    problems
+}
+
+/// Everything `restore` checks before changing anything: the archive's
+/// integrity and whether it fits the target database.
+pub async fn check(pool: &PgPool, dir: &Path) -> Result<Manifest, BackupError> {
+    let manifest = read_archive(dir)?;
+    let mut conn = pool.acquire().await?;
+    let problems = compatibility_problems(&manifest, &tables(&mut conn).await?);
+    if problems.is_empty() {
+        Ok(manifest)
+    } else {
+        Err(BackupError::Incompatible(problems))
+    }
+}
+
+/// Replaces the data of every table with the archive's, all or nothing.
+pub async fn restore(pool: &PgPool, dir: &Path) -> Result<Manifest, BackupError> {
+    let manifest = read_archive(dir)?;
+
+    let mut conn = pool.acquire().await?;
+    let mut tx = conn.begin().await?;
+    let target = tables(&mut tx).await?;
+    let problems = compatibility_problems(&manifest, &target);
+    if !problems.is_empty() {
+        return Err(BackupError::Incompatible(problems));
+    }
+
+    // Empty everything first so foreign keys only ever see archived rows
+    let all: Vec<String> = target.iter().map(|(schema, name, _)| quoted(schema, name)).collect();
+    sqlx::raw_sql(&format!("TRUNCATE {} RESTART IDENTITY", all.join(", "))).execute(&mut *tx).await?;
+
+    let entries: BTreeMap<String, &TableEntry> = manifest.tables.iter().map(|t| (t.qualified_name(), t)).collect();
+    for (schema, name, columns) in &target {
+        let table = quoted(schema, name);
+        let entry = entries[&format!("{}.{}", schema, name)];
+        let column_list = columns.iter().map(|c| quoted_column(&c.name)).collect::<Vec<_>>().join(", ");
+        let insert = format!(
+            "INSERT INTO {table} ({column_list}) SELECT {column_list} FROM jsonb_populate_recordset(NULL::{table}, $1::JSONB)"
+        );
+
+        let path = dir.join(&entry.file);
+        let mut lines = BufReader::new(File::open(&path).map_err(io_error(&path))?).lines();
+        loop {
+            let batch = lines.by_ref().take(BATCH_SIZE).collect::<Result<Vec<String>, _>>().map_err(io_error(&path))?;
+            if batch.is_empty() {
+                break;
+            }
+            sqlx::query(&insert).bind(format!("[{}]", batch.join(","))).execute(&mut *tx).await?;
+        }
+
+        // Serial columns continue after the highest restored value
+        for column in columns {
+            let sequence: Option<String> = sqlx::query_scalar!(
+                "SELECT pg_get_serial_sequence($1, $2)",
+                format!("{}.{}", quoted_column(schema), quoted_column(name)),
+                column.name
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            if let Some(sequence) = sequence {
+                let col = quoted_column(&column.name);
+                sqlx::query(&format!(
+                    "SELECT setval($1::REGCLASS, COALESCE((SELECT MAX({col}) FROM {table}), 0) + 1, false)"
+                ))
+                .bind(sequence)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+        tracing::info!(table = %entry.qualified_name(), rows = entry.rows, "table restored");
+    }
+    tx.commit().await?;
+    Ok(manifest)
+}
@@ -0,0 +1,256 @@
+//! Maintenance subcommands of the server binary. Without one, it serves the app.
+//!
+//! They call the same functions as the server functions and the REST API, so
+//! validation, webhooks and mails behave exactly as in the web UI.
+
+use clap::Subcommand;
+use sqlx::PgPool;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+use super::{accounts, backup, db, employees, migrate, security};
+use crate::models::auth::{password_policy_errors, ROLE_EMPLOYEE};
+use crate::models::employee::CreateEmployeeRequest;
+
+// Length of generated passwords, well above the policy minimum
+const GENERATED_PASSWORD_LEN: usize = 20;
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Export the public and auth schemas to a backup directory
+    Backup {
+        /// Directory to create (default: ems-backup-<timestamp>)
+        dir: Option<PathBuf>,
+    },
+    /// Replace all data with a backup's, after checking it fits this database
+    Restore {
+        dir: PathBuf,
+        /// Confirm that the current data of every table is replaced
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Manage login accounts
+    User {
+        #[command(subcommand)]
+        command: UserCommand,
+    },
+    /// Import or export employees as CSV
+    Employee {
+        #[command(subcommand)]
+        command: EmployeeCommand,
+    },
+    /// Apply pending schema migrations
+    Migrate,
+    /// Load the sample employees and users into an empty database
+    Seed,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum UserCommand {
+    /// Create a user; prints a generated password unless --password-stdin is given
+    Create {
+        username: String,
+        #[arg(long)]
+        email: Option<String>,
+        /// Role to grant, e.g. admin or ROLE_MANAGER (repeatable, default: employee)
+        #[arg(long = "role", value_name = "ROLE")]
+        roles: Vec<String>,
+        /// Read the password from the first line of stdin
+        #[arg(long)]
+        password_stdin: bool,
+    },
+    /// Replace a user's roles with the given ones
+    SetRole {
+        username: String,
+        #[arg(required = true, value_name = "ROLE")]
+        roles: Vec<String>,
+    },
+    /// Set a new password, unlock the user and end their sessions
+    SetPassword {
+        username: String,
+        /// Read the password from the first line of stdin
+        #[arg(long)]
+        password_stdin: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum EmployeeCommand {
+    /// Create employees from a CSV file with the columns first_name, last_name, email.
+    /// Each one gets the welcome mail and webhook event, like in the UI
+    Import {
+        file: PathBuf,
+        /// Only validate the file
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Write all employees as CSV
+    Export {
+        /// File to write (default: stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+pub async fn run(command: &Command) -> Result<(), anyhow::Error> {
+    let pool = db::connect_db().await?;
+
+    match command {
+        Command::Backup { dir } => {
+            let dir = dir.clone().unwrap_or_else(|| {
+                PathBuf::from(format!("ems-backup-{}", chrono::Utc::now().format("%Y%m%dT%H%M%SZ")))
+            });
+            let manifest = backup::backup(&pool, &dir).await?;
+            let rows: u64 = manifest.tables.iter().map(|t| t.rows).sum();
+            println!("Wrote {} tables ({} rows) to {}", manifest.tables.len(), rows, dir.display());
+        }
+        Command::Restore { dir, yes } => {
+            if !yes {
+                // Dry run: everything is checked, nothing is changed
+                let manifest = backup::check(&pool, dir).await?;
+                println!(
+                    "{} from {} (schema version {}) fits this database.",
+                    dir.display(),
+                    manifest.created_at.format("%Y-%m-%d %H:%M UTC"),
+                    manifest.schema_version
+                );
+                for table in &manifest.tables {
+                    println!("  {:<40} {:>8} rows", table.qualified_name(), table.rows);
+                }
+                anyhow::bail!("Restoring replaces all current data; run again with --yes to do it");
+            }
+            let manifest = backup::restore(&pool, dir).await?;
+            let rows: u64 = manifest.tables.iter().map(|t| t.rows).sum();
+            println!("Restored {} tables ({} rows) from {}", manifest.tables.len(), rows, dir.display());
+        }
+        Command::User { command } => user(&pool, command).await?,
+        Command::Employee { command: EmployeeCommand::Import { file, dry_run } } => {
+            import_employees(&pool, file, *dry_run).await?
+        }
+        Command::Employee { command: EmployeeCommand::Export { output } } => {
+            let count = match output {
+                Some(path) => employees::export_csv(&pool, std::fs::File::create(path)?).await?,
+                None => employees::export_csv(&pool, io::stdout()).await?,
+            };
+            eprintln!("Exported {} employees", count);
+        }
+        Command::Migrate => {
+            let done = migrate::run(&pool).await?;
+            if done.is_empty() {
+                println!("The database is up to date");
+            }
+            for (migration, outcome) in done {
+                let action = match outcome {
+                    migrate::Outcome::Applied => "applied",
+                    migrate::Outcome::Baselined => "already present, recorded",
+                };
+                println!("{}-{}: {}", migration.version, migration.name, action);
+            }
+        }
+        Command::Seed => {
+            if !migrate::seed(&pool).await? {
+                anyhow::bail!("The database already has users or employees; seed only fills an empty one");
+            }
+            println!("Sample data loaded; the sample users' password is \"password\"");
+        }
+    }
+    Ok(())
+}
+
+async fn user(pool: &PgPool, command: &UserCommand) -> Result<(), anyhow::Error> {
+    match command {
+        UserCommand::Create { username, email, roles, password_stdin } => {
+            let roles: Vec<String> = if roles.is_empty() {
+                vec![ROLE_EMPLOYEE.to_string()]
+            } else {
+                roles.iter().map(|r| accounts::role_name(r)).collect()
+            };
+            let (password, generated) = new_password(username, *password_stdin)?;
+
+            accounts::create(pool, username, email.as_deref(), &password, &roles).await?;
+            println!("Created user {} with {}", username.trim(), roles.join(", "));
+            if generated {
+                println!("Password: {}", password);
+            }
+        }
+        UserCommand::SetRole { username, roles } => {
+            let roles: Vec<String> = roles.iter().map(|r| accounts::role_name(r)).collect();
+            accounts::set_roles(pool, username, &roles).await?;
+            println!("{} now has {}", username.trim(), roles.join(", "));
+        }
+        UserCommand::SetPassword { username, password_stdin } => {
+            let (password, generated) = new_password(username, *password_stdin)?;
+            accounts::set_password(pool, username, &password).await?;
+            println!("Password of {} changed, their sessions were ended", username.trim());
+            if generated {
+                println!("Password: {}", password);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The password from stdin, or a generated one that passes the policy.
+/// The flag says whether it was generated and has to be shown.
+fn new_password(username: &str, from_stdin: bool) -> Result<(String, bool), anyhow::Error> {
+    if from_stdin {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+        return Ok((line.trim_end_matches(['\r', '\n']).to_string(), false));
+    }
+    loop {
+        let candidate = security::generate_secret(GENERATED_PASSWORD_LEN);
+        if password_policy_errors(&candidate, username.trim()).is_empty() {
+            return Ok((candidate, true));
+        }
+    }
+}
+
+async fn import_employees(pool: &PgPool, file: &Path, dry_run: bool) -> Result<(), anyhow::Error> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_path(file)?;
+    let headers = reader.headers()?.clone();
+
+    // Everything is checked before the first insert, so a bad file changes nothing
+    let mut rows = Vec::new();
+    let mut problems = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |p| p.line());
+        match record.deserialize::<CreateEmployeeRequest>(Some(&headers)) {
+            Err(e) => problems.push(format!("line {}: {}", line, e)),
+            Ok(req) => {
+                if let Err(errors) = req.validate() {
+                    for error in errors {
+                        problems.push(format!("line {}: {}", line, error.message));
+                    }
+                } else if !seen.insert(req.email.trim().to_lowercase()) {
+                    problems.push(format!("line {}: {} appears more than once", line, req.email));
+                } else {
+                    rows.push(req);
+                }
+            }
+        }
+    }
+    if !problems.is_empty() {
+        anyhow::bail!("{} has errors, nothing was imported:\n  {}", file.display(), problems.join("\n  "));
+    }
+    if dry_run {
+        println!("{} employees would be imported", rows.len());
+        return Ok(());
+    }
+
+    let (mut created, mut existing) = (0, 0);
+    for req in &rows {
+        match employees::create(pool, req).await {
+            Ok(_) => created += 1,
+            Err(employees::EmployeeError::DuplicateEmail) => {
+                existing += 1;
+                eprintln!("skipped {}: already exists", req.email);
+            }
+            Err(e) => anyhow::bail!("{} (after importing {} employees)", e, created),
+        }
+    }
+    println!("Imported {} employees, skipped {} existing", created, existing);
+    Ok(())
+}
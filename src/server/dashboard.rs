@@ -0,0 +1,123 @@
+use dioxus::prelude::*;
+use crate::models::dashboard::{DepartmentHeadcount, HeadcountTrendPoint, WorkforceSummary};
+
+#[cfg(feature = "server")]
+use super::{db, security};
+#[cfg(feature = "server")]
+use crate::models::auth::SCOPE_EMPLOYEES_READ;
+
+// An employee counts towards headcount from their hire date until (excluding) their exit date.
+
+#[server]
+pub async fn get_workforce_summary() -> Result<WorkforceSummary, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_EMPLOYEES_READ).await?;
+
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) FILTER (
+                    WHERE hire_date <= CURRENT_DATE
+                      AND (exit_date IS NULL OR exit_date > CURRENT_DATE)
+                ) AS "headcount!",
+                COUNT(*) FILTER (
+                    WHERE hire_date >= date_trunc('month', CURRENT_DATE)
+                      AND hire_date <  date_trunc('month', CURRENT_DATE) + INTERVAL '1 month'
+                ) AS "new_hires!",
+                COUNT(*) FILTER (
+                    WHERE exit_date >= date_trunc('month', CURRENT_DATE)
+                      AND exit_date <  date_trunc('month', CURRENT_DATE) + INTERVAL '1 month'
+                ) AS "leavers!"
+            FROM employee
+            "#
+        )
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(WorkforceSummary {
+            headcount: row.headcount,
+            new_hires_this_month: row.new_hires,
+            leavers_this_month: row.leavers,
+        })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+#[server]
+pub async fn get_headcount_by_department() -> Result<Vec<DepartmentHeadcount>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_EMPLOYEES_READ).await?;
+
+        let departments = sqlx::query_as!(
+            DepartmentHeadcount,
+            r#"
+            SELECT COALESCE(d.name, 'Unassigned') AS "department!", COUNT(e.id) AS "headcount!"
+            FROM employee e
+            LEFT JOIN departments d ON d.id = e.department_id
+            WHERE e.hire_date <= CURRENT_DATE
+              AND (e.exit_date IS NULL OR e.exit_date > CURRENT_DATE)
+            GROUP BY d.name
+            ORDER BY 2 DESC, 1 ASC
+            "#
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(departments)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Month-end headcount for the last `months` months, oldest first, including the current month.
+#[server]
+pub async fn get_headcount_trend(months: i32) -> Result<Vec<HeadcountTrendPoint>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_EMPLOYEES_READ).await?;
+
+        let months = months.clamp(1, 60);
+
+        let trend = sqlx::query_as!(
+            HeadcountTrendPoint,
+            r#"
+            SELECT to_char(m.month, 'YYYY-MM') AS "month!", COUNT(e.id) AS "headcount!"
+            FROM generate_series(
+                date_trunc('month', CURRENT_DATE) - make_interval(months => $1 - 1),
+                date_trunc('month', CURRENT_DATE),
+                INTERVAL '1 month'
+            ) AS m(month)
+            LEFT JOIN employee e
+                ON e.hire_date < m.month + INTERVAL '1 month'
+               AND (e.exit_date IS NULL OR e.exit_date >= m.month + INTERVAL '1 month')
+            GROUP BY m.month
+            ORDER BY m.month ASC
+            "#,
+            months
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(trend)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
@@ -1,14 +1,33 @@
-use sqlx::postgres::PgPoolOptions;
-use sqlx::PgPool;
-use std::env;
-
-pub async fn connect_db() -> Result<sqlx::PgPool, anyhow::Error> {
-    dotenvy::dotenv().ok();
-    
-    // Reads DATABASE_URL from .env file
-    let database_url = env::var("DATABASE_URL")
-        .expect("DATABASE_URL must be set in .env");
-    
-    let pool = sqlx::PgPool::connect(&database_url).await?;
-    Ok(pool)
-}
\ No newline at end of file
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{ConnectOptions, PgPool};
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use super::config;
+
+/// The shared connection pool, created on first use with the sizes from the config.
+/// `PgPool` is a handle, so the clone is cheap.
+///
+/// Statements are logged through `tracing` at debug level, inside the span of the
+/// request that ran them; slow ones as warnings.
+pub async fn connect_db() -> Result<PgPool, anyhow::Error> {
+    static POOL: OnceCell<PgPool> = OnceCell::const_new();
+
+    let pool = POOL
+        .get_or_try_init(|| async {
+            let db = &config::get().database;
+            let options = PgConnectOptions::from_str(&db.url)?
+                .log_statements(log::LevelFilter::Debug)
+                .log_slow_statements(log::LevelFilter::Warn, Duration::from_millis(db.slow_query_ms));
+
+            PgPoolOptions::new()
+                .max_connections(db.max_connections)
+                .min_connections(db.min_connections)
+                .acquire_timeout(Duration::from_secs(db.acquire_timeout_secs))
+                .connect_with(options)
+                .await
+        })
+        .await?;
+    Ok(pool.clone())
+}
@@ -0,0 +1,206 @@
+//! The employee service functions on a real Postgres with the schema and
+//! `04-employee-data.sql`, i.e. what `get_employees`, `add_employee`,
+//! `update_employee`, `delete_employee` and `restore_employee` run after their
+//! scope check.
+//! See [`crate::server::testing`] for where the database comes from.
+
+use sqlx::PgPool;
+
+use super::*;
+use crate::models::employee::{EmployeeSortField, SortDirection};
+use crate::models::webhook::{
+    EVENT_EMPLOYEE_CREATED, EVENT_EMPLOYEE_DELETED, EVENT_EMPLOYEE_RESTORED, EVENT_EMPLOYEE_UPDATED,
+};
+use crate::server::mail::notify;
+use crate::server::testing::{TestDb, SEEDED_EMPLOYEES};
+use crate::server::webhooks::delivery;
+
+fn request(search: Option<&str>, page: i64, page_size: i64) -> EmployeeRequest {
+    EmployeeRequest {
+        page,
+        page_size,
+        search_term: search.map(str::to_string),
+        sort_by: EmployeeSortField::LastName,
+        sort_dir: SortDirection::Asc,
+    }
+}
+
+/// Webhook events queued so far, oldest first
+async fn queued_events(pool: &PgPool) -> Vec<String> {
+    sqlx::query_scalar("SELECT payload->>'event' FROM public.jobs WHERE kind = $1 ORDER BY id")
+        .bind(delivery::KIND_DISPATCH)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+async fn queued_mails(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM public.jobs WHERE kind = $1")
+        .bind(notify::KIND_SEND)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn employee_count(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM public.employee").fetch_one(pool).await.unwrap()
+}
+
+#[tokio::test]
+async fn pages_cover_every_employee_once() {
+    let Some(db) = TestDb::start().await else { return };
+
+    let mut ids = Vec::new();
+    for (page, expected) in [(1, 7), (2, 7), (3, 6), (4, 0)] {
+        let res = list(&db.pool, &request(None, page, 7)).await.unwrap();
+        assert_eq!(res.employees.len(), expected, "page {}", page);
+        assert_eq!(res.total_count, SEEDED_EMPLOYEES);
+        ids.extend(res.employees.iter().map(|e| e.id));
+    }
+
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len() as i64, SEEDED_EMPLOYEES);
+}
+
+#[tokio::test]
+async fn search_is_case_insensitive_and_sorted() {
+    let Some(db) = TestDb::start().await else { return };
+
+    let res = list(&db.pool, &request(Some(" MA "), 1, 10)).await.unwrap();
+
+    let last_names: Vec<&str> = res.employees.iter().map(|e| e.last_name.as_str()).collect();
+    assert_eq!(last_names, ["Almeida", "Gonzalez", "Martinez"]);
+    assert_eq!(res.total_count, 3);
+
+    // LIKE wildcards in the term are matched literally
+    let res = list(&db.pool, &request(Some("%"), 1, 10)).await.unwrap();
+    assert_eq!(res.total_count, 0);
+}
+
+#[tokio::test]
+async fn add_with_an_existing_email_is_a_conflict() {
+    let Some(db) = TestDb::start().await else { return };
+    let req = CreateEmployeeRequest {
+        first_name: "Lucas".to_string(),
+        last_name: "Duplicate".to_string(),
+        email: " lucas@techthordev.com.br ".to_string(),
+    };
+
+    assert!(matches!(create(&db.pool, &req).await, Err(EmployeeError::DuplicateEmail)));
+
+    // The failed insert rolled back its outbox entries too
+    assert_eq!(employee_count(&db.pool).await, SEEDED_EMPLOYEES);
+    assert!(queued_events(&db.pool).await.is_empty());
+    assert_eq!(queued_mails(&db.pool).await, 0);
+}
+
+#[tokio::test]
+async fn add_update_and_delete_round_trip() {
+    let Some(db) = TestDb::start().await else { return };
+
+    let created = create(
+        &db.pool,
+        &CreateEmployeeRequest {
+            first_name: "Ana".to_string(),
+            last_name: "Silva".to_string(),
+            email: "ana.silva@example.com".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(employee_count(&db.pool).await, SEEDED_EMPLOYEES + 1);
+    assert_eq!(queued_mails(&db.pool).await, 1);
+
+    let req = UpdateEmployeeRequest {
+        id: created.id,
+        first_name: "Ana".to_string(),
+        last_name: "Souza".to_string(),
+        email: "ana.souza@example.com".to_string(),
+    };
+    update(&db.pool, &req).await.unwrap();
+    let found = find(&db.pool, created.id).await.unwrap();
+    assert_eq!((found.last_name.as_str(), found.email.as_str()), ("Souza", "ana.souza@example.com"));
+
+    delete(&db.pool, created.id).await.unwrap();
+    assert!(matches!(find(&db.pool, created.id).await, Err(EmployeeError::NotFound)));
+    assert!(matches!(delete(&db.pool, created.id).await, Err(EmployeeError::NotFound)));
+    assert!(matches!(update(&db.pool, &req).await, Err(EmployeeError::NotFound)));
+
+    assert_eq!(employee_count(&db.pool).await, SEEDED_EMPLOYEES);
+    assert_eq!(
+        queued_events(&db.pool).await,
+        [EVENT_EMPLOYEE_CREATED, EVENT_EMPLOYEE_UPDATED, EVENT_EMPLOYEE_DELETED]
+    );
+}
+
+#[tokio::test]
+async fn update_to_a_colleagues_email_is_a_conflict() {
+    let Some(db) = TestDb::start().await else { return };
+    let lucas = list(&db.pool, &request(Some("lucas@"), 1, 1)).await.unwrap().employees.remove(0);
+
+    let req = UpdateEmployeeRequest {
+        id: lucas.id,
+        first_name: lucas.first_name.clone(),
+        last_name: lucas.last_name.clone(),
+        email: "mateo@techthordev.com.br".to_string(),
+    };
+
+    assert!(matches!(update(&db.pool, &req).await, Err(EmployeeError::DuplicateEmail)));
+    assert_eq!(find(&db.pool, lucas.id).await.unwrap(), lucas);
+}
+
+#[tokio::test]
+async fn restore_undoes_a_delete_with_every_column() {
+    let Some(db) = TestDb::start().await else { return };
+    let lucas = list(&db.pool, &request(Some("lucas@"), 1, 1)).await.unwrap().employees.remove(0);
+    sqlx::query("INSERT INTO public.departments (code, name) VALUES ('ENG', 'Engineering')")
+        .execute(&db.pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        r#"
+        UPDATE public.employee
+        SET department_id = (SELECT id FROM public.departments WHERE code = 'ENG'),
+            hire_date = '2019-03-01', exit_date = '2024-06-30'
+        WHERE id = $1
+        "#,
+    )
+    .bind(lucas.id)
+    .execute(&db.pool)
+    .await
+    .unwrap();
+    let row = |pool: PgPool| async move {
+        sqlx::query_scalar::<_, serde_json::Value>("SELECT to_jsonb(e) FROM public.employee e WHERE id = $1")
+            .bind(lucas.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+    };
+    let before = row(db.pool.clone()).await;
+
+    delete(&db.pool, lucas.id).await.unwrap();
+    assert_eq!(restore(&db.pool, lucas.id).await.unwrap(), lucas);
+
+    assert_eq!(row(db.pool.clone()).await, before);
+    assert!(matches!(restore(&db.pool, lucas.id).await, Err(EmployeeError::NotFound)));
+    assert_eq!(queued_events(&db.pool).await, [EVENT_EMPLOYEE_DELETED, EVENT_EMPLOYEE_RESTORED]);
+    assert_eq!(queued_mails(&db.pool).await, 0);
+}
+
+#[tokio::test]
+async fn restore_is_a_conflict_once_the_email_is_taken_again() {
+    let Some(db) = TestDb::start().await else { return };
+    let lucas = list(&db.pool, &request(Some("lucas@"), 1, 1)).await.unwrap().employees.remove(0);
+
+    delete(&db.pool, lucas.id).await.unwrap();
+    let req = CreateEmployeeRequest {
+        first_name: "Other".to_string(),
+        last_name: "Lucas".to_string(),
+        email: lucas.email.clone(),
+    };
+    create(&db.pool, &req).await.unwrap();
+
+    assert!(matches!(restore(&db.pool, lucas.id).await, Err(EmployeeError::DuplicateEmail)));
+    assert!(matches!(find(&db.pool, lucas.id).await, Err(EmployeeError::NotFound)));
+}
@@ -0,0 +1,166 @@
+//! [`EmployeeRepository`] kept in memory, for exercising the service
+//! functions without a database. Published changes are recorded instead of
+//! queued, so tests can check what would have gone out.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use super::repository::{EmployeeChange, EmployeeFilter, EmployeeRepository, Page, Sort};
+use super::EmployeeError;
+use crate::models::employee::{
+    CreateEmployeeRequest, Employee, EmployeeSortField, SortDirection, UpdateEmployeeRequest,
+};
+
+#[derive(Debug, Default)]
+pub struct InMemoryEmployeeRepository {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    employees: BTreeMap<i64, Employee>,
+    // What delete kept for restore; there is no clock here, so no window
+    deleted: BTreeMap<i64, Employee>,
+    last_id: i64,
+    changes: Vec<EmployeeChange>,
+}
+
+impl InMemoryEmployeeRepository {
+    pub fn with(employees: impl IntoIterator<Item = (&'static str, &'static str, &'static str)>) -> Self {
+        let repo = Self::default();
+        {
+            let mut state = repo.state.lock().unwrap();
+            for (first_name, last_name, email) in employees {
+                state.last_id += 1;
+                let id = state.last_id;
+                let employee = Employee {
+                    id,
+                    first_name: first_name.to_string(),
+                    last_name: last_name.to_string(),
+                    email: email.to_string(),
+                };
+                state.employees.insert(id, employee);
+            }
+        }
+        repo
+    }
+
+    /// Everything published so far, oldest first
+    pub fn changes(&self) -> Vec<EmployeeChange> {
+        self.state.lock().unwrap().changes.clone()
+    }
+}
+
+impl State {
+    // Mirrors the unique constraint on employee.email
+    fn email_taken(&self, email: &str, except: Option<i64>) -> bool {
+        self.employees.values().any(|e| e.email == email && Some(e.id) != except)
+    }
+}
+
+// What the Postgres filter's ILIKE does, minus collation details
+fn matches(filter: &EmployeeFilter, employee: &Employee) -> bool {
+    match &filter.search {
+        None => true,
+        Some(search) => {
+            let search = search.to_lowercase();
+            [&employee.first_name, &employee.last_name, &employee.email]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&search))
+        }
+    }
+}
+
+fn compare(a: &Employee, b: &Employee, by: EmployeeSortField) -> Ordering {
+    match by {
+        EmployeeSortField::Id => a.id.cmp(&b.id),
+        EmployeeSortField::FirstName => a.first_name.cmp(&b.first_name),
+        EmployeeSortField::LastName => a.last_name.cmp(&b.last_name),
+        EmployeeSortField::Email => a.email.cmp(&b.email),
+    }
+}
+
+impl EmployeeRepository for InMemoryEmployeeRepository {
+    async fn list(&self, filter: &EmployeeFilter, sort: Sort, page: Page) -> Result<Vec<Employee>, EmployeeError> {
+        let state = self.state.lock().unwrap();
+        let mut employees: Vec<Employee> = state.employees.values().filter(|e| matches(filter, e)).cloned().collect();
+
+        employees.sort_by(|a, b| {
+            let ordering = compare(a, b, sort.by);
+            let ordering = if sort.dir == SortDirection::Desc { ordering.reverse() } else { ordering };
+            ordering.then(a.id.cmp(&b.id))
+        });
+        Ok(employees.into_iter().skip(page.offset as usize).take(page.limit as usize).collect())
+    }
+
+    async fn count(&self, filter: &EmployeeFilter) -> Result<i64, EmployeeError> {
+        let state = self.state.lock().unwrap();
+        Ok(state.employees.values().filter(|e| matches(filter, e)).count() as i64)
+    }
+
+    async fn find(&self, id: i64) -> Result<Option<Employee>, EmployeeError> {
+        Ok(self.state.lock().unwrap().employees.get(&id).cloned())
+    }
+
+    async fn insert(&self, req: &CreateEmployeeRequest) -> Result<Employee, EmployeeError> {
+        let mut state = self.state.lock().unwrap();
+        if state.email_taken(&req.email, None) {
+            return Err(EmployeeError::DuplicateEmail);
+        }
+
+        state.last_id += 1;
+        let employee = Employee {
+            id: state.last_id,
+            first_name: req.first_name.clone(),
+            last_name: req.last_name.clone(),
+            email: req.email.clone(),
+        };
+        state.employees.insert(employee.id, employee.clone());
+        state.changes.push(EmployeeChange::Created(employee.clone()));
+        Ok(employee)
+    }
+
+    async fn update(&self, req: &UpdateEmployeeRequest) -> Result<Option<Employee>, EmployeeError> {
+        let mut state = self.state.lock().unwrap();
+        if !state.employees.contains_key(&req.id) {
+            return Ok(None);
+        }
+        if state.email_taken(&req.email, Some(req.id)) {
+            return Err(EmployeeError::DuplicateEmail);
+        }
+
+        let employee = Employee {
+            id: req.id,
+            first_name: req.first_name.clone(),
+            last_name: req.last_name.clone(),
+            email: req.email.clone(),
+        };
+        state.employees.insert(employee.id, employee.clone());
+        state.changes.push(EmployeeChange::Updated(employee.clone()));
+        Ok(Some(employee))
+    }
+
+    async fn delete(&self, id: i64) -> Result<Option<Employee>, EmployeeError> {
+        let mut state = self.state.lock().unwrap();
+        let employee = state.employees.remove(&id);
+        if let Some(employee) = &employee {
+            state.deleted.insert(id, employee.clone());
+            state.changes.push(EmployeeChange::Deleted(employee.clone()));
+        }
+        Ok(employee)
+    }
+
+    async fn restore(&self, id: i64) -> Result<Option<Employee>, EmployeeError> {
+        let mut state = self.state.lock().unwrap();
+        let Some(employee) = state.deleted.get(&id).cloned() else { return Ok(None) };
+        if state.email_taken(&employee.email, None) {
+            return Err(EmployeeError::DuplicateEmail);
+        }
+
+        state.deleted.remove(&id);
+        state.employees.insert(id, employee.clone());
+        state.changes.push(EmployeeChange::Restored(employee.clone()));
+        Ok(Some(employee))
+    }
+}
@@ -0,0 +1,259 @@
+//! Employee logic shared by the `#[server]` functions, the REST API and the CLI.
+//!
+//! The functions here validate and normalise requests and leave storage to an
+//! [`EmployeeRepository`]: the Postgres pool in the app, a SQLite file in the
+//! offline desktop build, an in-memory store in unit tests. Callers check
+//! scopes before calling in.
+
+use dioxus::prelude::ServerFnError;
+use std::fmt;
+use std::io::Write;
+
+use crate::models::employee::{
+    CreateEmployeeRequest, Employee, EmployeeRequest, EmployeeResponse, EmployeeSortField, FieldError,
+    SortDirection, UpdateEmployeeRequest,
+};
+
+#[cfg(all(test, feature = "server"))]
+mod integration_tests;
+#[cfg(test)]
+pub mod memory;
+#[cfg(feature = "server")]
+pub mod postgres;
+pub mod repository;
+#[cfg(feature = "offline")]
+pub mod sqlite;
+
+pub use repository::EmployeeRepository;
+use repository::{EmployeeFilter, Page, Sort};
+
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+// How long a deleted employee can be restored, well beyond the UI's undo toast
+pub const RESTORE_WINDOW_MINUTES: i32 = 10;
+
+#[derive(Debug)]
+pub enum EmployeeError {
+    Validation(Vec<FieldError>),
+    DuplicateEmail,
+    NotFound,
+    Database(sqlx::Error),
+}
+
+impl fmt::Display for EmployeeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmployeeError::Validation(errors) => {
+                let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+                write!(f, "Validation failed: {}", messages.join(", "))
+            }
+            EmployeeError::DuplicateEmail => write!(f, "An employee with this email already exists"),
+            EmployeeError::NotFound => write!(f, "Employee not found"),
+            EmployeeError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for EmployeeError {}
+
+impl From<sqlx::Error> for EmployeeError {
+    fn from(e: sqlx::Error) -> Self {
+        match &e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => EmployeeError::DuplicateEmail,
+            _ => EmployeeError::Database(e),
+        }
+    }
+}
+
+impl From<EmployeeError> for ServerFnError {
+    fn from(e: EmployeeError) -> Self {
+        let code = match &e {
+            EmployeeError::Validation(_) => 422,
+            EmployeeError::DuplicateEmail => 409,
+            EmployeeError::NotFound => 404,
+            EmployeeError::Database(_) => 500,
+        };
+        ServerFnError::ServerError { message: e.to_string(), code, details: None }
+    }
+}
+
+pub async fn list(repo: &impl EmployeeRepository, req: &EmployeeRequest) -> Result<EmployeeResponse, EmployeeError> {
+    let page_size = req.page_size.clamp(1, MAX_PAGE_SIZE);
+    let page = Page { limit: page_size, offset: (req.page.max(1) - 1) * page_size };
+    let sort = Sort { by: req.sort_by, dir: req.sort_dir };
+    let filter = EmployeeFilter::from_request(req);
+
+    let employees = repo.list(&filter, sort, page).await?;
+    let total_count = repo.count(&filter).await?;
+    Ok(EmployeeResponse { employees, total_count })
+}
+
+pub async fn find(repo: &impl EmployeeRepository, id: i64) -> Result<Employee, EmployeeError> {
+    repo.find(id).await?.ok_or(EmployeeError::NotFound)
+}
+
+pub async fn create(repo: &impl EmployeeRepository, req: &CreateEmployeeRequest) -> Result<Employee, EmployeeError> {
+    req.validate().map_err(EmployeeError::Validation)?;
+    let req = CreateEmployeeRequest {
+        first_name: req.first_name.trim().to_string(),
+        last_name: req.last_name.trim().to_string(),
+        email: req.email.trim().to_string(),
+    };
+    repo.insert(&req).await
+}
+
+pub async fn update(repo: &impl EmployeeRepository, req: &UpdateEmployeeRequest) -> Result<Employee, EmployeeError> {
+    req.validate().map_err(EmployeeError::Validation)?;
+    let req = UpdateEmployeeRequest {
+        id: req.id,
+        first_name: req.first_name.trim().to_string(),
+        last_name: req.last_name.trim().to_string(),
+        email: req.email.trim().to_string(),
+    };
+    repo.update(&req).await?.ok_or(EmployeeError::NotFound)
+}
+
+pub async fn delete(repo: &impl EmployeeRepository, id: i64) -> Result<(), EmployeeError> {
+    repo.delete(id).await?.ok_or(EmployeeError::NotFound)?;
+    Ok(())
+}
+
+/// Undoes a recent delete. The email may have been taken in the meantime,
+/// which is `EmployeeError::DuplicateEmail` as for any write.
+pub async fn restore(repo: &impl EmployeeRepository, id: i64) -> Result<Employee, EmployeeError> {
+    repo.restore(id).await?.ok_or(EmployeeError::NotFound)
+}
+
+/// Writes every employee as CSV in id order and returns how many there were.
+/// The columns are the ones `employee import` reads, plus the id.
+pub async fn export_csv(repo: &impl EmployeeRepository, out: impl Write) -> Result<usize, anyhow::Error> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(["id", "first_name", "last_name", "email"])?;
+
+    let mut req = EmployeeRequest {
+        page: 1,
+        page_size: MAX_PAGE_SIZE,
+        search_term: None,
+        sort_by: EmployeeSortField::Id,
+        sort_dir: SortDirection::Asc,
+    };
+    let mut count = 0;
+    loop {
+        let page = list(repo, &req).await?;
+        for e in &page.employees {
+            writer.write_record([e.id.to_string(), e.first_name.clone(), e.last_name.clone(), e.email.clone()])?;
+        }
+        count += page.employees.len();
+        if page.employees.len() < req.page_size as usize {
+            break;
+        }
+        req.page += 1;
+    }
+    writer.flush()?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::memory::InMemoryEmployeeRepository;
+    use super::repository::EmployeeChange;
+    use super::*;
+    use crate::models::employee::{EmployeeSortField, SortDirection};
+
+    fn sample() -> InMemoryEmployeeRepository {
+        InMemoryEmployeeRepository::with([
+            ("Lucas", "Martinez", "lucas@example.com"),
+            ("Sofia", "Ramirez", "sofia@example.com"),
+            ("Mateo", "Gonzalez", "mateo@example.com"),
+        ])
+    }
+
+    fn request(search: Option<&str>, page: i64, page_size: i64) -> EmployeeRequest {
+        EmployeeRequest {
+            page,
+            page_size,
+            search_term: search.map(str::to_string),
+            sort_by: EmployeeSortField::LastName,
+            sort_dir: SortDirection::Asc,
+        }
+    }
+
+    #[tokio::test]
+    async fn list_counts_every_match_but_returns_one_page() {
+        let repo = sample();
+
+        let res = list(&repo, &request(Some("  A  "), 1, 2)).await.unwrap();
+
+        let last_names: Vec<&str> = res.employees.iter().map(|e| e.last_name.as_str()).collect();
+        assert_eq!(last_names, ["Gonzalez", "Martinez"]);
+        assert_eq!(res.total_count, 3);
+    }
+
+    #[tokio::test]
+    async fn create_trims_and_publishes() {
+        let repo = sample();
+        let req = CreateEmployeeRequest {
+            first_name: " Ana ".to_string(),
+            last_name: "Silva".to_string(),
+            email: " ana@example.com ".to_string(),
+        };
+
+        let employee = create(&repo, &req).await.unwrap();
+
+        assert_eq!((employee.first_name.as_str(), employee.email.as_str()), ("Ana", "ana@example.com"));
+        assert_eq!(repo.changes(), vec![EmployeeChange::Created(employee)]);
+    }
+
+    #[tokio::test]
+    async fn invalid_requests_never_reach_the_repository() {
+        let repo = sample();
+        let req = CreateEmployeeRequest {
+            first_name: String::new(),
+            last_name: "Silva".to_string(),
+            email: "not-an-email".to_string(),
+        };
+
+        let Err(EmployeeError::Validation(errors)) = create(&repo, &req).await else {
+            panic!("expected a validation error");
+        };
+        assert!(errors.iter().any(|e| e.field == "first_name"));
+        assert!(repo.changes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn duplicate_email_is_a_conflict() {
+        let repo = sample();
+        let req = UpdateEmployeeRequest {
+            id: 1,
+            first_name: "Lucas".to_string(),
+            last_name: "Martinez".to_string(),
+            email: "sofia@example.com".to_string(),
+        };
+
+        assert!(matches!(update(&repo, &req).await, Err(EmployeeError::DuplicateEmail)));
+        assert!(repo.changes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn missing_employees_are_not_found() {
+        let repo = sample();
+
+        assert!(matches!(find(&repo, 42).await, Err(EmployeeError::NotFound)));
+        assert!(matches!(delete(&repo, 42).await, Err(EmployeeError::NotFound)));
+        assert!(matches!(restore(&repo, 42).await, Err(EmployeeError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn restore_brings_back_the_deleted_employee_once() {
+        let repo = sample();
+        let sofia = find(&repo, 2).await.unwrap();
+
+        delete(&repo, 2).await.unwrap();
+        let restored = restore(&repo, 2).await.unwrap();
+
+        assert_eq!(restored, sofia);
+        assert_eq!(find(&repo, 2).await.unwrap(), sofia);
+        assert!(matches!(restore(&repo, 2).await, Err(EmployeeError::NotFound)));
+        assert_eq!(repo.changes(), vec![EmployeeChange::Deleted(sofia.clone()), EmployeeChange::Restored(sofia)]);
+    }
+}
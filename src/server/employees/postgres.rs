@@ -0,0 +1,201 @@
+//! [`EmployeeRepository`] on the shared Postgres pool.
+//!
+//! Every write queues its webhook event (and for new employees the welcome
+//! mail) in the same transaction, so they are published if and only if the
+//! change committed.
+
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
+use std::collections::BTreeMap;
+
+use super::repository::{EmployeeChange, EmployeeFilter, EmployeeRepository, Page, Sort};
+use super::{EmployeeError, RESTORE_WINDOW_MINUTES};
+use crate::models::email::TEMPLATE_EMPLOYEE_WELCOME;
+use crate::models::employee::{CreateEmployeeRequest, Employee, EmployeeSortField, UpdateEmployeeRequest};
+use crate::models::webhook::{
+    EVENT_EMPLOYEE_CREATED, EVENT_EMPLOYEE_DELETED, EVENT_EMPLOYEE_RESTORED, EVENT_EMPLOYEE_UPDATED,
+};
+use crate::server::mail::notify;
+use crate::server::webhooks::delivery;
+
+/// Appends the WHERE clause for `filter`; shared by the list and count queries.
+fn push_filter(query: &mut QueryBuilder<'_, Postgres>, filter: &EmployeeFilter) {
+    if let Some(pattern) = filter.like_pattern() {
+        query
+            .push(" WHERE (first_name ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR last_name ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR email ILIKE ")
+            .push_bind(pattern)
+            .push(")");
+    }
+}
+
+async fn publish(conn: &mut PgConnection, change: &EmployeeChange) -> Result<(), sqlx::Error> {
+    match change {
+        EmployeeChange::Created(employee) => {
+            delivery::enqueue_event(&mut *conn, EVENT_EMPLOYEE_CREATED, employee).await?;
+            notify::enqueue(
+                &mut *conn,
+                TEMPLATE_EMPLOYEE_WELCOME,
+                &employee.email,
+                BTreeMap::from([
+                    ("first_name".to_string(), employee.first_name.clone()),
+                    ("last_name".to_string(), employee.last_name.clone()),
+                    ("email".to_string(), employee.email.clone()),
+                ]),
+            )
+            .await
+        }
+        EmployeeChange::Updated(employee) => delivery::enqueue_event(conn, EVENT_EMPLOYEE_UPDATED, employee).await,
+        EmployeeChange::Deleted(employee) => delivery::enqueue_event(conn, EVENT_EMPLOYEE_DELETED, employee).await,
+        // Back, not new: no welcome mail
+        EmployeeChange::Restored(employee) => delivery::enqueue_event(conn, EVENT_EMPLOYEE_RESTORED, employee).await,
+    }
+}
+
+impl EmployeeRepository for PgPool {
+    async fn list(&self, filter: &EmployeeFilter, sort: Sort, page: Page) -> Result<Vec<Employee>, EmployeeError> {
+        let mut query = QueryBuilder::new("SELECT id, first_name, last_name, email FROM employee");
+        push_filter(&mut query, filter);
+
+        // Column and direction come from enums, never from the caller's text
+        query.push(format!(" ORDER BY {} {}", sort.by.as_str(), sort.dir.as_str()));
+        if sort.by != EmployeeSortField::Id {
+            query.push(", id ASC");
+        }
+        query.push(" LIMIT ").push_bind(page.limit).push(" OFFSET ").push_bind(page.offset);
+
+        Ok(query.build_query_as::<Employee>().fetch_all(self).await?)
+    }
+
+    async fn count(&self, filter: &EmployeeFilter) -> Result<i64, EmployeeError> {
+        let mut query = QueryBuilder::new("SELECT COUNT(*) FROM employee");
+        push_filter(&mut query, filter);
+
+        Ok(query.build_query_scalar::<i64>().fetch_one(self).await?)
+    }
+
+    async fn find(&self, id: i64) -> Result<Option<Employee>, EmployeeError> {
+        Ok(sqlx::query_as!(
+            Employee,
+            "SELECT id, first_name, last_name, email FROM employee WHERE id = $1",
+            id
+        )
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn insert(&self, req: &CreateEmployeeRequest) -> Result<Employee, EmployeeError> {
+        let mut tx = self.begin().await?;
+
+        let employee = sqlx::query_as!(
+            Employee,
+            r#"
+            INSERT INTO employee (first_name, last_name, email)
+            VALUES ($1, $2, $3)
+            RETURNING id, first_name, last_name, email
+            "#,
+            req.first_name,
+            req.last_name,
+            req.email
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        publish(&mut tx, &EmployeeChange::Created(employee.clone())).await?;
+        tx.commit().await?;
+        Ok(employee)
+    }
+
+    async fn update(&self, req: &UpdateEmployeeRequest) -> Result<Option<Employee>, EmployeeError> {
+        let mut tx = self.begin().await?;
+
+        let employee = sqlx::query_as!(
+            Employee,
+            r#"
+            UPDATE employee SET first_name = $1, last_name = $2, email = $3
+            WHERE id = $4
+            RETURNING id, first_name, last_name, email
+            "#,
+            req.first_name,
+            req.last_name,
+            req.email,
+            req.id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(employee) = &employee {
+            publish(&mut tx, &EmployeeChange::Updated(employee.clone())).await?;
+        }
+        tx.commit().await?;
+        Ok(employee)
+    }
+
+    async fn delete(&self, id: i64) -> Result<Option<Employee>, EmployeeError> {
+        let mut tx = self.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM deleted_employees WHERE deleted_at < now() - make_interval(mins => $1)",
+            RESTORE_WINDOW_MINUTES
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // The whole row goes to deleted_employees for restore, the returned
+        // record into the webhook payload
+        let employee = sqlx::query_as!(
+            Employee,
+            r#"
+            WITH gone AS (
+                DELETE FROM employee WHERE id = $1 RETURNING *
+            ), kept AS (
+                INSERT INTO deleted_employees (id, data)
+                SELECT id, to_jsonb(gone) FROM gone
+                ON CONFLICT (id) DO UPDATE SET data = EXCLUDED.data, deleted_at = now()
+            )
+            SELECT id AS "id!", first_name AS "first_name!", last_name AS "last_name!", email AS "email!"
+            FROM gone
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(employee) = &employee {
+            publish(&mut tx, &EmployeeChange::Deleted(employee.clone())).await?;
+        }
+        tx.commit().await?;
+        Ok(employee)
+    }
+
+    async fn restore(&self, id: i64) -> Result<Option<Employee>, EmployeeError> {
+        let mut tx = self.begin().await?;
+
+        // Every column as it was, including the id; a taken email rolls it all back
+        let employee = sqlx::query_as!(
+            Employee,
+            r#"
+            WITH kept AS (
+                DELETE FROM deleted_employees
+                WHERE id = $1 AND deleted_at >= now() - make_interval(mins => $2)
+                RETURNING data
+            )
+            INSERT INTO employee
+            SELECT (jsonb_populate_record(NULL::employee, data)).* FROM kept
+            RETURNING id, first_name, last_name, email
+            "#,
+            id,
+            RESTORE_WINDOW_MINUTES
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(employee) = &employee {
+            publish(&mut tx, &EmployeeChange::Restored(employee.clone())).await?;
+        }
+        tx.commit().await?;
+        Ok(employee)
+    }
+}
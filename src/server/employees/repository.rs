@@ -0,0 +1,90 @@
+//! Storage interface for employees.
+//!
+//! The service functions in [`super`] validate and then call a repository, so
+//! they run the same against Postgres, the offline desktop build's SQLite file
+//! and the in-memory store used in unit tests.
+
+use std::future::Future;
+
+use super::EmployeeError;
+use crate::models::employee::{
+    CreateEmployeeRequest, Employee, EmployeeRequest, EmployeeSortField, SortDirection, UpdateEmployeeRequest,
+};
+
+/// Which employees a list or count covers. Both queries are built from the
+/// same filter so the total always matches the rows.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EmployeeFilter {
+    // Case-insensitive substring of first name, last name or email
+    pub search: Option<String>,
+}
+
+impl EmployeeFilter {
+    pub fn from_request(req: &EmployeeRequest) -> Self {
+        let search = req.search_term.as_deref().map(str::trim).filter(|s| !s.is_empty());
+        Self { search: search.map(str::to_string) }
+    }
+
+    /// The search as a LIKE pattern escaped with backslashes, so `%` and `_` in it
+    /// match literally, like the in-memory filter does
+    pub fn like_pattern(&self) -> Option<String> {
+        let search = self.search.as_ref()?;
+        let escaped = search.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        Some(format!("%{}%", escaped))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sort {
+    pub by: EmployeeSortField,
+    pub dir: SortDirection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// A committed write, published to webhooks (and for new employees as a
+/// welcome mail) atomically with the write itself.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmployeeChange {
+    Created(Employee),
+    Updated(Employee),
+    Deleted(Employee),
+    // A delete undone, with the same id and every column as before
+    Restored(Employee),
+}
+
+/// Writes receive validated, trimmed requests and publish their
+/// [`EmployeeChange`] where there is anyone to tell (not in the offline SQLite
+/// store); a unique email conflict is `EmployeeError::DuplicateEmail`.
+pub trait EmployeeRepository: Send + Sync {
+    fn list(
+        &self,
+        filter: &EmployeeFilter,
+        sort: Sort,
+        page: Page,
+    ) -> impl Future<Output = Result<Vec<Employee>, EmployeeError>> + Send;
+
+    fn count(&self, filter: &EmployeeFilter) -> impl Future<Output = Result<i64, EmployeeError>> + Send;
+
+    fn find(&self, id: i64) -> impl Future<Output = Result<Option<Employee>, EmployeeError>> + Send;
+
+    fn insert(&self, req: &CreateEmployeeRequest) -> impl Future<Output = Result<Employee, EmployeeError>> + Send;
+
+    /// `None` if there is no employee with `req.id`
+    fn update(
+        &self,
+        req: &UpdateEmployeeRequest,
+    ) -> impl Future<Output = Result<Option<Employee>, EmployeeError>> + Send;
+
+    /// The deleted employee, `None` if there was none. The whole row is kept
+    /// for [`super::RESTORE_WINDOW_MINUTES`] so the delete can be undone.
+    fn delete(&self, id: i64) -> impl Future<Output = Result<Option<Employee>, EmployeeError>> + Send;
+
+    /// Puts back the row a recent delete of `id` kept, `None` if there is none
+    /// (never deleted, restored already or deleted too long ago).
+    fn restore(&self, id: i64) -> impl Future<Output = Result<Option<Employee>, EmployeeError>> + Send;
+}
@@ -0,0 +1,169 @@
+//! [`EmployeeRepository`] on a local SQLite file, for the desktop build's
+//! offline mode (`--features offline`).
+//!
+//! The schema mirrors `public.employee`. Nobody listens for changes to a local
+//! database, so writes publish no webhooks or mails. Queries are checked at
+//! run time: the `query!` macros verify against the Postgres `DATABASE_URL`.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
+use std::path::PathBuf;
+use tokio::sync::OnceCell;
+
+use super::repository::{EmployeeFilter, EmployeeRepository, Page, Sort};
+use super::{EmployeeError, RESTORE_WINDOW_MINUTES};
+use crate::models::employee::{CreateEmployeeRequest, Employee, EmployeeSortField, UpdateEmployeeRequest};
+
+const SCHEMA: &str = include_str!("../../../database/sqlite/employee-schema.sql");
+
+/// File used when `EMS_LOCAL_DB` is not set, relative to the working directory
+pub const DEFAULT_PATH: &str = "ems-local.db";
+
+pub fn path() -> PathBuf {
+    std::env::var_os("EMS_LOCAL_DB").map(PathBuf::from).unwrap_or_else(|| PathBuf::from(DEFAULT_PATH))
+}
+
+/// The local database, created with its schema on first use.
+pub async fn connect_local() -> Result<SqlitePool, sqlx::Error> {
+    static POOL: OnceCell<SqlitePool> = OnceCell::const_new();
+
+    let pool = POOL
+        .get_or_try_init(|| async {
+            let options = SqliteConnectOptions::new().filename(path()).create_if_missing(true);
+            let pool = SqlitePoolOptions::new().max_connections(4).connect_with(options).await?;
+            sqlx::raw_sql(SCHEMA).execute(&pool).await?;
+            Ok::<_, sqlx::Error>(pool)
+        })
+        .await?;
+    Ok(pool.clone())
+}
+
+/// Appends the WHERE clause for `filter`; shared by the list and count queries.
+/// SQLite's LIKE ignores case for ASCII, which covers what ILIKE does in Postgres.
+fn push_filter(query: &mut QueryBuilder<'_, Sqlite>, filter: &EmployeeFilter) {
+    if let Some(pattern) = filter.like_pattern() {
+        query
+            .push(" WHERE (first_name LIKE ")
+            .push_bind(pattern.clone())
+            .push(" ESCAPE '\\' OR last_name LIKE ")
+            .push_bind(pattern.clone())
+            .push(" ESCAPE '\\' OR email LIKE ")
+            .push_bind(pattern)
+            .push(" ESCAPE '\\')");
+    }
+}
+
+impl EmployeeRepository for SqlitePool {
+    async fn list(&self, filter: &EmployeeFilter, sort: Sort, page: Page) -> Result<Vec<Employee>, EmployeeError> {
+        let mut query = QueryBuilder::new("SELECT id, first_name, last_name, email FROM employee");
+        push_filter(&mut query, filter);
+
+        // Column and direction come from enums, never from the caller's text
+        query.push(format!(" ORDER BY {} {}", sort.by.as_str(), sort.dir.as_str()));
+        if sort.by != EmployeeSortField::Id {
+            query.push(", id ASC");
+        }
+        query.push(" LIMIT ").push_bind(page.limit).push(" OFFSET ").push_bind(page.offset);
+
+        Ok(query.build_query_as::<Employee>().fetch_all(self).await?)
+    }
+
+    async fn count(&self, filter: &EmployeeFilter) -> Result<i64, EmployeeError> {
+        let mut query = QueryBuilder::new("SELECT COUNT(*) FROM employee");
+        push_filter(&mut query, filter);
+
+        Ok(query.build_query_scalar::<i64>().fetch_one(self).await?)
+    }
+
+    async fn find(&self, id: i64) -> Result<Option<Employee>, EmployeeError> {
+        Ok(sqlx::query_as("SELECT id, first_name, last_name, email FROM employee WHERE id = ?")
+            .bind(id)
+            .fetch_optional(self)
+            .await?)
+    }
+
+    async fn insert(&self, req: &CreateEmployeeRequest) -> Result<Employee, EmployeeError> {
+        Ok(sqlx::query_as(
+            r#"
+            INSERT INTO employee (first_name, last_name, email)
+            VALUES (?, ?, ?)
+            RETURNING id, first_name, last_name, email
+            "#,
+        )
+        .bind(&req.first_name)
+        .bind(&req.last_name)
+        .bind(&req.email)
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn update(&self, req: &UpdateEmployeeRequest) -> Result<Option<Employee>, EmployeeError> {
+        Ok(sqlx::query_as(
+            r#"
+            UPDATE employee SET first_name = ?, last_name = ?, email = ?
+            WHERE id = ?
+            RETURNING id, first_name, last_name, email
+            "#,
+        )
+        .bind(&req.first_name)
+        .bind(&req.last_name)
+        .bind(&req.email)
+        .bind(req.id)
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn delete(&self, id: i64) -> Result<Option<Employee>, EmployeeError> {
+        let mut tx = self.begin().await?;
+
+        sqlx::query("DELETE FROM deleted_employee WHERE deleted_at < datetime('now', ?)")
+            .bind(format!("-{} minutes", RESTORE_WINDOW_MINUTES))
+            .execute(&mut *tx)
+            .await?;
+        let employee: Option<Employee> =
+            sqlx::query_as("DELETE FROM employee WHERE id = ? RETURNING id, first_name, last_name, email")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?;
+        if let Some(e) = &employee {
+            sqlx::query("INSERT OR REPLACE INTO deleted_employee (id, first_name, last_name, email) VALUES (?, ?, ?, ?)")
+                .bind(e.id)
+                .bind(&e.first_name)
+                .bind(&e.last_name)
+                .bind(&e.email)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(employee)
+    }
+
+    async fn restore(&self, id: i64) -> Result<Option<Employee>, EmployeeError> {
+        let mut tx = self.begin().await?;
+
+        let employee: Option<Employee> = sqlx::query_as(
+            r#"
+            DELETE FROM deleted_employee
+            WHERE id = ? AND deleted_at >= datetime('now', ?)
+            RETURNING id, first_name, last_name, email
+            "#,
+        )
+        .bind(id)
+        .bind(format!("-{} minutes", RESTORE_WINDOW_MINUTES))
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some(e) = &employee {
+            sqlx::query("INSERT INTO employee (id, first_name, last_name, email) VALUES (?, ?, ?, ?)")
+                .bind(e.id)
+                .bind(&e.first_name)
+                .bind(&e.last_name)
+                .bind(&e.email)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(employee)
+    }
+}
@@ -0,0 +1,71 @@
+//! Probes for orchestrators and the Prometheus scrape endpoint.
+//!
+//! * `/healthz`: the process is up and serving requests
+//! * `/readyz`: the database is reachable and has the schema this build expects
+//! * `/metrics`: Prometheus text format, see [`super::metrics`]
+
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::time::Duration;
+
+use super::{metrics, migrate};
+
+const DB_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn router(pool: PgPool, with_metrics: bool) -> Router {
+    let router = Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz));
+    let router = if with_metrics { router.route("/metrics", get(scrape)) } else { router };
+    router.with_state(pool)
+}
+
+#[derive(Debug, Serialize)]
+struct Readiness {
+    status: &'static str,
+    database: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    missing_tables: Vec<String>,
+}
+
+async fn healthz() -> &'static str {
+    "ok"
+}
+
+async fn readyz(State(pool): State<PgPool>) -> impl IntoResponse {
+    let check = tokio::time::timeout(DB_CHECK_TIMEOUT, async {
+        sqlx::query_scalar!(
+            r#"SELECT t AS "t!" FROM unnest($1::TEXT[]) AS t WHERE to_regclass(t) IS NULL"#,
+            &migrate::marker_tables()
+        )
+        .fetch_all(&pool)
+        .await
+    })
+    .await;
+
+    let (database, missing_tables) = match check {
+        Ok(Ok(missing)) => ("ok".to_string(), missing),
+        Ok(Err(e)) => (format!("error: {}", e), Vec::new()),
+        Err(_) => ("timeout".to_string(), Vec::new()),
+    };
+    let ready = database == "ok" && missing_tables.is_empty();
+    let status = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+
+    (
+        status,
+        Json(Readiness { status: if ready { "ready" } else { "not ready" }, database, missing_tables }),
+    )
+}
+
+async fn scrape(State(pool): State<PgPool>) -> impl IntoResponse {
+    metrics::record_gauges(&pool).await;
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics::handle().render(),
+    )
+}
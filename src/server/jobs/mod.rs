@@ -0,0 +1,142 @@
+use dioxus::prelude::*;
+use crate::models::job::{JobStats, JobSummary};
+
+#[cfg(feature = "server")]
+pub mod queue;
+#[cfg(feature = "server")]
+pub mod runner;
+
+#[cfg(feature = "server")]
+use super::{db, security};
+#[cfg(feature = "server")]
+use crate::models::{auth::SCOPE_ADMIN, job::JOB_DEAD};
+
+#[cfg(feature = "server")]
+const LIST_LIMIT: i64 = 200;
+
+/// Most recently touched jobs, optionally filtered by status.
+#[server]
+pub async fn list_jobs(status: Option<String>) -> Result<Vec<JobSummary>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query_as!(
+            JobSummary,
+            r#"
+            SELECT id, kind, status, attempts, max_attempts, run_at, last_error,
+                   payload::TEXT AS "payload!", created_at, updated_at
+            FROM jobs
+            WHERE $1::TEXT IS NULL OR status = $1
+            ORDER BY updated_at DESC, id DESC
+            LIMIT $2
+            "#,
+            status,
+            LIST_LIMIT
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+#[server]
+pub async fn job_stats() -> Result<JobStats, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query_as!(
+            JobStats,
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE status = 'queued')    AS "queued!",
+                COUNT(*) FILTER (WHERE status = 'running')   AS "running!",
+                COUNT(*) FILTER (WHERE status = 'succeeded') AS "succeeded!",
+                COUNT(*) FILTER (WHERE status = 'dead')      AS "dead!"
+            FROM jobs
+            "#
+        )
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Puts a dead job back in the queue with a fresh set of attempts.
+#[server]
+pub async fn retry_job(id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE jobs
+            SET status = 'queued', attempts = 0, run_at = now(), updated_at = now()
+            WHERE id = $1 AND status = $2
+            "#,
+            id,
+            JOB_DEAD
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(ServerFnError::ServerError {
+                message: "Only dead jobs can be retried".to_string(),
+                code: 409,
+                details: None,
+            });
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Removes a dead job for good.
+#[server]
+pub async fn delete_job(id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        let result = sqlx::query!("DELETE FROM jobs WHERE id = $1 AND status = $2", id, JOB_DEAD)
+            .execute(&pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(ServerFnError::ServerError {
+                message: "Only dead jobs can be deleted".to_string(),
+                code: 409,
+                details: None,
+            });
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
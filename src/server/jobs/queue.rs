@@ -0,0 +1,90 @@
+//! Enqueueing jobs. Takes any executor so jobs can be written inside the
+//! caller's transaction (transactional outbox): the job exists if and only
+//! if the change that caused it was committed.
+
+use serde::Serialize;
+use sqlx::PgExecutor;
+use std::fmt;
+
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+
+// Retry after 30s, 1m, 2m, 4m, ... capped at 6h
+const BACKOFF_BASE_SECS: f64 = 30.0;
+const BACKOFF_MAX_SECS: f64 = 6.0 * 3600.0;
+
+/// A claimed job as handed to a handler.
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: i64,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    /// Attempt number of the current run, starting at 1
+    pub attempts: i32,
+    pub max_attempts: i32,
+}
+
+impl Job {
+    pub fn is_last_attempt(&self) -> bool {
+        self.attempts >= self.max_attempts
+    }
+
+    pub fn payload<T: serde::de::DeserializeOwned>(&self) -> Result<T, JobError> {
+        serde_json::from_value(self.payload.clone())
+            .map_err(|e| JobError::Fatal(format!("invalid payload for {}: {}", self.kind, e)))
+    }
+}
+
+/// `Retry` reschedules the job with backoff, `Fatal` dead-letters it right away.
+#[derive(Debug)]
+pub enum JobError {
+    Retry(String),
+    Fatal(String),
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobError::Retry(e) | JobError::Fatal(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for JobError {
+    fn from(e: sqlx::Error) -> Self {
+        JobError::Retry(format!("Database error: {}", e))
+    }
+}
+
+pub fn backoff_secs(attempts: i32) -> f64 {
+    (BACKOFF_BASE_SECS * 2f64.powi(attempts.max(1) - 1)).min(BACKOFF_MAX_SECS)
+}
+
+pub async fn enqueue<'e, E, T>(executor: E, kind: &str, payload: &T) -> Result<i64, sqlx::Error>
+where
+    E: PgExecutor<'e>,
+    T: Serialize,
+{
+    enqueue_with_attempts(executor, kind, payload, DEFAULT_MAX_ATTEMPTS).await
+}
+
+pub async fn enqueue_with_attempts<'e, E, T>(
+    executor: E,
+    kind: &str,
+    payload: &T,
+    max_attempts: i32,
+) -> Result<i64, sqlx::Error>
+where
+    E: PgExecutor<'e>,
+    T: Serialize,
+{
+    let payload = serde_json::to_value(payload).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+    sqlx::query_scalar!(
+        "INSERT INTO jobs (kind, payload, max_attempts) VALUES ($1, $2, $3) RETURNING id",
+        kind,
+        payload,
+        max_attempts
+    )
+    .fetch_one(executor)
+    .await
+}
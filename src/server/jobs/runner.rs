@@ -0,0 +1,175 @@
+//! Worker pool that runs queued jobs next to the Dioxus server, plus periodic
+//! housekeeping of old rows.
+
+use sqlx::PgPool;
+use std::time::Duration;
+use tracing::Instrument;
+
+use super::queue::{backoff_secs, Job, JobError};
+use crate::server::ldap::sync as ldap_sync;
+use crate::server::mail::notify;
+use crate::server::password;
+use crate::server::webhooks::delivery;
+
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(60);
+
+// A job still `running` after this long is assumed to belong to a dead worker
+const STALE_LOCK_SECS: f64 = 600.0;
+
+// Succeeded jobs are purged after a week, dead ones are kept until an admin acts
+const SUCCEEDED_RETENTION_DAYS: i32 = 7;
+
+// Login attempts only matter for throttling, older ones are kept for auditing a while
+const LOGIN_ATTEMPT_RETENTION_DAYS: i32 = 90;
+
+/// Routes a job to its handler by `kind`.
+async fn handle(pool: &PgPool, job: &Job) -> Result<(), JobError> {
+    match job.kind.as_str() {
+        delivery::KIND_DISPATCH => delivery::dispatch(pool, job).await,
+        delivery::KIND_DELIVER => delivery::deliver(pool, job).await,
+        notify::KIND_SEND => notify::send(pool, job).await,
+        password::KIND_SEND_RESET => password::send_reset(pool, job).await,
+        ldap_sync::KIND_SYNC => ldap_sync::run(pool, job).await,
+        other => Err(JobError::Fatal(format!("unknown job kind '{}'", other))),
+    }
+}
+
+/// Spawns `workers` worker tasks.
+pub fn start(pool: PgPool, workers: usize) {
+    for n in 0..workers.max(1) {
+        let pool = pool.clone();
+        tokio::spawn(async move { run_worker(pool, format!("worker-{}", n)).await });
+    }
+}
+
+/// Spawns the maintenance task. Runs on every node, workers or not: its
+/// updates and deletes are idempotent, so several nodes may run it at once.
+pub fn start_maintenance(pool: PgPool) {
+    tokio::spawn(run_maintenance(pool));
+}
+
+async fn run_worker(pool: PgPool, worker_id: String) {
+    loop {
+        match claim(&pool, &worker_id).await {
+            Ok(Some(job)) => {
+                let span = tracing::info_span!("job", job_id = job.id, kind = %job.kind, attempt = job.attempts, worker = %worker_id);
+                async {
+                    let result = handle(&pool, &job).await;
+                    if let Err(e) = finish(&pool, &job, result).await {
+                        tracing::error!(error = %e, "could not record job result");
+                    }
+                }
+                .instrument(span)
+                .await
+            }
+            Ok(None) => tokio::time::sleep(IDLE_POLL_INTERVAL).await,
+            Err(e) => {
+                tracing::error!(worker = %worker_id, error = %e, "could not claim job");
+                tokio::time::sleep(IDLE_POLL_INTERVAL).await;
+            }
+        }
+    }
+}
+
+async fn claim(pool: &PgPool, worker_id: &str) -> Result<Option<Job>, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        UPDATE jobs
+        SET status = 'running', attempts = attempts + 1,
+            locked_at = now(), locked_by = $1, updated_at = now()
+        WHERE id = (
+            SELECT id FROM jobs
+            WHERE status = 'queued' AND run_at <= now()
+            ORDER BY run_at, id
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, kind, payload, attempts, max_attempts
+        "#,
+        worker_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|r| Job {
+        id: r.id,
+        kind: r.kind,
+        payload: r.payload,
+        attempts: r.attempts,
+        max_attempts: r.max_attempts,
+    }))
+}
+
+async fn finish(pool: &PgPool, job: &Job, result: Result<(), JobError>) -> Result<(), sqlx::Error> {
+    let (status, error, delay) = match result {
+        Ok(()) => ("succeeded", None, 0.0),
+        Err(JobError::Retry(e)) if !job.is_last_attempt() => ("queued", Some(e), backoff_secs(job.attempts)),
+        Err(e) => ("dead", Some(e.to_string()), 0.0),
+    };
+    match (status, &error) {
+        ("succeeded", _) => tracing::info!("job succeeded"),
+        ("queued", Some(e)) => tracing::warn!(error = %e, retry_in_secs = delay, "job failed, will retry"),
+        (_, e) => tracing::error!(error = e.as_deref().unwrap_or_default(), "job is dead"),
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE jobs
+        SET status = $2, last_error = COALESCE($3, last_error),
+            run_at = now() + make_interval(secs => $4),
+            locked_at = NULL, locked_by = NULL, updated_at = now()
+        WHERE id = $1
+        "#,
+        job.id,
+        status,
+        error,
+        delay
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn run_maintenance(pool: PgPool) {
+    loop {
+        let stale = sqlx::query!(
+            r#"
+            UPDATE jobs
+            SET status = 'queued', locked_at = NULL, locked_by = NULL, updated_at = now()
+            WHERE status = 'running' AND locked_at < now() - make_interval(secs => $1)
+            "#,
+            STALE_LOCK_SECS
+        )
+        .execute(&pool)
+        .await;
+
+        let purged = sqlx::query!(
+            "DELETE FROM jobs WHERE status = 'succeeded' AND updated_at < now() - make_interval(days => $1)",
+            SUCCEEDED_RETENTION_DAYS
+        )
+        .execute(&pool)
+        .await;
+
+        let attempts = sqlx::query!(
+            "DELETE FROM auth.login_attempts WHERE attempted_at < now() - make_interval(days => $1)",
+            LOGIN_ATTEMPT_RETENTION_DAYS
+        )
+        .execute(&pool)
+        .await;
+
+        let challenges = sqlx::query!("DELETE FROM auth.login_challenges WHERE expires_at < now()")
+            .execute(&pool)
+            .await;
+
+        let oidc_states = sqlx::query!("DELETE FROM auth.oidc_login_states WHERE expires_at < now()")
+            .execute(&pool)
+            .await;
+
+        if let Err(e) = stale.and(purged).and(attempts).and(challenges).and(oidc_states) {
+            tracing::error!(error = %e, "job maintenance failed");
+        }
+        tokio::time::sleep(MAINTENANCE_INTERVAL).await;
+    }
+}
@@ -0,0 +1,110 @@
+//! Reads people from the LDAP directory configured in the environment.
+
+use ldap3::adapters::{Adapter, EntriesOnly, PagedResults};
+use ldap3::{LdapConnAsync, LdapConnSettings, Scope, SearchEntry};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const PAGE_SIZE: i32 = 500;
+
+#[derive(Debug, Clone)]
+pub struct LdapConfig {
+    pub url: String,
+    pub bind_dn: Option<String>,
+    pub bind_password: String,
+    pub base_dn: String,
+    pub filter: String,
+    pub email_attr: String,
+    pub first_name_attr: String,
+    pub last_name_attr: String,
+    // Runs the sync unattended every n minutes, without a preview
+    pub interval_minutes: Option<u64>,
+}
+
+/// `None` unless `LDAP_URL` and `LDAP_BASE_DN` are set and the `ldap_sync` feature is on.
+pub fn config() -> Option<&'static LdapConfig> {
+    static CONFIG: OnceLock<Option<LdapConfig>> = OnceLock::new();
+    CONFIG.get_or_init(from_env).as_ref()
+}
+
+fn from_env() -> Option<LdapConfig> {
+    if !crate::server::config::get().features.ldap_sync {
+        return None;
+    }
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+    let attr = |name: &str, default: &str| var(name).unwrap_or_else(|| default.to_string());
+
+    Some(LdapConfig {
+        url: var("LDAP_URL")?,
+        base_dn: var("LDAP_BASE_DN")?,
+        bind_dn: var("LDAP_BIND_DN"),
+        bind_password: var("LDAP_BIND_PASSWORD").unwrap_or_default(),
+        filter: attr("LDAP_FILTER", "(objectClass=inetOrgPerson)"),
+        email_attr: attr("LDAP_ATTR_EMAIL", "mail"),
+        first_name_attr: attr("LDAP_ATTR_FIRST_NAME", "givenName"),
+        last_name_attr: attr("LDAP_ATTR_LAST_NAME", "sn"),
+        interval_minutes: var("LDAP_SYNC_INTERVAL_MINUTES")
+            .and_then(|v| v.parse().ok())
+            .filter(|&m| m > 0),
+    })
+}
+
+/// A directory entry with the attributes we map, each `None` if absent.
+#[derive(Debug, Clone)]
+pub struct DirectoryPerson {
+    pub dn: String,
+    pub email: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+}
+
+/// All entries under the base DN matching the filter, fetched in pages.
+pub async fn fetch_people(cfg: &LdapConfig) -> Result<Vec<DirectoryPerson>, ldap3::LdapError> {
+    let settings = LdapConnSettings::new().set_conn_timeout(CONNECT_TIMEOUT);
+    let (conn, mut ldap) = LdapConnAsync::with_settings(settings, &cfg.url).await?;
+    ldap3::drive!(conn);
+
+    if let Some(bind_dn) = &cfg.bind_dn {
+        ldap.simple_bind(bind_dn, &cfg.bind_password).await?.success()?;
+    }
+
+    let attrs = vec![
+        cfg.email_attr.as_str(),
+        cfg.first_name_attr.as_str(),
+        cfg.last_name_attr.as_str(),
+    ];
+    let adapters: Vec<Box<dyn Adapter<_, _>>> = vec![
+        Box::new(EntriesOnly::new()),
+        Box::new(PagedResults::new(PAGE_SIZE)),
+    ];
+    let mut search = ldap
+        .streaming_search_with(adapters, &cfg.base_dn, Scope::Subtree, &cfg.filter, attrs)
+        .await?;
+
+    let mut people = Vec::new();
+    while let Some(entry) = search.next().await? {
+        let entry = SearchEntry::construct(entry);
+        people.push(DirectoryPerson {
+            email: first_value(&entry.attrs, &cfg.email_attr),
+            first_name: first_value(&entry.attrs, &cfg.first_name_attr),
+            last_name: first_value(&entry.attrs, &cfg.last_name_attr),
+            dn: entry.dn,
+        });
+    }
+    search.finish().await.success()?;
+    ldap.unbind().await?;
+
+    Ok(people)
+}
+
+// Attribute names are case-insensitive in LDAP
+fn first_value(attrs: &HashMap<String, Vec<String>>, name: &str) -> Option<String> {
+    attrs
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .and_then(|(_, values)| values.first())
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
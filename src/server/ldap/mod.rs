@@ -0,0 +1,130 @@
+//! Sync of `employee` from an LDAP directory: admins preview the diff, then
+//! start an `ldap.sync` job that applies it.
+
+use dioxus::prelude::*;
+use crate::models::ldap::{LdapSyncPreview, LdapSyncRun, MissingEmployee};
+
+#[cfg(feature = "server")]
+pub mod directory;
+#[cfg(feature = "server")]
+pub mod sync;
+
+#[cfg(feature = "server")]
+use super::{db, security};
+#[cfg(feature = "server")]
+use crate::models::auth::SCOPE_ADMIN;
+
+#[cfg(feature = "server")]
+const RUN_LIST_LIMIT: i64 = 20;
+
+#[cfg(feature = "server")]
+fn not_configured() -> ServerFnError {
+    ServerFnError::ServerError {
+        message: "LDAP sync is disabled or not configured (set LDAP_URL and LDAP_BASE_DN)".to_string(),
+        code: 503,
+        details: None,
+    }
+}
+
+/// Reads the directory and returns what a sync would change, without changing anything.
+#[server]
+pub async fn preview_ldap_sync() -> Result<LdapSyncPreview, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        let cfg = directory::config().ok_or_else(not_configured)?;
+        let people = directory::fetch_people(cfg).await.map_err(|e| ServerFnError::ServerError {
+            message: format!("LDAP error: {}", e),
+            code: 502,
+            details: None,
+        })?;
+
+        sync::diff(&pool, people)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Queues the sync for a preview. The job refuses to run if the diff changed since.
+#[server]
+pub async fn apply_ldap_sync(fingerprint: String) -> Result<i64, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        let principal = security::require_scope(&pool, SCOPE_ADMIN).await?;
+        directory::config().ok_or_else(not_configured)?;
+
+        let payload = sync::SyncPayload { triggered_by: principal.username, fingerprint: Some(fingerprint) };
+        sync::enqueue(&pool, &payload)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+#[server]
+pub async fn list_ldap_sync_runs() -> Result<Vec<LdapSyncRun>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query_as!(
+            LdapSyncRun,
+            r#"
+            SELECT id, triggered_by, status, created, updated, missing, returned, error, started_at, finished_at
+            FROM ldap_sync_runs
+            ORDER BY started_at DESC
+            LIMIT $1
+            "#,
+            RUN_LIST_LIMIT
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Employees flagged because they are no longer in the directory.
+#[server]
+pub async fn list_missing_employees() -> Result<Vec<MissingEmployee>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query_as!(
+            MissingEmployee,
+            r#"
+            SELECT id, first_name, last_name, email, ldap_dn, ldap_missing_since AS "missing_since!"
+            FROM employee
+            WHERE ldap_missing_since IS NOT NULL
+            ORDER BY ldap_missing_since DESC, last_name
+            "#
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
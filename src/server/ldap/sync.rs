@@ -0,0 +1,281 @@
+//! Diff between the directory and `employee`, and the `ldap.sync` job that
+//! applies it.
+//!
+//! Employees are matched by email (case-insensitive). The sync creates and
+//! updates employees and flags the ones that disappeared from the directory,
+//! it never deletes anyone.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+use super::directory::{self, DirectoryPerson};
+use crate::models::employee::{CreateEmployeeRequest, Employee};
+use crate::models::ldap::{LdapChange, LdapChangeKind, LdapSkippedEntry, LdapSyncPreview};
+use crate::models::webhook::{EVENT_EMPLOYEE_CREATED, EVENT_EMPLOYEE_UPDATED};
+use crate::server::jobs::queue::{self, Job, JobError};
+use crate::server::webhooks::delivery;
+
+pub const KIND_SYNC: &str = "ldap.sync";
+const MAX_ATTEMPTS: i32 = 3;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncPayload {
+    pub triggered_by: String,
+    // Set when started from a preview; the job fails if the diff changed since
+    pub fingerprint: Option<String>,
+}
+
+pub async fn enqueue(pool: &PgPool, payload: &SyncPayload) -> Result<i64, sqlx::Error> {
+    queue::enqueue_with_attempts(pool, KIND_SYNC, payload, MAX_ATTEMPTS).await
+}
+
+/// Enqueues an unattended sync every `LDAP_SYNC_INTERVAL_MINUTES`, if set.
+pub fn start_schedule(pool: PgPool) {
+    let Some(minutes) = directory::config().and_then(|cfg| cfg.interval_minutes) else { return };
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(minutes * 60));
+        loop {
+            interval.tick().await;
+            let payload = SyncPayload { triggered_by: "schedule".to_string(), fingerprint: None };
+            if let Err(e) = enqueue(&pool, &payload).await {
+                tracing::error!(error = %e, "could not schedule LDAP sync");
+            }
+        }
+    });
+}
+
+struct CurrentEmployee {
+    id: i64,
+    first_name: String,
+    last_name: String,
+    email: String,
+    ldap_dn: Option<String>,
+    missing: bool,
+}
+
+/// Compares the directory with the employees without changing anything.
+pub async fn diff(pool: &PgPool, people: Vec<DirectoryPerson>) -> Result<LdapSyncPreview, sqlx::Error> {
+    let employees = sqlx::query_as!(
+        CurrentEmployee,
+        r#"
+        SELECT id, first_name, last_name, email, ldap_dn,
+               ldap_missing_since IS NOT NULL AS "missing!"
+        FROM employee
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+    let by_email: HashMap<String, &CurrentEmployee> =
+        employees.iter().map(|e| (e.email.to_lowercase(), e)).collect();
+
+    let mut changes = Vec::new();
+    let mut skipped = Vec::new();
+    let mut unchanged = 0;
+    let mut seen: HashMap<String, String> = HashMap::new();
+
+    for person in people {
+        let req = CreateEmployeeRequest {
+            first_name: person.first_name.clone().unwrap_or_default(),
+            last_name: person.last_name.clone().unwrap_or_default(),
+            email: person.email.clone().unwrap_or_default(),
+        };
+        if let Err(errors) = req.validate() {
+            let reasons: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+            skipped.push(LdapSkippedEntry { dn: person.dn, reason: reasons.join(", ") });
+            continue;
+        }
+
+        let email = req.email.to_lowercase();
+        if let Some(first_dn) = seen.get(&email) {
+            skipped.push(LdapSkippedEntry { dn: person.dn, reason: format!("Same mail as {}", first_dn) });
+            continue;
+        }
+        seen.insert(email.clone(), person.dn.clone());
+
+        let change = |kind, current: Option<&CurrentEmployee>| LdapChange {
+            kind,
+            employee_id: current.map(|e| e.id),
+            dn: Some(person.dn.clone()),
+            email: current.map_or(req.email.clone(), |e| e.email.clone()),
+            first_name: req.first_name.clone(),
+            last_name: req.last_name.clone(),
+            old_first_name: current.map(|e| e.first_name.clone()),
+            old_last_name: current.map(|e| e.last_name.clone()),
+        };
+
+        match by_email.get(&email) {
+            None => changes.push(change(LdapChangeKind::Create, None)),
+            Some(current) if current.missing => changes.push(change(LdapChangeKind::Returned, Some(current))),
+            Some(current)
+                if current.first_name != req.first_name
+                    || current.last_name != req.last_name
+                    || current.ldap_dn.as_deref() != Some(person.dn.as_str()) =>
+            {
+                changes.push(change(LdapChangeKind::Update, Some(current)))
+            }
+            Some(_) => unchanged += 1,
+        }
+    }
+
+    // Only employees that came from (or were matched in) the directory can go missing
+    for employee in &employees {
+        if employee.ldap_dn.is_some() && !employee.missing && !seen.contains_key(&employee.email.to_lowercase()) {
+            changes.push(LdapChange {
+                kind: LdapChangeKind::Missing,
+                employee_id: Some(employee.id),
+                dn: employee.ldap_dn.clone(),
+                email: employee.email.clone(),
+                first_name: employee.first_name.clone(),
+                last_name: employee.last_name.clone(),
+                old_first_name: None,
+                old_last_name: None,
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| (a.kind.as_str(), &a.email).cmp(&(b.kind.as_str(), &b.email)));
+    let fingerprint = fingerprint(&changes);
+
+    Ok(LdapSyncPreview { changes, skipped, unchanged, fingerprint })
+}
+
+fn fingerprint(changes: &[LdapChange]) -> String {
+    let json = serde_json::to_vec(changes).unwrap_or_default();
+    hex::encode(Sha256::digest(&json))
+}
+
+#[derive(Debug, Default)]
+pub struct ApplyCounts {
+    pub created: i32,
+    pub updated: i32,
+    pub missing: i32,
+    pub returned: i32,
+}
+
+/// Applies `preview` in one transaction. Created and updated employees publish
+/// the usual webhook events; no welcome mails are sent for synced people.
+pub async fn apply(pool: &PgPool, preview: &LdapSyncPreview) -> Result<ApplyCounts, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut counts = ApplyCounts::default();
+
+    for change in &preview.changes {
+        match (change.kind, change.employee_id) {
+            (LdapChangeKind::Create, _) => {
+                let employee = sqlx::query_as!(
+                    Employee,
+                    r#"
+                    INSERT INTO employee (first_name, last_name, email, ldap_dn, ldap_synced_at)
+                    VALUES ($1, $2, $3, $4, now())
+                    RETURNING id, first_name, last_name, email
+                    "#,
+                    change.first_name,
+                    change.last_name,
+                    change.email,
+                    change.dn
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+                delivery::enqueue_event(&mut *tx, EVENT_EMPLOYEE_CREATED, &employee).await?;
+                counts.created += 1;
+            }
+            (LdapChangeKind::Update | LdapChangeKind::Returned, Some(id)) => {
+                let employee = sqlx::query_as!(
+                    Employee,
+                    r#"
+                    UPDATE employee
+                    SET first_name = $2, last_name = $3, ldap_dn = $4,
+                        ldap_synced_at = now(), ldap_missing_since = NULL
+                    WHERE id = $1
+                    RETURNING id, first_name, last_name, email
+                    "#,
+                    id,
+                    change.first_name,
+                    change.last_name,
+                    change.dn
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+                delivery::enqueue_event(&mut *tx, EVENT_EMPLOYEE_UPDATED, &employee).await?;
+                if change.kind == LdapChangeKind::Returned {
+                    counts.returned += 1;
+                } else {
+                    counts.updated += 1;
+                }
+            }
+            (LdapChangeKind::Missing, Some(id)) => {
+                sqlx::query!("UPDATE employee SET ldap_missing_since = now() WHERE id = $1", id)
+                    .execute(&mut *tx)
+                    .await?;
+                counts.missing += 1;
+            }
+            _ => {}
+        }
+    }
+
+    // After the changes every linked employee that isn't flagged is in the directory
+    sqlx::query!("UPDATE employee SET ldap_synced_at = now() WHERE ldap_dn IS NOT NULL AND ldap_missing_since IS NULL")
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(counts)
+}
+
+/// Job handler: reads the directory, diffs, checks the preview fingerprint and applies.
+pub async fn run(pool: &PgPool, job: &Job) -> Result<(), JobError> {
+    let payload: SyncPayload = job.payload()?;
+    let cfg = directory::config().ok_or_else(|| JobError::Fatal("LDAP sync is not configured".to_string()))?;
+
+    let run_id = sqlx::query_scalar!(
+        "INSERT INTO ldap_sync_runs (triggered_by) VALUES ($1) RETURNING id",
+        payload.triggered_by
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let result = async {
+        let people = directory::fetch_people(cfg)
+            .await
+            .map_err(|e| JobError::Retry(format!("LDAP error: {}", e)))?;
+        let preview = diff(pool, people).await?;
+        if payload.fingerprint.as_ref().is_some_and(|f| *f != preview.fingerprint) {
+            return Err(JobError::Fatal(
+                "The directory or the employees changed since the preview, please preview again".to_string(),
+            ));
+        }
+        Ok(apply(pool, &preview).await?)
+    }
+    .await;
+
+    match &result {
+        Ok(counts) => {
+            sqlx::query!(
+                r#"
+                UPDATE ldap_sync_runs
+                SET status = 'succeeded', created = $2, updated = $3, missing = $4, returned = $5,
+                    finished_at = now()
+                WHERE id = $1
+                "#,
+                run_id,
+                counts.created,
+                counts.updated,
+                counts.missing,
+                counts.returned
+            )
+            .execute(pool)
+            .await?;
+        }
+        Err(e) => {
+            sqlx::query!(
+                "UPDATE ldap_sync_runs SET status = 'failed', error = $2, finished_at = now() WHERE id = $1",
+                run_id,
+                e.to_string()
+            )
+            .execute(pool)
+            .await?;
+        }
+    }
+    result.map(|_| ())
+}
@@ -0,0 +1,207 @@
+//! Brute-force protection for the login.
+//!
+//! Failed attempts are tracked per username and per client IP. After a few free
+//! attempts each further failure doubles the wait before the next try, and an
+//! account is locked for a while after too many consecutive failures. The lock
+//! is separate from `enabled`, so lifting it never re-enables a disabled account.
+
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+use dioxus::prelude::ServerFnError;
+use sqlx::PgPool;
+use std::net::SocketAddr;
+
+use super::config;
+
+// Failures older than this are forgotten, for throttling and for the lock
+pub const WINDOW_MINUTES: i32 = 15;
+
+const USERNAME_FREE_ATTEMPTS: i64 = 3;
+// Higher because many users may share one address behind a NAT
+const IP_FREE_ATTEMPTS: i64 = 10;
+const MAX_DELAY_SECS: i64 = 60;
+
+pub const LOCK_THRESHOLD: i32 = 5;
+pub const LOCK_MINUTES: i32 = 15;
+
+/// Seconds to wait after the last failure, doubling with each failure past the free ones.
+fn delay_secs(failures: i64, free: i64) -> i64 {
+    if failures < free {
+        0
+    } else {
+        2i64.saturating_pow((failures - free) as u32).min(MAX_DELAY_SECS)
+    }
+}
+
+/// Client address for throttling. Every proxy appends the address it got the
+/// request from to X-Forwarded-For, so only the last `server.trusted_proxies`
+/// entries can be believed; the client writes whatever it likes further left.
+/// Without trusted proxies the header is ignored and the socket address counts.
+pub fn client_ip(headers: &HeaderMap, peer: Option<SocketAddr>) -> String {
+    let Some(peer) = peer else { return "unknown".to_string() };
+    let forwarded: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .collect();
+
+    // Hops from the nearest one, the client being `trusted_proxies` hops away.
+    // With fewer entries the request skipped a proxy and the farthest known hop counts.
+    let peer = peer.ip().to_string();
+    std::iter::once(peer.as_str())
+        .chain(forwarded.into_iter().rev())
+        .take(config::get().server.trusted_proxies + 1)
+        .last()
+        .map(|ip| ip.chars().take(45).collect())
+        .unwrap_or_default()
+}
+
+pub fn normalize(username: &str) -> String {
+    username.trim().to_lowercase().chars().take(50).collect()
+}
+
+fn too_many(wait: i64) -> ServerFnError {
+    ServerFnError::ServerError {
+        message: format!("Too many failed attempts, try again in {} seconds", wait),
+        code: 429,
+        details: None,
+    }
+}
+
+pub fn locked(until: DateTime<Utc>) -> ServerFnError {
+    ServerFnError::ServerError {
+        message: format!("Account is locked after too many failed logins until {} UTC", until.format("%H:%M")),
+        code: 423,
+        details: None,
+    }
+}
+
+/// Rejects the attempt with 429 while the username or the IP still has to wait.
+pub async fn check(pool: &PgPool, username: &str, ip: &str) -> Result<(), ServerFnError> {
+    // Failures since the last successful login of this username
+    let by_user = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "failures!", MAX(attempted_at) AS last_failure
+        FROM auth.login_attempts
+        WHERE username = $1
+          AND NOT succeeded
+          AND attempted_at > now() - make_interval(mins => $2)
+          AND attempted_at > COALESCE(
+              (SELECT MAX(attempted_at) FROM auth.login_attempts WHERE username = $1 AND succeeded),
+              '-infinity'
+          )
+        "#,
+        username,
+        WINDOW_MINUTES
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let by_ip = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "failures!", MAX(attempted_at) AS last_failure
+        FROM auth.login_attempts
+        WHERE ip = $1 AND NOT succeeded AND attempted_at > now() - make_interval(mins => $2)
+        "#,
+        ip,
+        WINDOW_MINUTES
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let now = Utc::now();
+    let remaining = |last: Option<DateTime<Utc>>, delay: i64| {
+        last.map(|t| (t + chrono::Duration::seconds(delay) - now).num_seconds()).unwrap_or(0)
+    };
+    let wait = remaining(by_user.last_failure, delay_secs(by_user.failures, USERNAME_FREE_ATTEMPTS))
+        .max(remaining(by_ip.last_failure, delay_secs(by_ip.failures, IP_FREE_ATTEMPTS)));
+
+    if wait > 0 {
+        Err(too_many(wait))
+    } else {
+        Ok(())
+    }
+}
+
+/// Logs a failed attempt and, for existing accounts, counts it towards the lock.
+/// Returns the lock end if this failure locked the account.
+pub async fn record_failure(
+    pool: &PgPool,
+    username: &str,
+    ip: &str,
+    user_id: Option<i64>,
+) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO auth.login_attempts (username, ip, succeeded) VALUES ($1, $2, false)",
+        username,
+        ip
+    )
+    .execute(pool)
+    .await?;
+
+    let Some(user_id) = user_id else { return Ok(None) };
+
+    // Counting starts over once an earlier lock has run out, or when the last
+    // failure is older than the window: only failures close together lock
+    let locked_until = sqlx::query_scalar!(
+        r#"
+        WITH current AS (
+            SELECT id,
+                   CASE
+                       WHEN locked_until <= now()
+                         OR last_failed_login_at IS NULL
+                         OR last_failed_login_at <= now() - make_interval(mins => $4)
+                       THEN 0
+                       ELSE failed_logins
+                   END AS failures,
+                   locked_until > now() AS locked
+            FROM auth.users
+            WHERE id = $1
+            FOR UPDATE
+        )
+        UPDATE auth.users u
+        SET failed_logins = c.failures + 1,
+            last_failed_login_at = now(),
+            locked_until = CASE
+                WHEN c.locked THEN u.locked_until
+                WHEN c.failures + 1 >= $2 THEN now() + make_interval(mins => $3)
+                ELSE NULL
+            END
+        FROM current c
+        WHERE u.id = c.id
+        RETURNING u.locked_until
+        "#,
+        user_id,
+        LOCK_THRESHOLD,
+        LOCK_MINUTES,
+        WINDOW_MINUTES
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(locked_until)
+}
+
+pub async fn record_success(pool: &PgPool, username: &str, ip: &str, user_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO auth.login_attempts (username, ip, succeeded) VALUES ($1, $2, true)",
+        username,
+        ip
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE auth.users SET failed_logins = 0, locked_until = NULL, last_login_at = now() WHERE id = $1",
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
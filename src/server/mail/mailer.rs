@@ -0,0 +1,170 @@
+//! Mail transports. The backend is picked with `MAIL_TRANSPORT`:
+//!
+//! * `smtp` - `SMTP_HOST`, `SMTP_PORT`, `SMTP_TLS` (`none` | `starttls` | `tls`),
+//!   optional `SMTP_USERNAME` / `SMTP_PASSWORD`
+//! * `file` - writes one `.eml` file per mail into `MAIL_DIR` (default `./mail`)
+//! * `log`  - only prints the mail to stderr (default)
+//!
+//! `MAIL_FROM` sets the sender for all backends.
+
+use lettre::message::{header::ContentType, Mailbox, Message};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncFileTransport, AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use std::env;
+use std::fmt;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+
+const DEFAULT_FROM: &str = "EMS <no-reply@ems.local>";
+const DEFAULT_MAIL_DIR: &str = "./mail";
+
+/// A rendered plain-text mail ready to send.
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug)]
+pub enum MailError {
+    /// The mail itself is broken (bad address, ...), sending it again won't help
+    Invalid(String),
+    /// The transport failed, worth retrying later
+    Transport(String),
+}
+
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MailError::Invalid(e) => write!(f, "invalid mail: {}", e),
+            MailError::Transport(e) => write!(f, "mail transport failed: {}", e),
+        }
+    }
+}
+
+pub type SendFuture<'a> = Pin<Box<dyn Future<Output = Result<(), MailError>> + Send + 'a>>;
+
+/// Anything that can deliver an [`Email`].
+pub trait Mailer: Send + Sync {
+    fn send<'a>(&'a self, email: &'a Email) -> SendFuture<'a>;
+}
+
+fn build_message(from: &Mailbox, email: &Email) -> Result<Message, MailError> {
+    let to: Mailbox = email.to.parse().map_err(|e| MailError::Invalid(format!("recipient '{}': {}", email.to, e)))?;
+
+    Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(&email.subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(email.body.clone())
+        .map_err(|e| MailError::Invalid(e.to_string()))
+}
+
+pub struct SmtpMailer {
+    from: Mailbox,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl Mailer for SmtpMailer {
+    fn send<'a>(&'a self, email: &'a Email) -> SendFuture<'a> {
+        Box::pin(async move {
+            let message = build_message(&self.from, email)?;
+            self.transport
+                .send(message)
+                .await
+                .map(|_| ())
+                .map_err(|e| MailError::Transport(e.to_string()))
+        })
+    }
+}
+
+pub struct FileMailer {
+    from: Mailbox,
+    transport: AsyncFileTransport<Tokio1Executor>,
+}
+
+impl Mailer for FileMailer {
+    fn send<'a>(&'a self, email: &'a Email) -> SendFuture<'a> {
+        Box::pin(async move {
+            let message = build_message(&self.from, email)?;
+            let id = self
+                .transport
+                .send(message)
+                .await
+                .map_err(|e| MailError::Transport(e.to_string()))?;
+            tracing::info!(to = %email.to, file = %format!("{}.eml", id), "mail written");
+            Ok(())
+        })
+    }
+}
+
+pub struct LogMailer {
+    from: Mailbox,
+}
+
+impl Mailer for LogMailer {
+    fn send<'a>(&'a self, email: &'a Email) -> SendFuture<'a> {
+        Box::pin(async move {
+            // Build anyway so invalid addresses fail the same way as with SMTP
+            build_message(&self.from, email)?;
+            tracing::info!(
+                from = %self.from,
+                to = %email.to,
+                subject = %email.subject,
+                body = %email.body,
+                "mail (log transport, not sent)"
+            );
+            Ok(())
+        })
+    }
+}
+
+fn from_env() -> Result<Arc<dyn Mailer>, String> {
+    dotenvy::dotenv().ok();
+
+    let from: Mailbox = env::var("MAIL_FROM")
+        .unwrap_or_else(|_| DEFAULT_FROM.to_string())
+        .parse()
+        .map_err(|e| format!("MAIL_FROM: {}", e))?;
+
+    match env::var("MAIL_TRANSPORT").unwrap_or_else(|_| "log".to_string()).as_str() {
+        "smtp" => {
+            let host = env::var("SMTP_HOST").map_err(|_| "SMTP_HOST must be set for MAIL_TRANSPORT=smtp".to_string())?;
+            let tls = env::var("SMTP_TLS").unwrap_or_else(|_| "starttls".to_string());
+
+            let mut builder = match tls.as_str() {
+                "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host),
+                "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host).map_err(|e| e.to_string())?,
+                "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&host).map_err(|e| e.to_string())?,
+                other => return Err(format!("SMTP_TLS must be none, starttls or tls, got '{}'", other)),
+            };
+            if let Ok(port) = env::var("SMTP_PORT") {
+                builder = builder.port(port.parse().map_err(|_| format!("SMTP_PORT '{}' is not a port", port))?);
+            }
+            if let (Ok(user), Ok(password)) = (env::var("SMTP_USERNAME"), env::var("SMTP_PASSWORD")) {
+                builder = builder.credentials(Credentials::new(user, password));
+            }
+            Ok(Arc::new(SmtpMailer { from, transport: builder.build() }))
+        }
+        "file" => {
+            let dir = PathBuf::from(env::var("MAIL_DIR").unwrap_or_else(|_| DEFAULT_MAIL_DIR.to_string()));
+            std::fs::create_dir_all(&dir).map_err(|e| format!("MAIL_DIR {}: {}", dir.display(), e))?;
+            Ok(Arc::new(FileMailer { from, transport: AsyncFileTransport::new(dir) }))
+        }
+        "log" => Ok(Arc::new(LogMailer { from })),
+        other => Err(format!("MAIL_TRANSPORT must be smtp, file or log, got '{}'", other)),
+    }
+}
+
+/// The configured mailer, built on first use.
+pub fn mailer() -> Result<Arc<dyn Mailer>, MailError> {
+    static MAILER: OnceLock<Result<Arc<dyn Mailer>, String>> = OnceLock::new();
+    MAILER
+        .get_or_init(from_env)
+        .clone()
+        .map_err(MailError::Transport)
+}
@@ -0,0 +1,122 @@
+use dioxus::prelude::*;
+use crate::models::email::{EmailTemplate, SaveEmailTemplateRequest};
+
+#[cfg(feature = "server")]
+pub mod mailer;
+#[cfg(feature = "server")]
+pub mod notify;
+
+#[cfg(feature = "server")]
+use super::{db, security};
+#[cfg(feature = "server")]
+use crate::models::{
+    auth::SCOPE_ADMIN,
+    email::{placeholders, BODY_MAX_LEN, SUBJECT_MAX_LEN},
+};
+
+#[server]
+pub async fn list_email_templates() -> Result<Vec<EmailTemplate>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query_as!(
+            EmailTemplate,
+            "SELECT key, description, subject, body, variables, updated_at FROM email_templates ORDER BY key"
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Saves subject and body. Placeholders must be among the template's variables,
+/// otherwise they would end up verbatim in every mail.
+#[server]
+pub async fn update_email_template(key: String, req: SaveEmailTemplateRequest) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        let invalid = |message: String| ServerFnError::ServerError { message, code: 422, details: None };
+        if req.subject.trim().is_empty() || req.subject.len() > SUBJECT_MAX_LEN {
+            return Err(invalid(format!("Subject must be between 1 and {} characters", SUBJECT_MAX_LEN)));
+        }
+        if req.body.trim().is_empty() || req.body.len() > BODY_MAX_LEN {
+            return Err(invalid(format!("Body must be between 1 and {} characters", BODY_MAX_LEN)));
+        }
+
+        let variables = sqlx::query_scalar!("SELECT variables FROM email_templates WHERE key = $1", key)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?
+            .ok_or_else(|| ServerFnError::ServerError { message: "Template not found".to_string(), code: 404, details: None })?;
+
+        let unknown: Vec<String> = placeholders(&req.subject)
+            .into_iter()
+            .chain(placeholders(&req.body))
+            .filter(|name| !variables.contains(name))
+            .collect();
+        if !unknown.is_empty() {
+            return Err(invalid(format!("Unknown variables: {}", unknown.join(", "))));
+        }
+
+        sqlx::query!(
+            "UPDATE email_templates SET subject = $2, body = $3, updated_at = now() WHERE key = $1",
+            key,
+            req.subject.trim(),
+            req.body
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Sends the saved template right away with each variable filled in as `<name>`,
+/// handy for checking the transport setup against MailHog.
+#[server]
+pub async fn send_test_email(key: String, to: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        let variables = sqlx::query_scalar!("SELECT variables FROM email_templates WHERE key = $1", key)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?
+            .ok_or_else(|| ServerFnError::ServerError { message: "Template not found".to_string(), code: 404, details: None })?;
+        let vars = variables.into_iter().map(|name| (name.clone(), format!("<{}>", name))).collect();
+
+        let email = notify::render_template(&pool, &key, to.trim(), &vars)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?
+            .ok_or_else(|| ServerFnError::new("Template not found"))?;
+
+        let mailer = mailer::mailer().map_err(|e| ServerFnError::new(e.to_string()))?;
+        mailer.send(&email).await.map_err(|e| match e {
+            mailer::MailError::Invalid(_) => ServerFnError::ServerError { message: e.to_string(), code: 422, details: None },
+            mailer::MailError::Transport(_) => ServerFnError::ServerError { message: e.to_string(), code: 502, details: None },
+        })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
@@ -0,0 +1,76 @@
+//! Templated notifications sent through `email.send` jobs, so a slow or
+//! unreachable SMTP server never holds up the request that caused the mail.
+
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, PgPool};
+use std::collections::BTreeMap;
+
+use super::mailer::{mailer, Email, MailError};
+use crate::models::email::render;
+use crate::server::jobs::queue::{self, Job, JobError};
+
+pub const KIND_SEND: &str = "email.send";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SendPayload {
+    template: String,
+    to: String,
+    vars: BTreeMap<String, String>,
+}
+
+/// Queues `template` for `to`. Pass the caller's transaction so the mail only goes
+/// out if the change that triggered it is committed.
+pub async fn enqueue<'e, E: PgExecutor<'e>>(
+    executor: E,
+    template: &str,
+    to: &str,
+    vars: BTreeMap<String, String>,
+) -> Result<(), sqlx::Error> {
+    let payload = SendPayload { template: template.to_string(), to: to.to_string(), vars };
+    queue::enqueue(executor, KIND_SEND, &payload).await?;
+    Ok(())
+}
+
+/// Renders the current version of a template.
+pub async fn render_template(
+    pool: &PgPool,
+    template: &str,
+    to: &str,
+    vars: &BTreeMap<String, String>,
+) -> Result<Option<Email>, sqlx::Error> {
+    let row = sqlx::query!("SELECT subject, body FROM email_templates WHERE key = $1", template)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row.map(|t| Email {
+        to: to.to_string(),
+        subject: render(&t.subject, vars),
+        body: render(&t.body, vars),
+    }))
+}
+
+/// Handler for `email.send` jobs.
+pub async fn send(pool: &PgPool, job: &Job) -> Result<(), JobError> {
+    let payload: SendPayload = job.payload()?;
+    send_now(pool, &payload.template, &payload.to, &payload.vars).await
+}
+
+/// Renders and sends a template from inside a job handler, for mails whose
+/// variables must not be stored in the job payload.
+pub async fn send_now(
+    pool: &PgPool,
+    template: &str,
+    to: &str,
+    vars: &BTreeMap<String, String>,
+) -> Result<(), JobError> {
+    let email = render_template(pool, template, to, vars)
+        .await?
+        .ok_or_else(|| JobError::Fatal(format!("unknown email template '{}'", template)))?;
+
+    let mailer = mailer().map_err(|e| JobError::Retry(e.to_string()))?;
+    mailer.send(&email).await.map_err(|e| match e {
+        MailError::Invalid(_) => JobError::Fatal(e.to_string()),
+        MailError::Transport(_) => JobError::Retry(e.to_string()),
+    })
+}
@@ -0,0 +1,126 @@
+//! Prometheus metrics, rendered by `/metrics` in [`super::health`].
+//!
+//! The [`track`] middleware wraps the whole router. Server function calls are
+//! recorded per function (count, latency, errors by category), unknown `/api/`
+//! paths as function "other"; everything else is only counted by kind.
+
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::Response;
+use dioxus::server::ServerFunction;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use super::config;
+
+// Seconds; server functions are mostly a few DB queries, bcrypt logins take ~0.25s
+const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Installs the global recorder on first use and returns its handle.
+pub fn handle() -> &'static PrometheusHandle {
+    static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+    HANDLE.get_or_init(|| {
+        PrometheusBuilder::new()
+            .set_buckets_for_metric(Matcher::Suffix("duration_seconds".to_string()), &LATENCY_BUCKETS)
+            .expect("non-empty buckets")
+            .install_recorder()
+            .expect("metrics recorder installed once")
+    })
+}
+
+/// Server function behind a request path, e.g. `/api/get_employees` -> `get_employees`.
+/// Only registered functions have a name, so made-up paths can't add label values.
+fn server_fn_name(path: &str) -> Option<&'static str> {
+    static NAMES: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    let names = NAMES.get_or_init(|| {
+        ServerFunction::collect()
+            .into_iter()
+            .map(|function| {
+                let path = function.path();
+                let name = path.strip_prefix("/api/").unwrap_or(path);
+                // Dioxus appends a hash of the signature
+                (path, name.trim_end_matches(|c: char| c.is_ascii_digit()))
+            })
+            .collect()
+    });
+    names.get(path).copied()
+}
+
+fn request_kind(path: &str) -> &'static str {
+    match path {
+        "/healthz" | "/readyz" | "/metrics" => "probe",
+        p if p.starts_with("/api/v1/") => "rest",
+        p if p.starts_with("/api/") => "server_fn",
+        p if p.starts_with("/auth/") => "auth",
+        _ => "page",
+    }
+}
+
+/// `ServerFnError::ServerError` codes as used across the server modules.
+fn error_category(status: StatusCode) -> &'static str {
+    match status.as_u16() {
+        400 => "bad_request",
+        401 => "unauthenticated",
+        403 => "forbidden",
+        404 => "not_found",
+        409 => "conflict",
+        422 => "validation",
+        423 => "locked",
+        429 => "throttled",
+        502 | 503 => "unavailable",
+        500..=599 => "internal",
+        _ => "other",
+    }
+}
+
+pub async fn track(request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let status = response.status();
+
+    metrics::counter!(
+        "ems_http_requests_total",
+        "kind" => request_kind(&path),
+        "status" => status.as_u16().to_string()
+    )
+    .increment(1);
+
+    if request_kind(&path) == "server_fn" {
+        let function = server_fn_name(&path).unwrap_or("other");
+        metrics::counter!("ems_server_fn_requests_total", "function" => function).increment(1);
+        metrics::histogram!("ems_server_fn_duration_seconds", "function" => function)
+            .record(started.elapsed().as_secs_f64());
+        if status.is_client_error() || status.is_server_error() {
+            metrics::counter!(
+                "ems_server_fn_errors_total",
+                "function" => function,
+                "category" => error_category(status)
+            )
+            .increment(1);
+        }
+    }
+    response
+}
+
+/// Gauges that are cheaper to read at scrape time than to keep up to date.
+pub async fn record_gauges(pool: &PgPool) {
+    let size = pool.size() as f64;
+    let idle = pool.num_idle() as f64;
+    metrics::gauge!("ems_db_pool_connections", "state" => "idle").set(idle);
+    metrics::gauge!("ems_db_pool_connections", "state" => "in_use").set(size - idle);
+    metrics::gauge!("ems_db_pool_max_connections").set(config::get().database.max_connections as f64);
+
+    let jobs = sqlx::query!(r#"SELECT status, COUNT(*) AS "count!" FROM jobs GROUP BY status"#)
+        .fetch_all(pool)
+        .await;
+    if let Ok(rows) = jobs {
+        for row in rows {
+            metrics::gauge!("ems_jobs", "status" => row.status).set(row.count as f64);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Schema migrations run by the server itself.
+//!
+//! The SQL scripts under `database/` are compiled into the binary and applied
+//! in order, each in its own transaction, and recorded in
+//! `public.schema_migrations`. Databases created by the Postgres container's
+//! init scripts already contain the schema: a script whose marker table exists
+//! is recorded as applied without running it again.
+//!
+//! The scripts assign ownership to `rustconnector` (created by
+//! `database/init/01-init.sql`), so that role has to exist.
+
+use sqlx::{Connection, PgPool};
+
+// Serialises concurrent runs, e.g. several replicas starting at once
+const LOCK_KEY: i64 = 0x0065_6d73_5f6d_6967; // "ems_mig"
+
+pub struct Migration {
+    pub version: &'static str,
+    pub name: &'static str,
+    sql: &'static str,
+    // The newest table the script creates, None for idempotent data scripts
+    pub marker: Option<&'static str>,
+}
+
+/// Everything a database needs to run this build, without sample data.
+pub const MIGRATIONS: [Migration; 15] = [
+    Migration {
+        version: "02",
+        name: "default-schema",
+        sql: include_str!("../../database/schema/02-default-schema.sql"),
+        marker: Some("public.employee"),
+    },
+    Migration {
+        version: "03",
+        name: "auth-schema",
+        sql: include_str!("../../database/schema/03-auth-schema.sql"),
+        marker: Some("auth.user_roles"),
+    },
+    Migration {
+        version: "06",
+        name: "workforce-schema",
+        sql: include_str!("../../database/schema/06-workforce-schema.sql"),
+        marker: Some("public.departments"),
+    },
+    Migration {
+        version: "08",
+        name: "auth-tokens-schema",
+        sql: include_str!("../../database/schema/08-auth-tokens-schema.sql"),
+        marker: Some("auth.api_tokens"),
+    },
+    Migration {
+        version: "09",
+        name: "webhooks-schema",
+        sql: include_str!("../../database/schema/09-webhooks-schema.sql"),
+        marker: Some("public.webhook_delivery_attempts"),
+    },
+    Migration {
+        version: "10",
+        name: "jobs-schema",
+        sql: include_str!("../../database/schema/10-jobs-schema.sql"),
+        marker: Some("public.jobs"),
+    },
+    Migration {
+        version: "11",
+        name: "email-schema",
+        sql: include_str!("../../database/schema/11-email-schema.sql"),
+        marker: Some("public.email_templates"),
+    },
+    Migration {
+        version: "12",
+        name: "email-templates-data",
+        sql: include_str!("../../database/data/12-email-templates-data.sql"),
+        marker: None,
+    },
+    Migration {
+        version: "13",
+        name: "password-reset-schema",
+        sql: include_str!("../../database/schema/13-password-reset-schema.sql"),
+        marker: Some("auth.password_reset_tokens"),
+    },
+    Migration {
+        version: "15",
+        name: "login-protection-schema",
+        sql: include_str!("../../database/schema/15-login-protection-schema.sql"),
+        marker: Some("auth.login_attempts"),
+    },
+    Migration {
+        version: "16",
+        name: "two-factor-schema",
+        sql: include_str!("../../database/schema/16-two-factor-schema.sql"),
+        marker: Some("auth.login_challenges"),
+    },
+    Migration {
+        version: "17",
+        name: "oidc-schema",
+        sql: include_str!("../../database/schema/17-oidc-schema.sql"),
+        marker: Some("auth.oidc_login_states"),
+    },
+    Migration {
+        version: "18",
+        name: "ldap-sync-schema",
+        sql: include_str!("../../database/schema/18-ldap-sync-schema.sql"),
+        marker: Some("public.ldap_sync_runs"),
+    },
+    Migration {
+        version: "19",
+        name: "roles-data",
+        sql: include_str!("../../database/data/19-roles-data.sql"),
+        marker: None,
+    },
+    Migration {
+        version: "20",
+        name: "employee-undo-schema",
+        sql: include_str!("../../database/schema/20-employee-undo-schema.sql"),
+        marker: Some("public.deleted_employees"),
+    },
+];
+
+// Sample employees and users (password "password"), for `ems-rust seed`
+const SAMPLE_DATA: [(&str, &str); 4] = [
+    ("04-employee-data", include_str!("../../database/data/04-employee-data.sql")),
+    ("05-auth-data", include_str!("../../database/data/05-auth-data.sql")),
+    ("07-workforce-data", include_str!("../../database/data/07-workforce-data.sql")),
+    ("14-auth-email-data", include_str!("../../database/data/14-auth-email-data.sql")),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Applied,
+    // Found already in place (created by the init scripts), only recorded
+    Baselined,
+}
+
+/// Tables that must exist for this build, used by the readiness probe.
+pub fn marker_tables() -> Vec<String> {
+    MIGRATIONS.iter().filter_map(|m| m.marker.map(String::from)).collect()
+}
+
+/// Applies every migration not yet recorded and returns what was done.
+///
+/// The bookkeeping table is created here rather than by a script, so these
+/// queries are checked at runtime instead of by the `query!` macros.
+pub async fn run(pool: &PgPool) -> Result<Vec<(&'static Migration, Outcome)>, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+
+    let mut tx = conn.begin().await?;
+    sqlx::query("SELECT pg_advisory_xact_lock($1)").bind(LOCK_KEY).execute(&mut *tx).await?;
+    sqlx::raw_sql(
+        r#"
+        CREATE TABLE IF NOT EXISTS public.schema_migrations (
+            version    VARCHAR(20)  PRIMARY KEY,
+            name       VARCHAR(100) NOT NULL,
+            baseline   BOOLEAN      NOT NULL DEFAULT false,
+            applied_at TIMESTAMPTZ  NOT NULL DEFAULT now()
+        )
+        "#,
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    let mut done = Vec::new();
+    for migration in &MIGRATIONS {
+        let mut tx = conn.begin().await?;
+        // Held until commit; checking again after taking it keeps replicas from
+        // running the same script twice
+        sqlx::query("SELECT pg_advisory_xact_lock($1)").bind(LOCK_KEY).execute(&mut *tx).await?;
+
+        let recorded: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM public.schema_migrations WHERE version = $1)")
+                .bind(migration.version)
+                .fetch_one(&mut *tx)
+                .await?;
+        if recorded {
+            continue;
+        }
+
+        let present = match migration.marker {
+            Some(table) => {
+                sqlx::query_scalar::<_, bool>("SELECT to_regclass($1) IS NOT NULL")
+                    .bind(table)
+                    .fetch_one(&mut *tx)
+                    .await?
+            }
+            None => false,
+        };
+        if !present {
+            sqlx::raw_sql(migration.sql).execute(&mut *tx).await?;
+        }
+
+        sqlx::query("INSERT INTO public.schema_migrations (version, name, baseline) VALUES ($1, $2, $3)")
+            .bind(migration.version)
+            .bind(migration.name)
+            .bind(present)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        let outcome = if present { Outcome::Baselined } else { Outcome::Applied };
+        tracing::info!(version = migration.version, name = migration.name, ?outcome, "migration recorded");
+        done.push((migration, outcome));
+    }
+    Ok(done)
+}
+
+/// Loads the sample data in one transaction. Returns false without changing
+/// anything if the database already has users or employees.
+pub async fn seed(pool: &PgPool) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let empty = sqlx::query_scalar!(
+        r#"SELECT NOT EXISTS (SELECT 1 FROM auth.users) AND NOT EXISTS (SELECT 1 FROM public.employee) AS "empty!""#
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if !empty {
+        return Ok(false);
+    }
+
+    for (name, sql) in SAMPLE_DATA {
+        sqlx::raw_sql(sql).execute(&mut *tx).await?;
+        tracing::info!(script = name, "sample data loaded");
+    }
+    tx.commit().await?;
+    Ok(true)
+}
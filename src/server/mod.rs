@@ -1,11 +1,52 @@
 use dioxus::prelude::*;
 use crate::models::employee::{
-    Employee, 
     EmployeeRequest, EmployeeResponse, 
     CreateEmployeeRequest, UpdateEmployeeRequest};
 
+#[cfg(feature = "server")]
+pub mod accounts;
+#[cfg(feature = "server")]
+pub mod backup;
+#[cfg(feature = "server")]
+pub mod cli;
+#[cfg(feature = "server")]
+pub mod config;
 #[cfg(feature = "server")]
 pub mod db;
+#[cfg(any(feature = "server", feature = "offline"))]
+pub mod employees;
+#[cfg(feature = "server")]
+pub mod api;
+#[cfg(feature = "server")]
+pub mod security;
+#[cfg(feature = "server")]
+pub mod telemetry;
+#[cfg(feature = "server")]
+pub mod health;
+#[cfg(feature = "server")]
+pub mod login_guard;
+#[cfg(feature = "server")]
+pub mod migrate;
+#[cfg(feature = "server")]
+pub mod metrics;
+#[cfg(feature = "server")]
+pub mod totp;
+#[cfg(all(test, feature = "server"))]
+pub mod testing;
+#[cfg(feature = "server")]
+use crate::models::auth::{SCOPE_EMPLOYEES_READ, SCOPE_EMPLOYEES_WRITE};
+
+pub mod auth;
+pub mod dashboard;
+pub mod jobs;
+pub mod ldap;
+pub mod mail;
+pub mod oidc;
+pub mod password;
+pub mod tokens;
+pub mod two_factor;
+pub mod users;
+pub mod webhooks;
 
 #[server]
 pub async fn get_employees(req: EmployeeRequest) -> Result<EmployeeResponse, ServerFnError> {
@@ -13,40 +54,9 @@ pub async fn get_employees(req: EmployeeRequest) -> Result<EmployeeResponse, Ser
     {
         let pool = db::connect_db().await
             .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_EMPLOYEES_READ).await?;
 
-        let offset = (req.page - 1) * req.page_size;
-        let search = format!("%{}%", req.search_term.unwrap_or_default());
-
-        let employees = sqlx::query_as!(
-            Employee,
-            r#"
-            SELECT id, first_name, last_name, email 
-            FROM employee 
-            WHERE first_name ILIKE $1 OR last_name ILIKE $1 OR email ILIKE $1
-            ORDER BY id ASC
-            LIMIT $2 OFFSET $3
-            "#,
-            search,
-            req.page_size,
-            offset
-        )
-        .fetch_all(&pool)
-        .await
-        .map_err(|e| ServerFnError::new(e.to_string()))?;
-
-        let total_count = sqlx::query_scalar!(
-            "SELECT COUNT(*) FROM employee WHERE first_name ILIKE $1 OR last_name ILIKE $1 OR email ILIKE $1",
-            search
-        )
-        .fetch_one(&pool)
-        .await
-        .map_err(|e| ServerFnError::new(e.to_string()))?
-        .unwrap_or(0);
-
-        Ok(EmployeeResponse {
-            employees,
-            total_count,
-        })
+        Ok(employees::list(&pool, &req).await?)
     }
     #[cfg(not(feature = "server"))]
     {
@@ -60,16 +70,9 @@ pub async fn add_employee(req: CreateEmployeeRequest) -> Result<(), ServerFnErro
     {
         let pool = db::connect_db().await
             .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
-        
-        sqlx::query!(
-            "INSERT INTO employee (first_name, last_name, email) VALUES ($1, $2, $3)",
-            req.first_name,
-            req.last_name,
-            req.email
-        )
-        .execute(&pool)
-        .await
-        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+        security::require_scope(&pool, SCOPE_EMPLOYEES_WRITE).await?;
+
+        employees::create(&pool, &req).await?;
 
         Ok(())
     }
@@ -83,20 +86,11 @@ pub async fn add_employee(req: CreateEmployeeRequest) -> Result<(), ServerFnErro
 pub async fn update_employee(req: UpdateEmployeeRequest) -> Result<(), ServerFnError> {
     #[cfg(feature = "server")]
     {
-        use sqlx::Error as SqlxError;
         let pool = db::connect_db().await
             .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
-        
-        sqlx::query!(
-            "UPDATE employee SET first_name = $1, last_name = $2, email = $3 WHERE id = $4",
-            req.first_name,
-            req.last_name,
-            req.email,
-            req.id
-        )
-        .execute(&pool)
-        .await
-        .map_err(|e: SqlxError| ServerFnError::new(format!("Database error: {}", e)))?;
+        security::require_scope(&pool, SCOPE_EMPLOYEES_WRITE).await?;
+
+        employees::update(&pool, &req).await?;
         
         Ok(())
     }
@@ -110,14 +104,28 @@ pub async fn update_employee(req: UpdateEmployeeRequest) -> Result<(), ServerFnE
 pub async fn delete_employee(id: i64) -> Result<(), ServerFnError> {
     #[cfg(feature = "server")]
     {
-        use sqlx::Error as SqlxError;
         let pool = db::connect_db().await
             .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
-        
-        sqlx::query!("DELETE FROM employee WHERE id = $1", id)
-            .execute(&pool)
-            .await
-            .map_err(|e: SqlxError| ServerFnError::new(format!("Database error: {}", e)))?;
+        security::require_scope(&pool, SCOPE_EMPLOYEES_WRITE).await?;
+
+        employees::delete(&pool, id).await?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    { Err(ServerFnError::new("Server function not available on client")) }
+}
+
+/// Undoes a recent `delete_employee`, with the same id and every field as before.
+#[server]
+pub async fn restore_employee(id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_EMPLOYEES_WRITE).await?;
+
+        employees::restore(&pool, id).await?;
 
         Ok(())
     }
@@ -0,0 +1,251 @@
+//! The HTTP side of the flow: discovery, authorization URL, code exchange and
+//! ID token checks.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use super::config::OidcConfig;
+use crate::server::security::generate_secret;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+// Tolerated clock difference to the IdP when checking `exp`
+const CLOCK_SKEW_SECS: i64 = 60;
+
+#[derive(Debug)]
+pub enum OidcError {
+    Http(String),
+    Provider(String),
+    InvalidToken(String),
+}
+
+impl fmt::Display for OidcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OidcError::Http(e) => write!(f, "Identity provider unreachable: {}", e),
+            OidcError::Provider(e) => write!(f, "Identity provider error: {}", e),
+            OidcError::InvalidToken(e) => write!(f, "Invalid ID token: {}", e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for OidcError {
+    fn from(e: reqwest::Error) -> Self {
+        OidcError::Http(e.to_string())
+    }
+}
+
+// The parts of `/.well-known/openid-configuration` we use
+#[derive(Debug, Deserialize)]
+pub struct Discovery {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub id_token: String,
+}
+
+/// What we take from the ID token and userinfo.
+#[derive(Debug, Clone)]
+pub struct Claims {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub preferred_username: Option<String>,
+    pub groups: Vec<String>,
+    // How the IdP authenticated the user, from the ID token only
+    pub amr: Vec<String>,
+    pub acr: Option<String>,
+}
+
+// One client for all IdP calls, so connections are pooled
+fn http() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| reqwest::Client::builder().timeout(HTTP_TIMEOUT).build().expect("HTTP client"))
+}
+
+/// Fetched once per process and issuer; only a successful result is cached.
+pub async fn discovery(cfg: &OidcConfig) -> Result<Arc<Discovery>, OidcError> {
+    static DISCOVERY: OnceLock<Mutex<HashMap<String, Arc<Discovery>>>> = OnceLock::new();
+    let cache = DISCOVERY.get_or_init(Default::default);
+    if let Some(discovery) = cache.lock().unwrap().get(&cfg.issuer_url) {
+        return Ok(discovery.clone());
+    }
+
+    let url = format!("{}/.well-known/openid-configuration", cfg.issuer_url);
+    let discovery: Discovery = http().get(url).send().await?.error_for_status()?.json().await?;
+    if discovery.issuer.trim_end_matches('/') != cfg.issuer_url {
+        return Err(OidcError::Provider(format!(
+            "discovery issuer '{}' does not match OIDC_ISSUER_URL",
+            discovery.issuer
+        )));
+    }
+    // The ID token is trusted because it comes from here, see validate_id_token
+    if !cfg.allow_insecure_http && !discovery.token_endpoint.starts_with("https://") {
+        return Err(OidcError::Provider(format!(
+            "token endpoint '{}' does not use https",
+            discovery.token_endpoint
+        )));
+    }
+
+    let discovery = Arc::new(discovery);
+    cache.lock().unwrap().insert(cfg.issuer_url.clone(), discovery.clone());
+    Ok(discovery)
+}
+
+/// PKCE verifier and its S256 challenge.
+pub fn pkce() -> (String, String) {
+    let verifier = generate_secret(64);
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+    (verifier, challenge)
+}
+
+pub fn authorize_url(cfg: &OidcConfig, discovery: &Discovery, state: &str, nonce: &str, challenge: &str) -> String {
+    reqwest::Url::parse_with_params(
+        &discovery.authorization_endpoint,
+        [
+            ("response_type", "code"),
+            ("client_id", cfg.client_id.as_str()),
+            ("redirect_uri", cfg.redirect_url.as_str()),
+            ("scope", cfg.scopes.as_str()),
+            ("state", state),
+            ("nonce", nonce),
+            ("code_challenge", challenge),
+            ("code_challenge_method", "S256"),
+        ],
+    )
+    .map(String::from)
+    .unwrap_or_else(|_| discovery.authorization_endpoint.clone())
+}
+
+pub async fn exchange_code(
+    cfg: &OidcConfig,
+    discovery: &Discovery,
+    code: &str,
+    verifier: &str,
+) -> Result<TokenResponse, OidcError> {
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", cfg.redirect_url.as_str()),
+        ("code_verifier", verifier),
+    ];
+    let mut request = http().post(&discovery.token_endpoint);
+    match &cfg.client_secret {
+        Some(secret) => request = request.basic_auth(&cfg.client_id, Some(secret)),
+        None => form.push(("client_id", cfg.client_id.as_str())),
+    }
+
+    let response = request.form(&form).send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(OidcError::Provider(format!("token endpoint returned {}: {}", status, body)));
+    }
+    Ok(response.json().await?)
+}
+
+/// Checks issuer, audience, expiry and nonce of the ID token and returns its claims.
+///
+/// The token comes straight from the token endpoint over TLS, which OpenID Connect
+/// Core (3.1.3.7) accepts in place of checking the signature. TLS is enforced in
+/// [`config`](super::config) and [`discovery`] unless `OIDC_ALLOW_INSECURE_HTTP`
+/// is set for local testing.
+pub fn validate_id_token(
+    cfg: &OidcConfig,
+    discovery: &Discovery,
+    id_token: &str,
+    nonce: &str,
+) -> Result<Value, OidcError> {
+    let invalid = |reason: &str| OidcError::InvalidToken(reason.to_string());
+
+    let payload = id_token.split('.').nth(1).ok_or_else(|| invalid("not a JWT"))?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|_| invalid("payload is not base64url"))?;
+    let claims: Value = serde_json::from_slice(&bytes).map_err(|_| invalid("payload is not JSON"))?;
+
+    if claims["iss"].as_str() != Some(discovery.issuer.as_str()) {
+        return Err(invalid("wrong issuer"));
+    }
+    let audience_ok = match &claims["aud"] {
+        Value::String(aud) => *aud == cfg.client_id,
+        Value::Array(auds) => auds.iter().any(|a| a.as_str() == Some(cfg.client_id.as_str())),
+        _ => false,
+    };
+    if !audience_ok {
+        return Err(invalid("wrong audience"));
+    }
+    let expires = claims["exp"].as_i64().ok_or_else(|| invalid("missing exp"))?;
+    if expires + CLOCK_SKEW_SECS < chrono::Utc::now().timestamp() {
+        return Err(invalid("expired"));
+    }
+    if claims["nonce"].as_str() != Some(nonce) {
+        return Err(invalid("nonce mismatch"));
+    }
+    if claims["sub"].as_str().is_none_or(str::is_empty) {
+        return Err(invalid("missing sub"));
+    }
+    Ok(claims)
+}
+
+pub async fn userinfo(discovery: &Discovery, access_token: &str) -> Result<Option<Value>, OidcError> {
+    let Some(endpoint) = &discovery.userinfo_endpoint else { return Ok(None) };
+    let info = http()
+        .get(endpoint)
+        .bearer_auth(access_token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(Some(info))
+}
+
+/// Combines ID token and userinfo claims, the ID token wins. Userinfo is
+/// ignored if it is about a different subject.
+pub fn claims(cfg: &OidcConfig, id_claims: &Value, userinfo: Option<&Value>) -> Claims {
+    let subject = id_claims["sub"].as_str().unwrap_or_default().to_string();
+    let userinfo = userinfo.filter(|info| info["sub"].as_str() == Some(subject.as_str()));
+    let lookup = |name: &str| -> Option<&Value> {
+        Some(&id_claims[name])
+            .filter(|v| !v.is_null())
+            .or_else(|| userinfo.map(|info| &info[name]).filter(|v| !v.is_null()))
+    };
+    let string = |name: &str| {
+        lookup(name)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+
+    // Providers send groups as an array, some as a single string
+    let groups = match lookup(&cfg.groups_claim) {
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        Some(Value::String(group)) => vec![group.clone()],
+        _ => Vec::new(),
+    };
+
+    Claims {
+        email: string("email").map(|e| e.to_lowercase()),
+        // Treated as verified only when the IdP says so
+        email_verified: lookup("email_verified").and_then(Value::as_bool).unwrap_or(false),
+        preferred_username: string("preferred_username"),
+        groups,
+        amr: id_claims["amr"].as_array().into_iter().flatten().filter_map(Value::as_str).map(str::to_string).collect(),
+        acr: id_claims["acr"].as_str().map(str::to_string),
+        subject,
+    }
+}
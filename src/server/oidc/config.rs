@@ -0,0 +1,124 @@
+//! SSO settings from the environment. SSO is off unless `OIDC_ISSUER_URL` is set
+//! and the `sso` feature is enabled.
+
+use std::sync::OnceLock;
+
+use super::client::Claims;
+use crate::models::auth::ROLE_EMPLOYEE;
+use crate::server::config as app_config;
+
+#[derive(Debug, Clone)]
+pub struct OidcConfig {
+    pub issuer_url: String,
+    pub client_id: String,
+    // Public clients rely on PKCE alone and have no secret
+    pub client_secret: Option<String>,
+    pub redirect_url: String,
+    pub scopes: String,
+    pub provider_name: String,
+    pub groups_claim: String,
+    // IdP group -> auth.roles name, from `OIDC_ROLE_MAP=group=ROLE,group=ROLE`
+    pub role_map: Vec<(String, String)>,
+    // Given when none of the user's groups is mapped, empty for "no roles"
+    pub default_role: Option<String>,
+    // Link an IdP account to an existing user with the same (verified) email.
+    // Opt-in: the IdP then decides who gets that user, and its roles
+    pub link_by_email: bool,
+    // ID token `amr` / `acr` values that prove the IdP did MFA, from
+    // `OIDC_MFA_AMR` / `OIDC_MFA_ACR`. Only then is local 2FA skipped
+    pub mfa_amr: Vec<String>,
+    pub mfa_acr: Vec<String>,
+    // Plain http to the IdP, for a local mock only: the ID token's signature is
+    // not checked, so it is only as trustworthy as the connection it came over
+    pub allow_insecure_http: bool,
+}
+
+impl OidcConfig {
+    /// Local roles for a set of IdP groups.
+    pub fn roles_for_groups(&self, groups: &[String]) -> Vec<String> {
+        let mut roles: Vec<String> = self
+            .role_map
+            .iter()
+            .filter(|(group, _)| groups.contains(group))
+            .map(|(_, role)| role.clone())
+            .collect();
+        roles.sort();
+        roles.dedup();
+
+        if roles.is_empty() {
+            roles.extend(self.default_role.clone());
+        }
+        roles
+    }
+
+    /// Whether the ID token shows an MFA the configuration trusts in place of local 2FA.
+    pub fn mfa_done(&self, claims: &Claims) -> bool {
+        claims.amr.iter().any(|method| self.mfa_amr.contains(method))
+            || claims.acr.as_ref().is_some_and(|acr| self.mfa_acr.contains(acr))
+    }
+}
+
+pub fn config() -> Option<&'static OidcConfig> {
+    static CONFIG: OnceLock<Option<OidcConfig>> = OnceLock::new();
+    CONFIG.get_or_init(from_env).as_ref()
+}
+
+fn from_env() -> Option<OidcConfig> {
+    if !app_config::get().features.sso {
+        return None;
+    }
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+    let issuer_url = var("OIDC_ISSUER_URL")?.trim_end_matches('/').to_string();
+    let allow_insecure_http = var("OIDC_ALLOW_INSECURE_HTTP").is_some_and(|v| v == "true");
+    if !issuer_url.starts_with("https://") && !allow_insecure_http {
+        tracing::warn!("OIDC_ISSUER_URL does not use https, SSO is disabled (OIDC_ALLOW_INSECURE_HTTP=true allows it for local testing)");
+        return None;
+    }
+    let Some(client_id) = var("OIDC_CLIENT_ID") else {
+        tracing::warn!("OIDC_ISSUER_URL is set but OIDC_CLIENT_ID is missing, SSO is disabled");
+        return None;
+    };
+
+    let redirect_url = var("OIDC_REDIRECT_URL")
+        .unwrap_or_else(|| format!("{}/auth/oidc/callback", app_config::get().base_url()));
+
+    let role_map = var("OIDC_ROLE_MAP")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(group, role)| (group.trim().to_string(), role.trim().to_string()))
+        .filter(|(group, role)| !group.is_empty() && !role.is_empty())
+        .collect();
+
+    let list = |name: &str| -> Vec<String> {
+        var(name)
+            .unwrap_or_default()
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    };
+
+    let default_role = match var("OIDC_DEFAULT_ROLE") {
+        Some(role) if role.eq_ignore_ascii_case("none") => None,
+        Some(role) => Some(role),
+        None => Some(ROLE_EMPLOYEE.to_string()),
+    };
+
+    Some(OidcConfig {
+        issuer_url,
+        client_id,
+        client_secret: var("OIDC_CLIENT_SECRET"),
+        redirect_url,
+        scopes: var("OIDC_SCOPES").unwrap_or_else(|| "openid email profile".to_string()),
+        provider_name: var("OIDC_PROVIDER_NAME").unwrap_or_else(|| "SSO".to_string()),
+        groups_claim: var("OIDC_GROUPS_CLAIM").unwrap_or_else(|| "groups".to_string()),
+        role_map,
+        default_role,
+        link_by_email: var("OIDC_LINK_BY_EMAIL").is_some_and(|v| v == "true"),
+        mfa_amr: list("OIDC_MFA_AMR"),
+        mfa_acr: list("OIDC_MFA_ACR"),
+        allow_insecure_http,
+    })
+}
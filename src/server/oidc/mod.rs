@@ -0,0 +1,34 @@
+//! OpenID Connect single sign-on (authorization code flow with PKCE).
+//!
+//! The browser is redirected to the IdP by `/auth/oidc/login` and comes back to
+//! `/auth/oidc/callback`, both plain axum routes from [`routes`]. Users are matched
+//! by their IdP subject, provisioned on first login and get their roles from the
+//! IdP's groups on every login.
+
+use dioxus::prelude::*;
+use crate::models::auth::SsoProvider;
+
+#[cfg(feature = "server")]
+pub mod client;
+#[cfg(feature = "server")]
+pub mod config;
+#[cfg(feature = "server")]
+pub mod provision;
+#[cfg(feature = "server")]
+pub mod routes;
+
+/// The configured IdP for the login dialog, `None` when SSO is off.
+#[server]
+pub async fn sso_provider() -> Result<Option<SsoProvider>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        Ok(config::config().map(|cfg| SsoProvider {
+            name: cfg.provider_name.clone(),
+            login_url: routes::LOGIN_PATH.to_string(),
+        }))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
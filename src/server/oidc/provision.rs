@@ -0,0 +1,192 @@
+//! Matches an IdP account to a local user, creating it on first login, and
+//! syncs the user's roles from the IdP groups.
+
+use sqlx::PgPool;
+use std::fmt;
+
+use super::client::Claims;
+use super::config::OidcConfig;
+use crate::server::accounts::{self, AccountError, USERNAME_MAX_LEN};
+
+// bcrypt never produces this, so SSO-only users can't sign in with a password
+// until they set one through "Forgot password?"
+const NO_PASSWORD: &str = "!sso";
+
+#[derive(Debug)]
+pub enum ProvisionError {
+    Disabled,
+    Locked,
+    UnknownRole(String),
+    Database(sqlx::Error),
+}
+
+impl fmt::Display for ProvisionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProvisionError::Disabled => write!(f, "This account is disabled"),
+            ProvisionError::Locked => write!(f, "This account is locked after too many failed logins"),
+            ProvisionError::UnknownRole(role) => write!(f, "OIDC_ROLE_MAP refers to unknown role '{}'", role),
+            ProvisionError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for ProvisionError {
+    fn from(e: sqlx::Error) -> Self {
+        ProvisionError::Database(e)
+    }
+}
+
+// Only what `accounts::sync_roles` can fail with
+impl From<AccountError> for ProvisionError {
+    fn from(e: AccountError) -> Self {
+        match e {
+            AccountError::UnknownRole(role) => ProvisionError::UnknownRole(role),
+            AccountError::Database(e) => ProvisionError::Database(e),
+            other => ProvisionError::Database(sqlx::Error::Protocol(other.to_string())),
+        }
+    }
+}
+
+/// Returns `(user id, username)` of the local user for `claims`.
+pub async fn provision(
+    pool: &PgPool,
+    cfg: &OidcConfig,
+    issuer: &str,
+    claims: &Claims,
+) -> Result<(i64, String), ProvisionError> {
+    let mut tx = pool.begin().await?;
+
+    let linked = sqlx::query_scalar!(
+        "SELECT user_id FROM auth.user_identities WHERE issuer = $1 AND subject = $2 FOR UPDATE",
+        issuer,
+        claims.subject
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let user_id = match linked {
+        Some(user_id) => {
+            sqlx::query!(
+                r#"
+                UPDATE auth.user_identities SET email = $3, last_login_at = now()
+                WHERE issuer = $1 AND subject = $2
+                "#,
+                issuer,
+                claims.subject,
+                claims.email
+            )
+            .execute(&mut *tx)
+            .await?;
+            user_id
+        }
+        None => {
+            // An unverified email could belong to anyone, so it never links accounts
+            let existing = match (&claims.email, cfg.link_by_email && claims.email_verified) {
+                (Some(email), true) => {
+                    sqlx::query_scalar!("SELECT id FROM auth.users WHERE lower(email) = $1", email)
+                        .fetch_optional(&mut *tx)
+                        .await?
+                }
+                _ => None,
+            };
+            let user_id = match existing {
+                Some(user_id) => user_id,
+                None => create_user(&mut tx, claims).await?,
+            };
+
+            sqlx::query!(
+                r#"
+                INSERT INTO auth.user_identities (user_id, issuer, subject, email, last_login_at)
+                VALUES ($1, $2, $3, $4, now())
+                "#,
+                user_id,
+                issuer,
+                claims.subject,
+                claims.email
+            )
+            .execute(&mut *tx)
+            .await?;
+            user_id
+        }
+    };
+
+    let user = sqlx::query!(
+        "SELECT username, enabled, locked_until FROM auth.users WHERE id = $1",
+        user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if !user.enabled {
+        return Err(ProvisionError::Disabled);
+    }
+    if user.locked_until.is_some_and(|until| until > chrono::Utc::now()) {
+        return Err(ProvisionError::Locked);
+    }
+
+    // The IdP is the source of truth for roles
+    accounts::sync_roles(&mut tx, user_id, &cfg.roles_for_groups(&claims.groups)).await?;
+
+    tx.commit().await?;
+    Ok((user_id, user.username))
+}
+
+async fn create_user(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, claims: &Claims) -> Result<i64, ProvisionError> {
+    let base = username_base(claims);
+
+    // "jane", "jane-2", "jane-3", ... whichever is free first
+    let taken: Vec<String> = sqlx::query_scalar!(
+        "SELECT lower(username) AS \"username!\" FROM auth.users WHERE lower(username) LIKE $1 || '%'",
+        base
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    let username = std::iter::once(base.clone())
+        .chain((2..).map(|n| format!("{}-{}", base, n)))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded candidates");
+
+    // Leave the email out if a different user already has it
+    let email_free = match &claims.email {
+        Some(email) => sqlx::query_scalar!("SELECT id FROM auth.users WHERE lower(email) = $1", email)
+            .fetch_optional(&mut **tx)
+            .await?
+            .is_none(),
+        None => false,
+    };
+
+    let user_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO auth.users (username, password, enabled, email)
+        VALUES ($1, $2, true, $3)
+        RETURNING id
+        "#,
+        username,
+        NO_PASSWORD,
+        claims.email.clone().filter(|_| email_free)
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(user_id)
+}
+
+/// Lower-case username from `preferred_username`, the email's local part or the subject.
+fn username_base(claims: &Claims) -> String {
+    let source = claims
+        .preferred_username
+        .clone()
+        .or_else(|| claims.email.as_ref().and_then(|e| e.split('@').next()).map(str::to_string))
+        .unwrap_or_else(|| format!("sso-{}", claims.subject));
+
+    let cleaned: String = source
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '-' })
+        .take(USERNAME_MAX_LEN - 4)
+        .collect();
+    if cleaned.trim_matches('-').is_empty() {
+        format!("sso-{}", &claims.subject.chars().take(12).collect::<String>())
+    } else {
+        cleaned
+    }
+}
@@ -0,0 +1,197 @@
+//! Browser redirects of the SSO flow, mounted at `/auth/oidc`.
+
+use axum::extract::{ConnectInfo, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{AppendHeaders, Html, IntoResponse, Redirect, Response};
+use axum::routing::get;
+use axum::Router;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::net::SocketAddr;
+
+use super::{client, config, provision};
+use crate::server::{login_guard, security, two_factor};
+use crate::Route;
+
+pub const LOGIN_PATH: &str = "/auth/oidc/login";
+
+// Binds the pending login to the browser that started it
+const STATE_COOKIE: &str = "ems_oidc_state";
+const STATE_TTL_MINUTES: i32 = 10;
+
+pub fn router(pool: PgPool) -> Router {
+    Router::new()
+        .route("/login", get(login))
+        .route("/callback", get(callback))
+        .with_state(pool)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CallbackParams {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+/// Plain HTML page for failures, the user lands here instead of the app.
+fn failure(status: StatusCode, message: impl std::fmt::Display) -> Response {
+    let message = message.to_string().replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    let page = format!(
+        "<!doctype html><html><head><title>Sign-in failed</title></head>\
+         <body style=\"font-family: sans-serif; max-width: 40rem; margin: 4rem auto\">\
+         <h1>Single sign-on failed</h1><p>{}</p><p><a href=\"/\">Back to the app</a></p></body></html>",
+        message
+    );
+    (status, [(header::SET_COOKIE, clear_state_cookie())], Html(page)).into_response()
+}
+
+fn clear_state_cookie() -> String {
+    format!("{}=; Path=/auth/oidc; HttpOnly; SameSite=Lax; Max-Age=0{}", STATE_COOKIE, security::secure_attribute())
+}
+
+async fn login(State(pool): State<PgPool>) -> Response {
+    let Some(cfg) = config::config() else {
+        return failure(StatusCode::NOT_FOUND, "Single sign-on is not configured");
+    };
+    let discovery = match client::discovery(cfg).await {
+        Ok(discovery) => discovery,
+        Err(e) => return failure(StatusCode::BAD_GATEWAY, e),
+    };
+
+    let state = security::generate_secret(32);
+    let nonce = security::generate_secret(32);
+    let (verifier, challenge) = client::pkce();
+
+    let stored = sqlx::query!(
+        r#"
+        INSERT INTO auth.oidc_login_states (state_hash, nonce, code_verifier, expires_at)
+        VALUES ($1, $2, $3, now() + make_interval(mins => $4))
+        "#,
+        security::hash_token(&state),
+        nonce,
+        verifier,
+        STATE_TTL_MINUTES
+    )
+    .execute(&pool)
+    .await;
+    if let Err(e) = stored {
+        return failure(StatusCode::INTERNAL_SERVER_ERROR, e);
+    }
+
+    // SameSite=Lax still sends the cookie on the IdP's top-level redirect back
+    let cookie = format!(
+        "{}={}; Path=/auth/oidc; HttpOnly; SameSite=Lax; Max-Age={}{}",
+        STATE_COOKIE,
+        state,
+        STATE_TTL_MINUTES * 60,
+        security::secure_attribute()
+    );
+    let location = client::authorize_url(cfg, &discovery, &state, &nonce, &challenge);
+    ([(header::SET_COOKIE, cookie)], Redirect::to(&location)).into_response()
+}
+
+async fn callback(
+    State(pool): State<PgPool>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(params): Query<CallbackParams>,
+) -> Response {
+    let Some(cfg) = config::config() else {
+        return failure(StatusCode::NOT_FOUND, "Single sign-on is not configured");
+    };
+    if let Some(error) = params.error {
+        return failure(StatusCode::UNAUTHORIZED, params.error_description.unwrap_or(error));
+    }
+    let (Some(code), Some(state)) = (params.code, params.state) else {
+        return failure(StatusCode::BAD_REQUEST, "Missing code or state");
+    };
+    if security::cookie(&headers, STATE_COOKIE) != Some(state.as_str()) {
+        return failure(StatusCode::BAD_REQUEST, "This sign-in was started in a different browser, please try again");
+    }
+
+    // Single use: the row is gone whether or not the rest succeeds
+    let pending = sqlx::query!(
+        r#"
+        DELETE FROM auth.oidc_login_states
+        WHERE state_hash = $1
+        RETURNING nonce, code_verifier, expires_at > now() AS "valid!"
+        "#,
+        security::hash_token(&state)
+    )
+    .fetch_optional(&pool)
+    .await;
+    let pending = match pending {
+        Ok(Some(pending)) if pending.valid => pending,
+        Ok(_) => return failure(StatusCode::BAD_REQUEST, "The sign-in took too long, please try again"),
+        Err(e) => return failure(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+
+    let claims = match exchange(cfg, &code, &pending.code_verifier, &pending.nonce).await {
+        Ok(claims) => claims,
+        Err(e) => return failure(StatusCode::BAD_GATEWAY, e),
+    };
+    let issuer = match client::discovery(cfg).await {
+        Ok(discovery) => discovery.issuer.clone(),
+        Err(e) => return failure(StatusCode::BAD_GATEWAY, e),
+    };
+
+    let (user_id, username) = match provision::provision(&pool, cfg, &issuer, &claims).await {
+        Ok(user) => user,
+        Err(e @ (provision::ProvisionError::Disabled | provision::ProvisionError::Locked)) => {
+            return failure(StatusCode::FORBIDDEN, e)
+        }
+        Err(e) => return failure(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+
+    // Local 2FA applies as for a password login, unless the ID token shows an MFA
+    // that is configured as good enough. The second step happens in the app.
+    let (enrolled, required) = match two_factor::state(&pool, user_id).await {
+        Ok(state) => state,
+        Err(e) => return failure(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+    if (enrolled || required) && !cfg.mfa_done(&claims) {
+        let purpose = if enrolled { two_factor::PURPOSE_VERIFY } else { two_factor::PURPOSE_ENROLL };
+        let challenge = match two_factor::create_challenge(&pool, user_id, purpose).await {
+            Ok(challenge) => challenge,
+            Err(e) => return failure(StatusCode::INTERNAL_SERVER_ERROR, e),
+        };
+        let next = Route::SsoTwoFactorView { challenge, step: purpose.to_string() };
+        return ([(header::SET_COOKIE, clear_state_cookie())], Redirect::to(&next.to_string())).into_response();
+    }
+
+    let ip = login_guard::client_ip(&headers, Some(peer));
+    let session = match login_guard::record_success(&pool, &login_guard::normalize(&username), &ip, user_id).await {
+        Ok(()) => security::create_session(&pool, user_id).await,
+        Err(e) => Err(e),
+    };
+    match session {
+        Ok(raw) => (
+            AppendHeaders([
+                (header::SET_COOKIE, security::session_cookie(&raw)),
+                (header::SET_COOKIE, clear_state_cookie()),
+            ]),
+            Redirect::to("/"),
+        )
+            .into_response(),
+        Err(e) => failure(StatusCode::INTERNAL_SERVER_ERROR, e),
+    }
+}
+
+async fn exchange(
+    cfg: &config::OidcConfig,
+    code: &str,
+    verifier: &str,
+    nonce: &str,
+) -> Result<client::Claims, client::OidcError> {
+    let discovery = client::discovery(cfg).await?;
+    let tokens = client::exchange_code(cfg, &discovery, code, verifier).await?;
+    let id_claims = client::validate_id_token(cfg, &discovery, &tokens.id_token, nonce)?;
+
+    // Groups and email are often only in userinfo; a failing userinfo call just means fewer claims
+    let userinfo = client::userinfo(&discovery, &tokens.access_token).await.unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "OIDC userinfo failed");
+        None
+    });
+    Ok(client::claims(cfg, &id_claims, userinfo.as_ref()))
+}
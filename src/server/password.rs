@@ -0,0 +1,273 @@
+use dioxus::prelude::*;
+use crate::models::auth::{ChangePasswordRequest, ResetPasswordRequest};
+
+#[cfg(feature = "server")]
+use super::{config, db, mail::notify, security};
+#[cfg(feature = "server")]
+use crate::models::{auth::password_policy_errors, email::TEMPLATE_PASSWORD_RESET};
+#[cfg(feature = "server")]
+use super::jobs::queue::{self, Job, JobError};
+#[cfg(feature = "server")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "server")]
+use sqlx::PgPool;
+#[cfg(feature = "server")]
+use std::collections::BTreeMap;
+
+pub const RESET_TOKEN_TTL_MINUTES: i32 = 30;
+
+#[cfg(feature = "server")]
+const RESET_TOKEN_LEN: usize = 48;
+
+#[cfg(feature = "server")]
+pub const KIND_SEND_RESET: &str = "email.password_reset";
+
+// Only the token row: the link is made when the mail is sent, so the raw token
+// never sits in the jobs table
+#[cfg(feature = "server")]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResetMailPayload {
+    token_id: i64,
+}
+
+#[cfg(feature = "server")]
+fn check_policy(password: &str, username: &str) -> Result<(), ServerFnError> {
+    let errors = password_policy_errors(password, username);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ServerFnError::ServerError {
+            message: format!("Password does not meet the policy: {}", errors.join(", ")),
+            code: 422,
+            details: None,
+        })
+    }
+}
+
+/// Mails a reset link to the user with this username or email address.
+///
+/// Always succeeds for well-formed requests so the answer doesn't reveal which
+/// accounts exist. Requesting a new link invalidates older unused ones.
+#[server]
+pub async fn request_password_reset(login: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+
+        // One user's username can be another one's email address; the email wins
+        let user = sqlx::query!(
+            r#"
+            SELECT id, username, email AS "email!"
+            FROM auth.users
+            WHERE (username = $1 OR lower(email) = lower($1)) AND enabled AND email IS NOT NULL
+            ORDER BY lower(email) = lower($1) DESC, id
+            LIMIT 1
+            "#,
+            login.trim()
+        )
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let Some(user) = user else { return Ok(()) };
+
+        let mut tx = pool.begin().await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        sqlx::query!("DELETE FROM auth.password_reset_tokens WHERE user_id = $1 AND used_at IS NULL", user.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        // Nobody knows a token for this hash; send_reset replaces it with the one it mails
+        let token_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO auth.password_reset_tokens (user_id, token_hash, expires_at)
+            VALUES ($1, $2, now() + make_interval(mins => $3))
+            RETURNING id
+            "#,
+            user.id,
+            security::hash_token(&security::generate_secret(RESET_TOKEN_LEN)),
+            RESET_TOKEN_TTL_MINUTES
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        queue::enqueue(&mut *tx, KIND_SEND_RESET, &ResetMailPayload { token_id })
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| ServerFnError::new(e.to_string()))?;
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Handler for `email.password_reset` jobs. Every attempt puts a fresh token
+/// on the row and mails the link with it, so the token is only ever in the
+/// mail. Nothing is sent once the row was used, expired or superseded.
+#[cfg(feature = "server")]
+pub async fn send_reset(pool: &PgPool, job: &Job) -> Result<(), JobError> {
+    let payload: ResetMailPayload = job.payload()?;
+    let raw = security::generate_secret(RESET_TOKEN_LEN);
+
+    let user = sqlx::query!(
+        r#"
+        UPDATE auth.password_reset_tokens t SET token_hash = $2
+        FROM auth.users u
+        WHERE t.id = $1 AND u.id = t.user_id
+          AND t.used_at IS NULL AND t.expires_at > now()
+          AND u.enabled AND u.email IS NOT NULL
+        RETURNING u.username, u.email AS "email!"
+        "#,
+        payload.token_id,
+        security::hash_token(&raw)
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(user) = user else { return Ok(()) };
+
+    let vars = BTreeMap::from([
+        ("username".to_string(), user.username),
+        ("reset_url".to_string(), format!("{}/reset-password?token={}", config::get().base_url(), raw)),
+        ("expires_in_minutes".to_string(), RESET_TOKEN_TTL_MINUTES.to_string()),
+    ]);
+    notify::send_now(pool, TEMPLATE_PASSWORD_RESET, &user.email, &vars).await
+}
+
+/// Sets a new password using a mailed reset token and signs the user out everywhere.
+#[server]
+pub async fn reset_password(req: ResetPasswordRequest) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+
+        let mut tx = pool.begin().await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        // FOR UPDATE so two concurrent requests can't both use the same token
+        let token = sqlx::query!(
+            r#"
+            SELECT t.id, t.user_id, u.username
+            FROM auth.password_reset_tokens t
+            JOIN auth.users u ON u.id = t.user_id
+            WHERE t.token_hash = $1
+              AND t.used_at IS NULL
+              AND t.expires_at > now()
+              AND u.enabled
+            FOR UPDATE OF t
+            "#,
+            security::hash_token(req.token.trim())
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| ServerFnError::ServerError {
+            message: "This reset link is invalid or has expired".to_string(),
+            code: 400,
+            details: None,
+        })?;
+
+        check_policy(&req.new_password, &token.username)?;
+        let hash = security::hash_password(&req.new_password).await?;
+
+        sqlx::query!(
+            "UPDATE auth.users SET password = $2, password_changed_at = now() WHERE id = $1",
+            token.user_id,
+            hash
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        sqlx::query!("UPDATE auth.password_reset_tokens SET used_at = now() WHERE id = $1", token.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        sqlx::query!("DELETE FROM auth.sessions WHERE user_id = $1", token.user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| ServerFnError::new(e.to_string()))?;
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Changes the signed-in user's password. Other sessions of the user are ended,
+/// the current one stays signed in.
+#[server]
+pub async fn change_password(req: ChangePasswordRequest) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+
+        let principal = security::require_session(&pool).await?;
+
+        let current_hash = sqlx::query_scalar!("SELECT password FROM auth.users WHERE id = $1", principal.user_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        if !security::verify_password(&req.current_password, Some(&current_hash)).await? {
+            return Err(ServerFnError::ServerError {
+                message: "Current password is incorrect".to_string(),
+                code: 422,
+                details: None,
+            });
+        }
+        if req.new_password == req.current_password {
+            return Err(ServerFnError::ServerError {
+                message: "New password must differ from the current one".to_string(),
+                code: 422,
+                details: None,
+            });
+        }
+        check_policy(&req.new_password, &principal.username)?;
+        let hash = security::hash_password(&req.new_password).await?;
+
+        let headers = security::request_headers();
+        let current_session = security::cookie(&headers, security::SESSION_COOKIE)
+            .map(security::hash_session_token)
+            .unwrap_or_default();
+
+        let mut tx = pool.begin().await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        sqlx::query!(
+            "UPDATE auth.users SET password = $2, password_changed_at = now() WHERE id = $1",
+            principal.user_id,
+            hash
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        sqlx::query!(
+            "DELETE FROM auth.sessions WHERE user_id = $1 AND token_hash <> $2",
+            principal.user_id,
+            current_session
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| ServerFnError::new(e.to_string()))?;
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
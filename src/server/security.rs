@@ -0,0 +1,383 @@
+//! Authentication shared by the server functions and the REST API.
+//!
+//! A caller is identified either by a `Bearer` API token or by the session
+//! cookie set at login. Both resolve to a [`Principal`] whose scopes decide
+//! what the caller may do.
+
+use axum::extract::ConnectInfo;
+use axum::http::{header, HeaderMap, HeaderValue};
+use dioxus::fullstack::FullstackContext;
+use dioxus::prelude::ServerFnError;
+use hmac::{Hmac, Mac};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::fmt;
+use std::net::SocketAddr;
+
+use super::config;
+use crate::models::auth::{scopes_for_roles, CurrentUser};
+
+pub const SESSION_COOKIE: &str = "ems_session";
+
+// Raw API tokens look like `ems_<40 alphanumerics>`
+const API_TOKEN_PREFIX: &str = "ems_";
+const API_TOKEN_LEN: usize = 40;
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+// bcrypt of "password", checked for unknown users so the response time
+// doesn't reveal which usernames exist
+const DUMMY_PASSWORD_HASH: &str = "$2a$10$qeS0HEh7urweMojsnwNAR.vcXJeXR1UcMRZ2WcGQl9YeuspUdgF.q";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuthMethod {
+    Session,
+    ApiToken { token_id: i64 },
+}
+
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub user_id: i64,
+    pub username: String,
+    pub roles: Vec<String>,
+    pub scopes: Vec<String>,
+    pub method: AuthMethod,
+}
+
+impl Principal {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+
+    pub fn require_scope(&self, scope: &str) -> Result<(), AuthError> {
+        if self.has_scope(scope) {
+            Ok(())
+        } else {
+            Err(AuthError::Forbidden(format!("Missing scope '{}'", scope)))
+        }
+    }
+
+    pub fn to_current_user(&self) -> CurrentUser {
+        CurrentUser {
+            id: self.user_id,
+            username: self.username.clone(),
+            roles: self.roles.clone(),
+            scopes: self.scopes.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AuthError {
+    Unauthenticated,
+    Forbidden(String),
+    Database(sqlx::Error),
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Unauthenticated => write!(f, "Authentication required"),
+            AuthError::Forbidden(reason) => write!(f, "Forbidden: {}", reason),
+            AuthError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for AuthError {
+    fn from(e: sqlx::Error) -> Self {
+        AuthError::Database(e)
+    }
+}
+
+impl From<AuthError> for ServerFnError {
+    fn from(e: AuthError) -> Self {
+        let code = match &e {
+            AuthError::Unauthenticated => 401,
+            AuthError::Forbidden(_) => 403,
+            AuthError::Database(_) => 500,
+        };
+        ServerFnError::ServerError { message: e.to_string(), code, details: None }
+    }
+}
+
+pub fn generate_secret(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// SHA-256 is enough here because tokens are long random strings, not passwords.
+pub fn hash_token(raw: &str) -> String {
+    hex::encode(Sha256::digest(raw.as_bytes()))
+}
+
+/// Hash of a session cookie. Keyed with `session.secret` when one is configured,
+/// so a copy of `auth.sessions` alone can't be matched against guessed tokens.
+pub fn hash_session_token(raw: &str) -> String {
+    match &config::get().session.secret {
+        Some(secret) => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+            mac.update(raw.as_bytes());
+            hex::encode(mac.finalize().into_bytes())
+        }
+        None => hash_token(raw),
+    }
+}
+
+/// Checks `password` against a bcrypt hash off the async runtime. Pass `None` for
+/// unknown users, the check then runs against a dummy hash and always fails.
+pub async fn verify_password(password: &str, hash: Option<&str>) -> Result<bool, ServerFnError> {
+    let password = password.to_string();
+    let known = hash.is_some();
+    let hash = hash.unwrap_or(DUMMY_PASSWORD_HASH).to_string();
+    let valid = tokio::task::spawn_blocking(move || bcrypt::verify(password, &hash).unwrap_or(false))
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(known && valid)
+}
+
+pub async fn hash_password(password: &str) -> Result<String, ServerFnError> {
+    let password = password.to_string();
+    tokio::task::spawn_blocking(move || bcrypt::hash(password, bcrypt::DEFAULT_COST))
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Returns `(raw token, display prefix, hash)` for a new API token.
+pub fn new_api_token() -> (String, String, String) {
+    let raw = format!("{}{}", API_TOKEN_PREFIX, generate_secret(API_TOKEN_LEN));
+    let prefix = raw[..DISPLAY_PREFIX_LEN].to_string();
+    let hash = hash_token(&raw);
+    (raw, prefix, hash)
+}
+
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+pub fn cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Roles of a user, used for both login and token resolution.
+pub async fn user_roles(pool: &PgPool, user_id: i64) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT r.name
+        FROM auth.user_roles ur
+        JOIN auth.roles r ON r.id = ur.role_id
+        WHERE ur.user_id = $1
+        ORDER BY r.name
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Resolves the caller from the request headers.
+///
+/// A `Bearer` token takes precedence over the session cookie. An invalid,
+/// expired or revoked token is rejected instead of falling back to the cookie.
+pub async fn authenticate(pool: &PgPool, headers: &HeaderMap) -> Result<Option<Principal>, AuthError> {
+    if let Some(raw) = bearer_token(headers) {
+        return authenticate_api_token(pool, raw).await.map(Some);
+    }
+    if let Some(raw) = cookie(headers, SESSION_COOKIE) {
+        return authenticate_session(pool, raw).await;
+    }
+    Ok(None)
+}
+
+pub async fn require(pool: &PgPool, headers: &HeaderMap, scope: &str) -> Result<Principal, AuthError> {
+    let principal = authenticate(pool, headers).await?.ok_or(AuthError::Unauthenticated)?;
+    principal.require_scope(scope)?;
+    Ok(principal)
+}
+
+async fn authenticate_api_token(pool: &PgPool, raw: &str) -> Result<Principal, AuthError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT t.id AS token_id, t.scopes, u.id AS user_id, u.username
+        FROM auth.api_tokens t
+        JOIN auth.users u ON u.id = t.user_id
+        WHERE t.token_hash = $1
+          AND t.revoked_at IS NULL
+          AND t.expires_at > now()
+          AND u.enabled
+        "#,
+        hash_token(raw)
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AuthError::Unauthenticated)?;
+
+    sqlx::query!("UPDATE auth.api_tokens SET last_used_at = now() WHERE id = $1", row.token_id)
+        .execute(pool)
+        .await?;
+
+    // A token can never do more than its user's roles allow
+    let roles = user_roles(pool, row.user_id).await?;
+    let scopes = scopes_for_roles(&roles)
+        .into_iter()
+        .filter(|s| row.scopes.contains(s))
+        .collect();
+
+    Ok(Principal {
+        user_id: row.user_id,
+        username: row.username,
+        roles,
+        scopes,
+        method: AuthMethod::ApiToken { token_id: row.token_id },
+    })
+}
+
+async fn authenticate_session(pool: &PgPool, raw: &str) -> Result<Option<Principal>, AuthError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT u.id, u.username
+        FROM auth.sessions s
+        JOIN auth.users u ON u.id = s.user_id
+        WHERE s.token_hash = $1
+          AND s.expires_at > now()
+          AND u.enabled
+        "#,
+        hash_session_token(raw)
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    // An unknown or expired cookie just means "not signed in"
+    let Some(row) = row else { return Ok(None) };
+
+    let roles = user_roles(pool, row.id).await?;
+    let scopes = scopes_for_roles(&roles);
+
+    Ok(Some(Principal {
+        user_id: row.id,
+        username: row.username,
+        roles,
+        scopes,
+        method: AuthMethod::Session,
+    }))
+}
+
+/// Creates a session row and returns the raw token for the cookie.
+pub async fn create_session(pool: &PgPool, user_id: i64) -> Result<String, sqlx::Error> {
+    let raw = generate_secret(48);
+    sqlx::query!(
+        r#"
+        INSERT INTO auth.sessions (user_id, token_hash, expires_at)
+        VALUES ($1, $2, now() + make_interval(hours => $3))
+        "#,
+        user_id,
+        hash_session_token(&raw),
+        config::get().session.ttl_hours
+    )
+    .execute(pool)
+    .await?;
+    Ok(raw)
+}
+
+// ---------------------------------------------------------------------------
+// Helpers for `#[server]` functions, which read the current request through
+// the Dioxus fullstack context instead of axum extractors.
+// ---------------------------------------------------------------------------
+
+pub fn request_headers() -> HeaderMap {
+    FullstackContext::current()
+        .map(|ctx| ctx.parts_mut().headers.clone())
+        .unwrap_or_default()
+}
+
+/// The peer of the connection, see [`super::login_guard::client_ip`].
+pub fn request_peer() -> Option<SocketAddr> {
+    FullstackContext::current().and_then(|ctx| {
+        ctx.parts_mut().extensions.get::<ConnectInfo<SocketAddr>>().map(|info| info.0)
+    })
+}
+
+pub async fn current_principal(pool: &PgPool) -> Result<Option<Principal>, ServerFnError> {
+    Ok(authenticate(pool, &request_headers()).await?)
+}
+
+pub async fn require_scope(pool: &PgPool, scope: &str) -> Result<Principal, ServerFnError> {
+    Ok(require(pool, &request_headers(), scope).await?)
+}
+
+/// The caller, who must be signed in through the browser. Used for account
+/// changes (password, 2FA) that API tokens must not be able to make.
+pub async fn require_session(pool: &PgPool) -> Result<Principal, ServerFnError> {
+    let principal = current_principal(pool).await?.ok_or(AuthError::Unauthenticated)?;
+    if principal.method != AuthMethod::Session {
+        return Err(AuthError::Forbidden("Only possible from a login session".to_string()).into());
+    }
+    Ok(principal)
+}
+
+/// Final step of every login: creates the session, sets the cookie and returns the user.
+pub async fn start_session(pool: &PgPool, user_id: i64, username: String) -> Result<CurrentUser, ServerFnError> {
+    let session = create_session(pool, user_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    set_session_cookie(&session);
+
+    let roles = user_roles(pool, user_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let scopes = scopes_for_roles(&roles);
+
+    Ok(CurrentUser { id: user_id, username, roles, scopes })
+}
+
+/// `; Secure` when the app is served over https, so browsers never send the
+/// cookie over plain http. Left out otherwise, or local http logins would break.
+pub fn secure_attribute() -> &'static str {
+    if config::get().base_url().starts_with("https://") {
+        "; Secure"
+    } else {
+        ""
+    }
+}
+
+/// `Set-Cookie` value for a new session, also used by the SSO callback.
+pub fn session_cookie(raw: &str) -> String {
+    format!(
+        "{}={}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}{}",
+        SESSION_COOKIE,
+        raw,
+        config::get().session.ttl_hours * 3600,
+        secure_attribute()
+    )
+}
+
+pub fn set_session_cookie(raw: &str) {
+    set_cookie(&session_cookie(raw));
+}
+
+pub fn clear_session_cookie() {
+    set_cookie(&format!("{}=; Path=/; HttpOnly; SameSite=Lax; Max-Age=0{}", SESSION_COOKIE, secure_attribute()));
+}
+
+fn set_cookie(value: &str) {
+    if let (Some(ctx), Ok(value)) = (FullstackContext::current(), HeaderValue::from_str(value)) {
+        ctx.add_response_header(header::SET_COOKIE, value);
+    }
+}
@@ -0,0 +1,106 @@
+//! Structured logging with `tracing`.
+//!
+//! Every HTTP request runs inside a `request` span carrying a request ID, so
+//! everything logged while handling it (server functions, sqlx statements,
+//! slow-query warnings) can be correlated. The ID is taken from an incoming
+//! `X-Request-Id` header or generated, and returned in the response.
+
+use axum::body::{to_bytes, Body};
+use axum::extract::Request;
+use axum::http::{header, HeaderName, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use rand::Rng;
+use std::time::Instant;
+use tracing::Instrument;
+use tracing_subscriber::EnvFilter;
+
+use super::config::LogConfig;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+const REQUEST_ID_MAX_LEN: usize = 64;
+// Server function error bodies are a short message; anything larger isn't logged
+const ERROR_BODY_LOG_LIMIT: usize = 4096;
+
+/// Installs the global subscriber. `RUST_LOG` takes precedence over `log.level`.
+pub fn init(log: &LogConfig) {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(format!("{},hyper=warn,h2=warn,tower=warn", log.level)));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter).with_target(true);
+
+    // Fails only if a subscriber is already installed, which is fine
+    let _ = if log.format == "json" {
+        builder.json().flatten_event(true).with_current_span(true).with_span_list(false).try_init()
+    } else {
+        builder.try_init()
+    };
+}
+
+/// Accepts a caller's ID if it looks harmless, otherwise makes a new one.
+fn request_id(request: &Request) -> String {
+    request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= REQUEST_ID_MAX_LEN
+                && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{:016x}", rand::thread_rng().gen::<u64>()))
+}
+
+fn is_probe(path: &str) -> bool {
+    matches!(path, "/healthz" | "/readyz" | "/metrics")
+}
+
+pub async fn trace_request(request: Request, next: Next) -> Response {
+    let id = request_id(&request);
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let span = tracing::info_span!("request", request_id = %id, %method, %path);
+
+    async move {
+        let started = Instant::now();
+        let response = next.run(request).await;
+        let status = response.status();
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+
+        // Server functions answer errors with a short text body: log it, then pass it on
+        let mut response = if status.is_server_error() || (status.is_client_error() && path.starts_with("/api/")) {
+            let (parts, body) = response.into_parts();
+            let small = parts
+                .headers
+                .get(header::CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok()?.parse::<usize>().ok())
+                .is_some_and(|len| len <= ERROR_BODY_LOG_LIMIT);
+            let (message, body) = if small {
+                let bytes = to_bytes(body, ERROR_BODY_LOG_LIMIT).await.unwrap_or_default();
+                (String::from_utf8_lossy(&bytes).into_owned(), Body::from(bytes))
+            } else {
+                (String::new(), body)
+            };
+            if status.is_server_error() {
+                tracing::error!(status = status.as_u16(), elapsed_ms, error = %message, "request failed");
+            } else {
+                tracing::warn!(status = status.as_u16(), elapsed_ms, error = %message, "request rejected");
+            }
+            Response::from_parts(parts, body)
+        } else {
+            if is_probe(&path) {
+                tracing::debug!(status = status.as_u16(), elapsed_ms, "request finished");
+            } else {
+                tracing::info!(status = status.as_u16(), elapsed_ms, "request finished");
+            }
+            response
+        };
+
+        if let Ok(value) = HeaderValue::from_str(&id) {
+            response.headers_mut().insert(REQUEST_ID_HEADER, value);
+        }
+        response
+    }
+    .instrument(span)
+    .await
+}
@@ -0,0 +1,131 @@
+use dioxus::prelude::*;
+use crate::models::auth::{ApiToken, IssueApiTokenRequest, IssuedApiToken};
+
+#[cfg(feature = "server")]
+use super::{db, security};
+#[cfg(feature = "server")]
+use crate::models::auth::{ALL_SCOPES, SCOPE_ADMIN};
+
+pub const MAX_TOKEN_LIFETIME_DAYS: i32 = 365;
+
+#[cfg(feature = "server")]
+async fn fetch_tokens(pool: &sqlx::PgPool, id: Option<i64>) -> Result<Vec<ApiToken>, sqlx::Error> {
+    sqlx::query_as!(
+        ApiToken,
+        r#"
+        SELECT t.id, u.username, t.name, t.kind, t.token_prefix, t.scopes,
+               t.created_at, t.expires_at, t.last_used_at, t.revoked_at
+        FROM auth.api_tokens t
+        JOIN auth.users u ON u.id = t.user_id
+        WHERE $1::BIGINT IS NULL OR t.id = $1
+        ORDER BY t.created_at DESC
+        "#,
+        id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+#[server]
+pub async fn list_api_tokens() -> Result<Vec<ApiToken>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        fetch_tokens(&pool, None)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Issues a new token. The raw secret is only returned here and never stored.
+#[server]
+pub async fn issue_api_token(req: IssueApiTokenRequest) -> Result<IssuedApiToken, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        let admin = security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        let invalid = |message: String| ServerFnError::ServerError { message, code: 422, details: None };
+        if req.name.trim().is_empty() || req.name.len() > 100 {
+            return Err(invalid("Token name must be between 1 and 100 characters".to_string()));
+        }
+        if req.scopes.is_empty() {
+            return Err(invalid("Select at least one scope".to_string()));
+        }
+        if let Some(unknown) = req.scopes.iter().find(|s| !ALL_SCOPES.contains(&s.as_str())) {
+            return Err(invalid(format!("Unknown scope '{}'", unknown)));
+        }
+        if !(1..=MAX_TOKEN_LIFETIME_DAYS).contains(&req.expires_in_days) {
+            return Err(invalid(format!("Expiry must be between 1 and {} days", MAX_TOKEN_LIFETIME_DAYS)));
+        }
+
+        let (secret, prefix, hash) = security::new_api_token();
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO auth.api_tokens
+                (user_id, name, kind, token_prefix, token_hash, scopes, created_by, expires_at)
+            SELECT u.id, $2, $3, $4, $5, $6, $7, now() + make_interval(days => $8)
+            FROM auth.users u
+            WHERE u.username = $1
+            RETURNING id
+            "#,
+            req.username.trim(),
+            req.name.trim(),
+            req.kind.as_str(),
+            prefix,
+            hash,
+            &req.scopes,
+            admin.user_id,
+            req.expires_in_days
+        )
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| invalid(format!("Unknown user '{}'", req.username)))?;
+
+        let token = fetch_tokens(&pool, Some(id))
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ServerFnError::new("Issued token not found"))?;
+
+        Ok(IssuedApiToken { token, secret })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+#[server]
+pub async fn revoke_api_token(id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query!(
+            "UPDATE auth.api_tokens SET revoked_at = now() WHERE id = $1 AND revoked_at IS NULL",
+            id
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
@@ -0,0 +1,531 @@
+use dioxus::prelude::*;
+use crate::models::auth::CurrentUser;
+use crate::models::two_factor::{RolePolicy, TotpConfirmation, TotpEnrollment, TwoFactorStatus};
+
+#[cfg(feature = "server")]
+use super::{db, login_guard, security, totp};
+#[cfg(feature = "server")]
+use crate::models::{auth::SCOPE_ADMIN, two_factor::RECOVERY_CODE_COUNT};
+#[cfg(feature = "server")]
+use sqlx::PgPool;
+
+#[cfg(feature = "server")]
+pub const PURPOSE_VERIFY: &str = "verify";
+#[cfg(feature = "server")]
+pub const PURPOSE_ENROLL: &str = "enroll";
+
+// A challenge is good for a few minutes and a few wrong codes
+#[cfg(feature = "server")]
+const CHALLENGE_TTL_MINUTES: i32 = 5;
+#[cfg(feature = "server")]
+const CHALLENGE_MAX_ATTEMPTS: i32 = 5;
+#[cfg(feature = "server")]
+const RECOVERY_CODE_LEN: usize = 10;
+
+#[cfg(feature = "server")]
+fn invalid_code() -> ServerFnError {
+    ServerFnError::ServerError { message: "Invalid code".to_string(), code: 422, details: None }
+}
+
+#[cfg(feature = "server")]
+fn expired_challenge() -> ServerFnError {
+    ServerFnError::ServerError { message: "Login expired, please sign in again".to_string(), code: 401, details: None }
+}
+
+#[cfg(feature = "server")]
+fn db_error(e: sqlx::Error) -> ServerFnError {
+    ServerFnError::new(e.to_string())
+}
+
+/// `(enrolled, required)` for a user: whether 2FA is active and whether one of
+/// their roles demands it.
+#[cfg(feature = "server")]
+pub async fn state(pool: &PgPool, user_id: i64) -> Result<(bool, bool), sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            EXISTS (SELECT 1 FROM auth.user_totp WHERE user_id = $1 AND confirmed_at IS NOT NULL) AS "enrolled!",
+            EXISTS (
+                SELECT 1 FROM auth.user_roles ur
+                JOIN auth.roles r ON r.id = ur.role_id
+                WHERE ur.user_id = $1 AND r.require_two_factor
+            ) AS "required!"
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok((row.enrolled, row.required))
+}
+
+/// Issues a challenge for the second login step and returns its raw token.
+#[cfg(feature = "server")]
+pub async fn create_challenge(pool: &PgPool, user_id: i64, purpose: &str) -> Result<String, sqlx::Error> {
+    let raw = security::generate_secret(48);
+    sqlx::query!(
+        r#"
+        INSERT INTO auth.login_challenges (user_id, token_hash, purpose, expires_at)
+        VALUES ($1, $2, $3, now() + make_interval(mins => $4))
+        "#,
+        user_id,
+        security::hash_token(&raw),
+        purpose,
+        CHALLENGE_TTL_MINUTES
+    )
+    .execute(pool)
+    .await?;
+    Ok(raw)
+}
+
+#[cfg(feature = "server")]
+struct Challenge {
+    id: i64,
+    user_id: i64,
+    username: String,
+}
+
+#[cfg(feature = "server")]
+async fn load_challenge(pool: &PgPool, raw: &str, purpose: &str) -> Result<Challenge, ServerFnError> {
+    sqlx::query_as!(
+        Challenge,
+        r#"
+        SELECT c.id, c.user_id, u.username
+        FROM auth.login_challenges c
+        JOIN auth.users u ON u.id = c.user_id
+        WHERE c.token_hash = $1 AND c.purpose = $2
+          AND c.expires_at > now() AND c.attempts < $3
+          AND u.enabled AND (u.locked_until IS NULL OR u.locked_until <= now())
+        "#,
+        security::hash_token(raw),
+        purpose,
+        CHALLENGE_MAX_ATTEMPTS
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(db_error)?
+    .ok_or_else(expired_challenge)
+}
+
+/// The user setting up 2FA: from an enrollment challenge during login, or the
+/// signed-in user otherwise.
+#[cfg(feature = "server")]
+async fn enrolling_user(pool: &PgPool, challenge: Option<&str>) -> Result<(i64, String, Option<i64>), ServerFnError> {
+    match challenge {
+        Some(raw) => {
+            let c = load_challenge(pool, raw, PURPOSE_ENROLL).await?;
+            Ok((c.user_id, c.username, Some(c.id)))
+        }
+        None => {
+            let principal = security::require_session(pool).await?;
+            Ok((principal.user_id, principal.username, None))
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_lowercase()
+}
+
+/// Replaces all recovery codes of a user and returns the new ones (`xxxxx-xxxxx`).
+#[cfg(feature = "server")]
+async fn replace_recovery_codes<'e, E>(executor: E, user_id: i64) -> Result<Vec<String>, sqlx::Error>
+where
+    E: sqlx::Acquire<'e, Database = sqlx::Postgres>,
+{
+    let mut conn = executor.acquire().await?;
+    let codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let raw = security::generate_secret(RECOVERY_CODE_LEN).to_lowercase();
+            format!("{}-{}", &raw[..RECOVERY_CODE_LEN / 2], &raw[RECOVERY_CODE_LEN / 2..])
+        })
+        .collect();
+    let hashes: Vec<String> = codes.iter().map(|c| security::hash_token(&normalize_recovery_code(c))).collect();
+
+    sqlx::query!("DELETE FROM auth.recovery_codes WHERE user_id = $1", user_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query!(
+        "INSERT INTO auth.recovery_codes (user_id, code_hash) SELECT $1, UNNEST($2::TEXT[])",
+        user_id,
+        &hashes
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(codes)
+}
+
+/// Checks a TOTP code against the user's confirmed secret and marks its step as used.
+#[cfg(feature = "server")]
+async fn check_totp(pool: &PgPool, user_id: i64, code: &str) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query!(
+        "SELECT secret, last_used_step FROM auth.user_totp WHERE user_id = $1 AND confirmed_at IS NOT NULL",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(step) = row.and_then(|r| totp::verify(&r.secret, code, r.last_used_step)) else {
+        return Ok(false);
+    };
+
+    // Conditional update, so two requests racing with the same code can't both win
+    let updated = sqlx::query!(
+        "UPDATE auth.user_totp SET last_used_step = $2 WHERE user_id = $1 AND last_used_step < $2",
+        user_id,
+        step
+    )
+    .execute(pool)
+    .await?;
+    Ok(updated.rows_affected() == 1)
+}
+
+/// Marks a matching unused recovery code as used. The outer `used_at IS NULL`
+/// is re-checked on the locked row, so of two requests racing with the same
+/// code only one updates it; the other sees no row and fails.
+#[cfg(feature = "server")]
+async fn use_recovery_code(pool: &PgPool, user_id: i64, code: &str) -> Result<bool, sqlx::Error> {
+    let used = sqlx::query!(
+        r#"
+        UPDATE auth.recovery_codes SET used_at = now()
+        WHERE id = (
+            SELECT id FROM auth.recovery_codes
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            LIMIT 1
+        )
+        AND used_at IS NULL
+        "#,
+        user_id,
+        security::hash_token(&normalize_recovery_code(code))
+    )
+    .execute(pool)
+    .await?;
+    Ok(used.rows_affected() == 1)
+}
+
+/// Second login step: a TOTP code or one of the recovery codes.
+#[server]
+pub async fn verify_second_factor(challenge: String, code: String) -> Result<CurrentUser, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+
+        let c = load_challenge(&pool, &challenge, PURPOSE_VERIFY).await?;
+        let attempt_name = login_guard::normalize(&c.username);
+        let ip = login_guard::client_ip(&security::request_headers(), security::request_peer());
+        login_guard::check(&pool, &attempt_name, &ip).await?;
+
+        let valid = check_totp(&pool, c.user_id, &code).await.map_err(db_error)?
+            || use_recovery_code(&pool, c.user_id, &code).await.map_err(db_error)?;
+
+        if !valid {
+            sqlx::query!("UPDATE auth.login_challenges SET attempts = attempts + 1 WHERE id = $1", c.id)
+                .execute(&pool)
+                .await
+                .map_err(db_error)?;
+            // Wrong codes count towards the lockout like wrong passwords
+            if let Some(until) = login_guard::record_failure(&pool, &attempt_name, &ip, Some(c.user_id))
+                .await
+                .map_err(db_error)?
+            {
+                sqlx::query!("DELETE FROM auth.login_challenges WHERE id = $1", c.id)
+                    .execute(&pool)
+                    .await
+                    .map_err(db_error)?;
+                return Err(login_guard::locked(until));
+            }
+            return Err(invalid_code());
+        }
+
+        sqlx::query!("DELETE FROM auth.login_challenges WHERE id = $1", c.id)
+            .execute(&pool)
+            .await
+            .map_err(db_error)?;
+        login_guard::record_success(&pool, &attempt_name, &ip, c.user_id)
+            .await
+            .map_err(db_error)?;
+
+        security::start_session(&pool, c.user_id, c.username).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Starts (or restarts) setting up an authenticator. Pass the login challenge when
+/// enrollment is required before signing in, `None` when signed in already.
+#[server]
+pub async fn begin_totp_enrollment(challenge: Option<String>) -> Result<TotpEnrollment, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+
+        let (user_id, username, _) = enrolling_user(&pool, challenge.as_deref()).await?;
+        let secret = totp::generate_secret();
+
+        // Only an unconfirmed secret may be replaced, an active one has to be disabled first
+        let stored = sqlx::query!(
+            r#"
+            INSERT INTO auth.user_totp (user_id, secret) VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+                SET secret = EXCLUDED.secret, last_used_step = 0, created_at = now()
+                WHERE auth.user_totp.confirmed_at IS NULL
+            "#,
+            user_id,
+            secret
+        )
+        .execute(&pool)
+        .await
+        .map_err(db_error)?;
+        if stored.rows_affected() == 0 {
+            return Err(ServerFnError::ServerError {
+                message: "Two-factor authentication is already enabled".to_string(),
+                code: 409,
+                details: None,
+            });
+        }
+
+        let otpauth_uri = totp::provisioning_uri(&username, &secret);
+        let qr_svg = totp::qr_svg(&otpauth_uri).map_err(ServerFnError::new)?;
+        Ok(TotpEnrollment { secret, otpauth_uri, qr_svg })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Activates 2FA with a first code from the app and hands out the recovery codes.
+/// During a login this also starts the session.
+#[server]
+pub async fn confirm_totp_enrollment(challenge: Option<String>, code: String) -> Result<TotpConfirmation, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+
+        let (user_id, username, challenge_id) = enrolling_user(&pool, challenge.as_deref()).await?;
+
+        let pending = sqlx::query!(
+            "SELECT secret FROM auth.user_totp WHERE user_id = $1 AND confirmed_at IS NULL",
+            user_id
+        )
+        .fetch_optional(&pool)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ServerFnError::ServerError {
+            message: "Start the setup first".to_string(),
+            code: 409,
+            details: None,
+        })?;
+
+        let Some(step) = totp::verify(&pending.secret, &code, 0) else {
+            if let Some(id) = challenge_id {
+                sqlx::query!("UPDATE auth.login_challenges SET attempts = attempts + 1 WHERE id = $1", id)
+                    .execute(&pool)
+                    .await
+                    .map_err(db_error)?;
+            }
+            return Err(invalid_code());
+        };
+
+        let mut tx = pool.begin().await.map_err(db_error)?;
+        sqlx::query!(
+            "UPDATE auth.user_totp SET confirmed_at = now(), last_used_step = $2 WHERE user_id = $1",
+            user_id,
+            step
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let recovery_codes = replace_recovery_codes(&mut *tx, user_id).await.map_err(db_error)?;
+        if let Some(id) = challenge_id {
+            sqlx::query!("DELETE FROM auth.login_challenges WHERE id = $1", id)
+                .execute(&mut *tx)
+                .await
+                .map_err(db_error)?;
+        }
+        tx.commit().await.map_err(db_error)?;
+
+        let user = match challenge_id {
+            Some(_) => {
+                let ip = login_guard::client_ip(&security::request_headers(), security::request_peer());
+                login_guard::record_success(&pool, &login_guard::normalize(&username), &ip, user_id)
+                    .await
+                    .map_err(db_error)?;
+                Some(security::start_session(&pool, user_id, username).await?)
+            }
+            None => None,
+        };
+
+        Ok(TotpConfirmation { recovery_codes, user })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+#[server]
+pub async fn two_factor_status() -> Result<TwoFactorStatus, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        let principal = security::require_session(&pool).await?;
+
+        let (enabled, required) = state(&pool, principal.user_id).await.map_err(db_error)?;
+        let recovery_codes_left = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM auth.recovery_codes WHERE user_id = $1 AND used_at IS NULL"#,
+            principal.user_id
+        )
+        .fetch_one(&pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(TwoFactorStatus { enabled, required, recovery_codes_left })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// New set of recovery codes, the old ones stop working. Needs a current TOTP code.
+#[server]
+pub async fn regenerate_recovery_codes(code: String) -> Result<Vec<String>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        let principal = security::require_session(&pool).await?;
+
+        if !check_totp(&pool, principal.user_id, &code).await.map_err(db_error)? {
+            return Err(invalid_code());
+        }
+        replace_recovery_codes(&pool, principal.user_id).await.map_err(db_error)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Turns 2FA off for the signed-in user, unless a role requires it.
+#[server]
+pub async fn disable_totp(current_password: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        let principal = security::require_session(&pool).await?;
+
+        let (_, required) = state(&pool, principal.user_id).await.map_err(db_error)?;
+        if required {
+            return Err(ServerFnError::ServerError {
+                message: "Your role requires two-factor authentication".to_string(),
+                code: 409,
+                details: None,
+            });
+        }
+
+        let hash = sqlx::query_scalar!("SELECT password FROM auth.users WHERE id = $1", principal.user_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(db_error)?;
+        if !security::verify_password(&current_password, Some(&hash)).await? {
+            return Err(ServerFnError::ServerError {
+                message: "Current password is incorrect".to_string(),
+                code: 422,
+                details: None,
+            });
+        }
+
+        reset(&pool, principal.user_id).await.map_err(db_error)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+#[cfg(feature = "server")]
+async fn reset(pool: &PgPool, user_id: i64) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query!("DELETE FROM auth.user_totp WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query!("DELETE FROM auth.recovery_codes WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await
+}
+
+// ---------------------------------------------------------------------------
+// Admin
+// ---------------------------------------------------------------------------
+
+#[server]
+pub async fn list_role_policies() -> Result<Vec<RolePolicy>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query_as!(RolePolicy, "SELECT name, require_two_factor FROM auth.roles ORDER BY name")
+            .fetch_all(&pool)
+            .await
+            .map_err(db_error)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Users of a role that requires 2FA have to set it up at their next login.
+#[server]
+pub async fn set_role_two_factor(role: String, required: bool) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        let result = sqlx::query!("UPDATE auth.roles SET require_two_factor = $2 WHERE name = $1", role, required)
+            .execute(&pool)
+            .await
+            .map_err(db_error)?;
+        if result.rows_affected() == 0 {
+            return Err(ServerFnError::ServerError { message: "Role not found".to_string(), code: 404, details: None });
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// For users who lost their authenticator and recovery codes. If a role requires
+/// 2FA they set it up again at their next login.
+#[server]
+pub async fn reset_user_two_factor(user_id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        reset(&pool, user_id).await.map_err(db_error)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
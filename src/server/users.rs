@@ -0,0 +1,117 @@
+use dioxus::prelude::*;
+use crate::models::auth::UserAccount;
+
+#[cfg(feature = "server")]
+use super::{db, login_guard, security};
+#[cfg(feature = "server")]
+use crate::models::auth::SCOPE_ADMIN;
+
+#[server]
+pub async fn list_users() -> Result<Vec<UserAccount>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query_as!(
+            UserAccount,
+            r#"
+            SELECT u.id, u.username, u.email, u.enabled,
+                   COALESCE(ARRAY_AGG(r.name ORDER BY r.name) FILTER (WHERE r.name IS NOT NULL), '{}') AS "roles!",
+                   (t.confirmed_at IS NOT NULL) AS "two_factor_enabled!",
+                   CASE WHEN u.last_failed_login_at > now() - make_interval(mins => $1) OR u.locked_until > now()
+                        THEN u.failed_logins ELSE 0 END AS "failed_logins!",
+                   u.locked_until, u.last_login_at
+            FROM auth.users u
+            LEFT JOIN auth.user_totp t ON t.user_id = u.id
+            LEFT JOIN auth.user_roles ur ON ur.user_id = u.id
+            LEFT JOIN auth.roles r ON r.id = ur.role_id
+            GROUP BY u.id, t.confirmed_at
+            ORDER BY u.username
+            "#,
+            login_guard::WINDOW_MINUTES
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Lifts a temporary lock and resets the failure counter. Leaves `enabled` alone.
+#[server]
+pub async fn unlock_user(id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        let username = sqlx::query_scalar!(
+            "UPDATE auth.users SET failed_logins = 0, locked_until = NULL WHERE id = $1 RETURNING username",
+            id
+        )
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| ServerFnError::ServerError { message: "User not found".to_string(), code: 404, details: None })?;
+
+        // Otherwise the progressive delay would still hold the user back
+        sqlx::query!(
+            "DELETE FROM auth.login_attempts WHERE username = lower($1) AND NOT succeeded",
+            username
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Enables or disables an account. Disabling also ends its sessions.
+#[server]
+pub async fn set_user_enabled(id: i64, enabled: bool) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        let admin = security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        if admin.user_id == id && !enabled {
+            return Err(ServerFnError::ServerError {
+                message: "You cannot disable your own account".to_string(),
+                code: 409,
+                details: None,
+            });
+        }
+
+        let result = sqlx::query!("UPDATE auth.users SET enabled = $2 WHERE id = $1", id, enabled)
+            .execute(&pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        if result.rows_affected() == 0 {
+            return Err(ServerFnError::ServerError { message: "User not found".to_string(), code: 404, details: None });
+        }
+
+        if !enabled {
+            sqlx::query!("DELETE FROM auth.sessions WHERE user_id = $1", id)
+                .execute(&pool)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
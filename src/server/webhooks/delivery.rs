@@ -0,0 +1,182 @@
+//! Webhook delivery on top of the job queue.
+//!
+//! Employee writes put a `webhook.dispatch` job into their own transaction.
+//! Dispatch fans the event out into one `webhook_deliveries` row and one
+//! `webhook.deliver` job per matching subscription; the job queue takes care
+//! of retries with exponential backoff.
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::{PgExecutor, PgPool};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::server::jobs::queue::{self, Job, JobError};
+
+pub const KIND_DISPATCH: &str = "webhook.dispatch";
+pub const KIND_DELIVER: &str = "webhook.deliver";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+pub const MAX_ATTEMPTS: i32 = 8;
+
+pub const SIGNATURE_HEADER: &str = "X-EMS-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-EMS-Timestamp";
+pub const EVENT_HEADER: &str = "X-EMS-Event";
+pub const DELIVERY_HEADER: &str = "X-EMS-Delivery";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope<'a, T: Serialize> {
+    event: &'a str,
+    occurred_at: String,
+    data: &'a T,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeliverPayload {
+    delivery_id: i64,
+}
+
+/// Records `event` in the outbox. Pass the transaction of the change that caused it.
+pub async fn enqueue_event<'e, E, T>(executor: E, event: &str, data: &T) -> Result<(), sqlx::Error>
+where
+    E: PgExecutor<'e>,
+    T: Serialize,
+{
+    let envelope = Envelope { event, occurred_at: Utc::now().to_rfc3339(), data };
+    queue::enqueue(executor, KIND_DISPATCH, &envelope).await?;
+    Ok(())
+}
+
+/// Re-queues a delivery, used by the admin "Retry" action.
+pub async fn enqueue_delivery<'e, E: PgExecutor<'e>>(executor: E, delivery_id: i64) -> Result<(), sqlx::Error> {
+    queue::enqueue_with_attempts(executor, KIND_DELIVER, &DeliverPayload { delivery_id }, MAX_ATTEMPTS).await?;
+    Ok(())
+}
+
+/// `sha256=<hex>` over `"{timestamp}.{body}"`, so a captured request can't be replayed later
+/// with a different timestamp.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+fn http_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("HTTP client for webhooks")
+    })
+}
+
+/// Handler for `webhook.dispatch` jobs.
+pub async fn dispatch(pool: &PgPool, job: &Job) -> Result<(), JobError> {
+    let event = job.payload["event"]
+        .as_str()
+        .ok_or_else(|| JobError::Fatal("dispatch payload without event".to_string()))?;
+
+    let mut tx = pool.begin().await?;
+
+    let deliveries = sqlx::query_scalar!(
+        r#"
+        INSERT INTO webhook_deliveries (subscription_id, event, payload)
+        SELECT id, $1::TEXT, $2
+        FROM webhook_subscriptions
+        WHERE enabled AND $1::TEXT = ANY(events)
+        RETURNING id
+        "#,
+        event,
+        job.payload
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for delivery_id in deliveries {
+        enqueue_delivery(&mut *tx, delivery_id).await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Handler for `webhook.deliver` jobs: one signed HTTP attempt.
+pub async fn deliver(pool: &PgPool, job: &Job) -> Result<(), JobError> {
+    let DeliverPayload { delivery_id } = job.payload()?;
+
+    let delivery = sqlx::query!(
+        r#"
+        SELECT d.id, d.event, d.payload, d.attempts, s.url, s.secret, s.enabled
+        FROM webhook_deliveries d
+        JOIN webhook_subscriptions s ON s.id = d.subscription_id
+        WHERE d.id = $1
+        "#,
+        delivery_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    // The subscription was deleted or switched off in the meantime
+    let Some(delivery) = delivery.filter(|d| d.enabled) else { return Ok(()) };
+
+    let attempt = delivery.attempts + 1;
+    let body = delivery.payload.to_string();
+    let timestamp = Utc::now().timestamp();
+    let started = Instant::now();
+
+    let result = http_client()
+        .post(&delivery.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, &delivery.event)
+        .header(DELIVERY_HEADER, delivery.id.to_string())
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(SIGNATURE_HEADER, sign(&delivery.secret, timestamp, &body))
+        .body(body)
+        .send()
+        .await;
+
+    let duration_ms = started.elapsed().as_millis().min(i32::MAX as u128) as i32;
+    let (response_status, error) = match result {
+        Ok(res) if res.status().is_success() => (Some(res.status().as_u16() as i32), None),
+        Ok(res) => (Some(res.status().as_u16() as i32), Some(format!("HTTP {}", res.status()))),
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    sqlx::query!(
+        r#"
+        INSERT INTO webhook_delivery_attempts (delivery_id, attempt, response_status, error, duration_ms)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        delivery.id,
+        attempt,
+        response_status,
+        error,
+        duration_ms
+    )
+    .execute(pool)
+    .await?;
+
+    let status = match (&error, job.is_last_attempt()) {
+        (None, _) => "succeeded",
+        (Some(_), true) => "failed",
+        (Some(_), false) => "pending",
+    };
+    sqlx::query!(
+        "UPDATE webhook_deliveries SET status = $2, attempts = $3 WHERE id = $1",
+        delivery.id,
+        status,
+        attempt
+    )
+    .execute(pool)
+    .await?;
+
+    match error {
+        None => Ok(()),
+        Some(e) => Err(JobError::Retry(e)),
+    }
+}
@@ -0,0 +1,205 @@
+use dioxus::prelude::*;
+use crate::models::webhook::{CreatedWebhook, SaveWebhookRequest, WebhookAttempt, WebhookSubscription};
+
+#[cfg(feature = "server")]
+pub mod delivery;
+
+#[cfg(feature = "server")]
+use super::{db, security};
+#[cfg(feature = "server")]
+use crate::models::{auth::SCOPE_ADMIN, webhook::ALL_EVENTS};
+
+#[cfg(feature = "server")]
+fn validate(req: &SaveWebhookRequest) -> Result<(), ServerFnError> {
+    let invalid = |message: &str| ServerFnError::ServerError { message: message.to_string(), code: 422, details: None };
+
+    if req.name.trim().is_empty() || req.name.len() > 100 {
+        return Err(invalid("Name must be between 1 and 100 characters"));
+    }
+    if !(req.url.starts_with("https://") || req.url.starts_with("http://")) {
+        return Err(invalid("URL must start with http:// or https://"));
+    }
+    if req.events.is_empty() || req.events.iter().any(|e| !ALL_EVENTS.contains(&e.as_str())) {
+        return Err(invalid("Select at least one known event"));
+    }
+    Ok(())
+}
+
+#[server]
+pub async fn list_webhooks() -> Result<Vec<WebhookSubscription>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query_as!(
+            WebhookSubscription,
+            "SELECT id, name, url, events, enabled, created_at FROM webhook_subscriptions ORDER BY id"
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Creates a subscription with a generated signing secret, which is only returned here.
+#[server]
+pub async fn create_webhook(req: SaveWebhookRequest) -> Result<CreatedWebhook, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+        validate(&req)?;
+
+        let secret = security::generate_secret(48);
+        let subscription = sqlx::query_as!(
+            WebhookSubscription,
+            r#"
+            INSERT INTO webhook_subscriptions (name, url, secret, events, enabled)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, name, url, events, enabled, created_at
+            "#,
+            req.name.trim(),
+            req.url.trim(),
+            secret,
+            &req.events,
+            req.enabled
+        )
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(CreatedWebhook { subscription, secret })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+#[server]
+pub async fn update_webhook(id: i64, req: SaveWebhookRequest) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+        validate(&req)?;
+
+        sqlx::query!(
+            "UPDATE webhook_subscriptions SET name = $1, url = $2, events = $3, enabled = $4 WHERE id = $5",
+            req.name.trim(),
+            req.url.trim(),
+            &req.events,
+            req.enabled,
+            id
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+#[server]
+pub async fn delete_webhook(id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query!("DELETE FROM webhook_subscriptions WHERE id = $1", id)
+            .execute(&pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// The most recent delivery attempts across all subscriptions, newest first.
+#[server]
+pub async fn list_webhook_attempts(limit: i64) -> Result<Vec<WebhookAttempt>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        sqlx::query_as!(
+            WebhookAttempt,
+            r#"
+            SELECT a.id, a.delivery_id, s.name AS subscription_name, d.event, a.attempt,
+                   a.attempted_at, a.response_status, a.error, a.duration_ms,
+                   d.status AS delivery_status
+            FROM webhook_delivery_attempts a
+            JOIN webhook_deliveries d ON d.id = a.delivery_id
+            JOIN webhook_subscriptions s ON s.id = d.subscription_id
+            ORDER BY a.attempted_at DESC, a.id DESC
+            LIMIT $1
+            "#,
+            limit.clamp(1, 500)
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
+
+/// Puts a failed delivery back into the queue with a fresh attempt budget.
+#[server]
+pub async fn retry_webhook_delivery(delivery_id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        let pool = db::connect_db().await
+            .map_err(|e| ServerFnError::new(format!("DB Connection failed: {}", e)))?;
+        security::require_scope(&pool, SCOPE_ADMIN).await?;
+
+        let mut tx = pool.begin().await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let retried = sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'pending'
+            WHERE id = $1 AND status = 'failed'
+            "#,
+            delivery_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        if retried.rows_affected() > 0 {
+            delivery::enqueue_delivery(&mut *tx, delivery_id)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+        }
+        tx.commit().await.map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::new("Server function not available on client"))
+    }
+}
@@ -0,0 +1,22 @@
+use dioxus::prelude::*;
+
+use crate::components::auth::use_auth;
+use crate::components::tables::api_token_table::ApiTokenTable;
+
+#[component]
+pub fn ApiTokensView() -> Element {
+    let auth = use_auth();
+
+    rsx! {
+        div { class: "max-w-[80%] mx-auto mt-10",
+            div { class: "container mx-auto p-4",
+                h2 { class: "text-2xl font-bold mb-4 text-blue-500", "API Tokens" }
+                if auth.is_admin() {
+                    ApiTokenTable {}
+                } else {
+                    p { class: "text-slate-600", "Only administrators can manage API tokens." }
+                }
+            }
+        }
+    }
+}
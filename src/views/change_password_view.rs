@@ -0,0 +1,78 @@
+use dioxus::prelude::*;
+
+use crate::components::auth::new_password_fields::{new_password_ready, NewPasswordFields};
+use crate::components::auth::use_auth;
+use crate::models::auth::ChangePasswordRequest;
+
+#[component]
+pub fn ChangePasswordView() -> Element {
+    let auth = use_auth();
+    let mut current = use_signal(String::new);
+    let mut password = use_signal(String::new);
+    let mut confirm = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut pending = use_signal(|| false);
+    let mut done = use_signal(|| false);
+
+    let username = auth.user.read().as_ref().map(|u| u.username.clone());
+
+    rsx! {
+        div { class: "max-w-md mx-auto mt-10",
+            div { class: "bg-white rounded-lg shadow p-8 flex flex-col gap-4",
+                h2 { class: "text-2xl font-bold text-blue-500", "Change password" }
+
+                if let Some(username) = username {
+                    if done() {
+                        p { class: "text-emerald-700", "Your password was changed. Other sessions were signed out." }
+                    }
+                    form {
+                        class: "flex flex-col gap-4",
+                        onsubmit: move |e| async move {
+                            e.prevent_default();
+                            pending.set(true);
+                            error.set(None);
+                            done.set(false);
+                            let req = ChangePasswordRequest { current_password: current(), new_password: password() };
+                            match crate::server::password::change_password(req).await {
+                                Ok(()) => {
+                                    current.set(String::new());
+                                    password.set(String::new());
+                                    confirm.set(String::new());
+                                    done.set(true);
+                                }
+                                Err(e) => error.set(Some(e.to_string())),
+                            }
+                            pending.set(false);
+                        },
+
+                        div { class: "flex flex-col gap-1",
+                            label { class: "text-sm font-medium text-slate-700", "Current password" }
+                            input {
+                                class: "border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                r#type: "password",
+                                autocomplete: "current-password",
+                                value: "{current}",
+                                oninput: move |e| current.set(e.value())
+                            }
+                        }
+
+                        NewPasswordFields { password, confirm, username: username.clone() }
+
+                        if let Some(message) = error() {
+                            span { class: "text-sm text-red-600", "{message}" }
+                        }
+
+                        button {
+                            class: "w-full bg-blue-600 text-white py-2 rounded hover:bg-blue-700 transition font-medium disabled:opacity-50 disabled:cursor-not-allowed",
+                            r#type: "submit",
+                            disabled: pending() || current().is_empty() || !new_password_ready(&password(), &confirm(), &username),
+                            if pending() { "Saving..." } else { "Change password" }
+                        }
+                    }
+                } else {
+                    p { class: "text-slate-600", "Please log in to change your password." }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use dioxus::prelude::*;
+
+use crate::components::auth::use_auth;
+use crate::components::tables::{
+    ldap_sync_preview::LdapSyncPreviewTable, ldap_sync_run_table::LdapSyncRunTable,
+    missing_employee_table::MissingEmployeeTable,
+};
+
+#[component]
+pub fn DirectorySyncView() -> Element {
+    let auth = use_auth();
+    // Bumped after a sync was queued so history and flags reload
+    let mut refresh = use_signal(|| 0u32);
+
+    rsx! {
+        div { class: "max-w-[80%] mx-auto mt-10",
+            div { class: "container mx-auto p-4 flex flex-col gap-8",
+                h2 { class: "text-2xl font-bold text-blue-500", "Directory sync" }
+                if auth.is_admin() {
+                    LdapSyncPreviewTable { on_applied: move |_| refresh += 1 }
+                    LdapSyncRunTable { refresh }
+                    MissingEmployeeTable { refresh }
+                } else {
+                    p { class: "text-slate-600", "Only administrators can sync the directory." }
+                }
+            }
+        }
+    }
+}
@@ -5,10 +5,10 @@ use crate::components::tables::employee_table::EmployeeTable;
 #[component]
 pub fn EmployeeView() -> Element {
     rsx! {
-        // Container to center and limit width to 80%
-        div { class: "max-w-[80%] mx-auto mt-10",
-            div { class: "container mx-auto p-4",
-                        h2 { class: "text-2xl font-bold mb-4 text-blue-500", "Employee Overview" }
+        // Full width on phones, centered and limited to 80% from md up
+        div { class: "w-full md:max-w-[80%] mx-auto mt-4 md:mt-10",
+            div { class: "container mx-auto px-0 py-4 md:p-4",
+                        h2 { class: "text-2xl font-bold mb-4 px-3 md:px-0 text-blue-500", "Employee Overview" }
                 EmployeeTable {  }
             }
         }
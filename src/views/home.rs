@@ -1,12 +1,137 @@
 use dioxus::prelude::*;
 
+use crate::components::cards::stat_card::StatCard;
+#[cfg(not(feature = "offline"))]
+use crate::components::{
+    auth::use_auth,
+    charts::{bar_chart::BarChart, line_chart::LineChart, ChartPoint},
+};
+#[cfg(not(feature = "offline"))]
+use crate::server::dashboard::{get_headcount_by_department, get_headcount_trend, get_workforce_summary};
+
+#[cfg(not(feature = "offline"))]
+const TREND_MONTHS: i32 = 12;
+
 /// The Home page component that will be rendered when the current route is `[Route::Home]`
+#[cfg(not(feature = "offline"))]
+#[component]
+pub fn Home() -> Element {
+    // The metrics need a signed-in user, reading it reloads them after login/logout
+    let auth = use_auth();
+    let summary = use_resource(move || {
+        let _user = auth.user.read().clone();
+        get_workforce_summary()
+    });
+    let departments = use_resource(move || {
+        let _user = auth.user.read().clone();
+        get_headcount_by_department()
+    });
+    let trend = use_resource(move || {
+        let _user = auth.user.read().clone();
+        get_headcount_trend(TREND_MONTHS)
+    });
+
+    let summary_data = summary.read();
+    let (headcount, new_hires, leavers) = match &*summary_data {
+        Some(Ok(s)) => (Some(s.headcount), Some(s.new_hires_this_month), Some(s.leavers_this_month)),
+        _ => (None, None, None),
+    };
+
+    rsx! {
+        div { class: "max-w-[80%] mx-auto mt-10",
+            div { class: "container mx-auto p-4 flex flex-col gap-6",
+                h2 { class: "text-2xl font-bold text-blue-500", "Workforce Dashboard" }
+
+                if let Some(Err(e)) = &*summary_data {
+                    div { class: "bg-red-50 border border-red-200 text-red-700 rounded-lg px-4 py-3 text-sm",
+                        "Could not load dashboard: {e}"
+                    }
+                }
+
+                // KPI CARDS
+                div { class: "grid grid-cols-1 md:grid-cols-3 gap-4",
+                    StatCard { title: "Total headcount", value: headcount }
+                    StatCard { title: "New hires this month", value: new_hires, accent: "text-emerald-600" }
+                    StatCard { title: "Leavers this month", value: leavers, accent: "text-red-500" }
+                }
+
+                div { class: "grid grid-cols-1 lg:grid-cols-2 gap-4",
+                    // HEADCOUNT PER DEPARTMENT
+                    div { class: "bg-white rounded-lg shadow p-6 border border-blue-100",
+                        h3 { class: "text-sm font-bold text-blue-700 uppercase mb-4", "Headcount per department" }
+                        match &*departments.read() {
+                            Some(Ok(rows)) => rsx! {
+                                BarChart {
+                                    points: rows.iter().map(|d| ChartPoint { label: d.department.clone(), value: d.headcount }).collect::<Vec<_>>()
+                                }
+                            },
+                            Some(Err(e)) => rsx! { p { class: "text-sm text-red-600", "Error: {e}" } },
+                            None => rsx! { div { class: "h-48 bg-slate-100 rounded animate-pulse" } },
+                        }
+                    }
+
+                    // HEADCOUNT TREND
+                    div { class: "bg-white rounded-lg shadow p-6 border border-blue-100",
+                        h3 { class: "text-sm font-bold text-blue-700 uppercase mb-4", "Headcount trend (last {TREND_MONTHS} months)" }
+                        match &*trend.read() {
+                            Some(Ok(rows)) => rsx! {
+                                LineChart {
+                                    points: rows.iter().map(|t| ChartPoint { label: t.month.clone(), value: t.headcount }).collect::<Vec<_>>()
+                                }
+                            },
+                            Some(Err(e)) => rsx! { p { class: "text-sm text-red-600", "Error: {e}" } },
+                            None => rsx! { div { class: "h-48 bg-slate-100 rounded animate-pulse" } },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Home of the offline desktop build. There is no server for the dashboard
+/// queries, so it shows what the local file can tell: the headcount.
+#[cfg(feature = "offline")]
 #[component]
 pub fn Home() -> Element {
+    use crate::components::tables::employee_data::use_employee_backend;
+    use crate::models::employee::EmployeeRequest;
+    use crate::Route;
+
+    let backend = use_employee_backend();
+    let headcount = use_resource(move || {
+        backend.list(EmployeeRequest {
+            page: 1,
+            page_size: 1,
+            search_term: None,
+            sort_by: Default::default(),
+            sort_dir: Default::default(),
+        })
+    });
+    let value = headcount.read().as_ref().and_then(|r| r.as_ref().ok()).map(|r| r.total_count);
+    let path = crate::server::employees::sqlite::path();
+
     rsx! {
-        div { class: "flex flex-col items-center justify-center min-h-[50vh]",
-            h1 { class: "text-4xl font-bold text-blue-500", "Welcome to EMS Rust" }
-            p { class: "text-blue-700 mt-2", "Select 'View' to manage employees or login to continue." }
+        div { class: "max-w-[80%] mx-auto mt-10",
+            div { class: "container mx-auto p-4 flex flex-col gap-6",
+                h2 { class: "text-2xl font-bold text-blue-500", "Workforce Dashboard" }
+
+                if let Some(Err(e)) = &*headcount.read() {
+                    div { class: "bg-red-50 border border-red-200 text-red-700 rounded-lg px-4 py-3 text-sm",
+                        "Could not load dashboard: {e}"
+                    }
+                }
+
+                div { class: "grid grid-cols-1 md:grid-cols-3 gap-4",
+                    StatCard { title: "Total headcount", value }
+                }
+
+                p { class: "text-sm text-slate-500",
+                    "Offline mode, the employees are kept in {path.display()}. Departments and the headcount "
+                    "trend need a server. "
+                    Link { to: Route::EmployeeView {}, class: "text-blue-600 hover:underline", "Go to the employees" }
+                }
+            }
         }
     }
 }
@@ -0,0 +1,22 @@
+use dioxus::prelude::*;
+
+use crate::components::auth::use_auth;
+use crate::components::tables::job_table::JobTable;
+
+#[component]
+pub fn JobsView() -> Element {
+    let auth = use_auth();
+
+    rsx! {
+        div { class: "max-w-[80%] mx-auto mt-10",
+            div { class: "container mx-auto p-4 flex flex-col gap-8",
+                h2 { class: "text-2xl font-bold text-blue-500", "Background jobs" }
+                if auth.is_admin() {
+                    JobTable {}
+                } else {
+                    p { class: "text-slate-600", "Only administrators can inspect background jobs." }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,25 @@
 pub mod home;
 pub mod employee_view;
+pub mod api_tokens_view;
+pub mod webhooks_view;
+pub mod jobs_view;
+pub mod email_templates_view;
+pub mod reset_password_view;
+pub mod change_password_view;
+pub mod users_view;
+pub mod two_factor_view;
+pub mod directory_sync_view;
+pub mod sso_two_factor_view;
 
 pub use home::Home;
 pub use employee_view::EmployeeView;
+pub use api_tokens_view::ApiTokensView;
+pub use webhooks_view::WebhooksView;
+pub use jobs_view::JobsView;
+pub use email_templates_view::EmailTemplatesView;
+pub use reset_password_view::ResetPasswordView;
+pub use change_password_view::ChangePasswordView;
+pub use users_view::UsersView;
+pub use two_factor_view::TwoFactorView;
+pub use directory_sync_view::DirectorySyncView;
+pub use sso_two_factor_view::SsoTwoFactorView;
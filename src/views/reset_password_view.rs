@@ -0,0 +1,61 @@
+use dioxus::prelude::*;
+
+use crate::components::auth::new_password_fields::{new_password_ready, NewPasswordFields};
+use crate::models::auth::ResetPasswordRequest;
+
+/// Target of the link in the password reset mail.
+#[component]
+pub fn ResetPasswordView(token: String) -> Element {
+    let password = use_signal(String::new);
+    let confirm = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut pending = use_signal(|| false);
+    let mut done = use_signal(|| false);
+
+    rsx! {
+        div { class: "max-w-md mx-auto mt-10",
+            div { class: "bg-white rounded-lg shadow p-8 flex flex-col gap-4",
+                h2 { class: "text-2xl font-bold text-blue-500", "Choose a new password" }
+
+                if done() {
+                    p { class: "text-slate-600",
+                        "Your password was changed and all sessions were signed out. You can now log in with the new password."
+                    }
+                } else if token.is_empty() {
+                    p { class: "text-red-600", "This reset link is incomplete. Please use the link from the mail." }
+                } else {
+                    form {
+                        class: "flex flex-col gap-4",
+                        onsubmit: move |e| {
+                            let token = token.clone();
+                            async move {
+                                e.prevent_default();
+                                pending.set(true);
+                                error.set(None);
+                                let req = ResetPasswordRequest { token, new_password: password() };
+                                match crate::server::password::reset_password(req).await {
+                                    Ok(()) => done.set(true),
+                                    Err(e) => error.set(Some(e.to_string())),
+                                }
+                                pending.set(false);
+                            }
+                        },
+
+                        NewPasswordFields { password, confirm }
+
+                        if let Some(message) = error() {
+                            span { class: "text-sm text-red-600", "{message}" }
+                        }
+
+                        button {
+                            class: "w-full bg-blue-600 text-white py-2 rounded hover:bg-blue-700 transition font-medium disabled:opacity-50 disabled:cursor-not-allowed",
+                            r#type: "submit",
+                            disabled: pending() || !new_password_ready(&password(), &confirm(), ""),
+                            if pending() { "Saving..." } else { "Set new password" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
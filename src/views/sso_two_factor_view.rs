@@ -0,0 +1,25 @@
+use dioxus::prelude::*;
+
+use crate::components::auth::login_dialog::{LoginDialog, SecondStep};
+use crate::Route;
+
+/// Where the SSO callback sends users whose account needs local 2FA: the login
+/// dialog, opened at the code (or enrollment) step.
+#[component]
+pub fn SsoTwoFactorView(challenge: String, step: String) -> Element {
+    let navigator = use_navigator();
+
+    let resume = match step.as_str() {
+        "enroll" => SecondStep::Enroll { challenge },
+        _ => SecondStep::Code { challenge },
+    };
+
+    rsx! {
+        LoginDialog {
+            resume: Some(resume),
+            on_close: move |_| {
+                navigator.replace(Route::Home {});
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use dioxus::prelude::*;
+
+use crate::components::auth::totp_enrollment::{RecoveryCodes, TotpEnrollmentPanel};
+use crate::components::auth::use_auth;
+
+#[component]
+pub fn TwoFactorView() -> Element {
+    let auth = use_auth();
+    let mut enrolling = use_signal(|| false);
+    let mut new_codes = use_signal(|| None::<Vec<String>>);
+    let mut totp_code = use_signal(String::new);
+    let mut password = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let mut status_resource = use_resource(move || async move {
+        // Refetch on login changes
+        let _ = auth.user.read();
+        crate::server::two_factor::two_factor_status().await
+    });
+
+    let signed_in = auth.user.read().is_some();
+
+    rsx! {
+        div { class: "max-w-md mx-auto mt-10",
+            div { class: "bg-white rounded-lg shadow p-8 flex flex-col gap-4",
+                h2 { class: "text-2xl font-bold text-blue-500", "Two-factor authentication" }
+
+                if !signed_in {
+                    p { class: "text-slate-600", "Please log in to manage two-factor authentication." }
+                } else if let Some(codes) = new_codes() {
+                    RecoveryCodes { codes, on_saved: move |_| new_codes.set(None) }
+                } else {
+                    match &*status_resource.read() {
+                        Some(Ok(status)) if !status.enabled => rsx! {
+                            if status.required {
+                                p { class: "text-sm text-amber-700", "Your role requires two-factor authentication." }
+                            }
+                            if enrolling() {
+                                TotpEnrollmentPanel {
+                                    challenge: None,
+                                    on_done: move |_| {
+                                        enrolling.set(false);
+                                        status_resource.restart();
+                                    }
+                                }
+                            } else {
+                                p { class: "text-slate-600", "Protect your account with a code from an authenticator app in addition to your password." }
+                                button {
+                                    class: "w-full bg-blue-600 text-white py-2 rounded hover:bg-blue-700 transition font-medium",
+                                    onclick: move |_| enrolling.set(true),
+                                    "Set up two-factor authentication"
+                                }
+                            }
+                        },
+                        Some(Ok(status)) => rsx! {
+                            p { class: "text-emerald-700", "Two-factor authentication is enabled." }
+                            p { class: "text-sm text-slate-600", "Recovery codes left: {status.recovery_codes_left}" }
+
+                            // NEW RECOVERY CODES
+                            form {
+                                class: "flex flex-col gap-2 border-t pt-4",
+                                onsubmit: move |e| async move {
+                                    e.prevent_default();
+                                    error.set(None);
+                                    match crate::server::two_factor::regenerate_recovery_codes(totp_code()).await {
+                                        Ok(codes) => {
+                                            totp_code.set(String::new());
+                                            new_codes.set(Some(codes));
+                                            status_resource.restart();
+                                        }
+                                        Err(e) => error.set(Some(e.to_string())),
+                                    }
+                                },
+                                label { class: "text-sm font-medium text-slate-700", "New recovery codes" }
+                                div { class: "flex gap-2",
+                                    input {
+                                        class: "flex-1 border rounded-lg px-4 py-2 font-mono focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                        autocomplete: "one-time-code",
+                                        placeholder: "Code from your app",
+                                        value: "{totp_code}",
+                                        oninput: move |e| totp_code.set(e.value())
+                                    }
+                                    button {
+                                        class: "px-4 py-2 rounded-lg border border-slate-300 text-slate-600 hover:bg-slate-100 transition disabled:opacity-50",
+                                        r#type: "submit",
+                                        disabled: totp_code().trim().len() != 6,
+                                        "Generate"
+                                    }
+                                }
+                            }
+
+                            // DISABLE
+                            if status.required {
+                                p { class: "text-sm text-slate-500 border-t pt-4",
+                                    "Your role requires two-factor authentication, so it can't be turned off."
+                                }
+                            } else {
+                                form {
+                                    class: "flex flex-col gap-2 border-t pt-4",
+                                    onsubmit: move |e| async move {
+                                        e.prevent_default();
+                                        error.set(None);
+                                        match crate::server::two_factor::disable_totp(password()).await {
+                                            Ok(()) => {
+                                                password.set(String::new());
+                                                status_resource.restart();
+                                            }
+                                            Err(e) => error.set(Some(e.to_string())),
+                                        }
+                                    },
+                                    label { class: "text-sm font-medium text-slate-700", "Turn off with your password" }
+                                    div { class: "flex gap-2",
+                                        input {
+                                            class: "flex-1 border rounded-lg px-4 py-2 focus:ring-2 focus:ring-blue-500 focus:border-blue-500 outline-none transition",
+                                            r#type: "password",
+                                            autocomplete: "current-password",
+                                            value: "{password}",
+                                            oninput: move |e| password.set(e.value())
+                                        }
+                                        button {
+                                            class: "px-4 py-2 rounded-lg text-red-600 border border-red-200 hover:bg-red-50 transition disabled:opacity-50",
+                                            r#type: "submit",
+                                            disabled: password().is_empty(),
+                                            "Turn off"
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "text-red-600", "Could not load status: {e}" }
+                        },
+                        None => rsx! {
+                            div { class: "h-4 bg-slate-100 rounded w-full animate-pulse" }
+                        },
+                    }
+
+                    if let Some(message) = error() {
+                        span { class: "text-sm text-red-600", "{message}" }
+                    }
+                }
+            }
+        }
+    }
+}